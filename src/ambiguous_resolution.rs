@@ -0,0 +1,279 @@
+#[cfg(test)]
+mod ambiguous_resolution_tests;
+
+use crate::constants::*;
+use crate::markdown_file::{self, MarkdownFile};
+use crate::markdown_files::MarkdownFiles;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::utils;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::target_key;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// how an ambiguous display text was narrowed down to one of its targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResolutionRule {
+    /// config maps the text to the target
+    Configured,
+    /// the only target in the same folder as the note
+    SameFolder,
+    /// the only target the note already links to
+    LinkedInNote,
+    /// the target more notes link to than any other
+    MostLinked,
+}
+
+impl FromStr for ResolutionRule {
+    type Err = String;
+
+    // configured isn't a rule to list - it's what the targets mapping does
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        match rule {
+            RESOLUTION_SAME_FOLDER => Ok(ResolutionRule::SameFolder),
+            RESOLUTION_LINKED_IN_NOTE => Ok(ResolutionRule::LinkedInNote),
+            RESOLUTION_MOST_LINKED => Ok(ResolutionRule::MostLinked),
+            unknown => Err(format!("unknown rule: {}", unknown)),
+        }
+    }
+}
+
+impl fmt::Display for ResolutionRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ResolutionRule::Configured => RESOLUTION_CONFIGURED,
+            ResolutionRule::SameFolder => RESOLUTION_SAME_FOLDER,
+            ResolutionRule::LinkedInNote => RESOLUTION_LINKED_IN_NOTE,
+            ResolutionRule::MostLinked => RESOLUTION_MOST_LINKED,
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// picks a target for an ambiguous display text - the configured targets are tried first,
+/// then the rules in the order they're listed. empty leaves every ambiguous match to be fixed
+/// by hand
+#[derive(Debug, Clone, Default)]
+pub struct AmbiguousResolver {
+    /// keyed by the case folded display text
+    targets: HashMap<String, String>,
+    rules: Vec<ResolutionRule>,
+}
+
+impl AmbiguousResolver {
+    pub fn new(targets: &BTreeMap<String, String>, rules: Vec<ResolutionRule>) -> Self {
+        Self {
+            targets: targets
+                .iter()
+                .map(|(text, target)| (utils::fold_case(text), target.trim().to_string()))
+                .collect(),
+            rules,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty() && self.rules.is_empty()
+    }
+
+    /// a rule only picks a target when it singles one out - a configured target that isn't one
+    /// of the candidates is ignored
+    pub fn resolve(
+        &self,
+        display_text: &str,
+        candidates: &HashSet<String>,
+        note: &NoteLinks,
+        vault: &VaultLinks,
+    ) -> Option<(String, ResolutionRule)> {
+        let mut candidates: Vec<&String> = candidates.iter().collect();
+        candidates.sort();
+
+        let configured = self
+            .targets
+            .get(&utils::fold_case(display_text))
+            .and_then(|target| {
+                candidates
+                    .iter()
+                    .find(|candidate| utils::eq_fold_case(candidate, target))
+            });
+        if let Some(target) = configured {
+            return Some((target.to_string(), ResolutionRule::Configured));
+        }
+
+        self.rules.iter().find_map(|rule| {
+            let target = match rule {
+                ResolutionRule::Configured => None,
+                ResolutionRule::SameFolder => only_one(
+                    candidates
+                        .iter()
+                        .copied()
+                        .filter(|candidate| vault.is_in_folder(candidate, &note.folder)),
+                ),
+                ResolutionRule::LinkedInNote => only_one(
+                    candidates
+                        .iter()
+                        .copied()
+                        .filter(|candidate| note.links_to(candidate)),
+                ),
+                ResolutionRule::MostLinked => vault.most_linked(&candidates),
+            }?;
+            Some((target.to_string(), *rule))
+        })
+    }
+}
+
+fn only_one<T>(mut items: impl Iterator<Item = T>) -> Option<T> {
+    let first = items.next()?;
+    items.next().is_none().then_some(first)
+}
+
+/// what the rules know about the note being back populated
+pub struct NoteLinks {
+    folder: PathBuf,
+    /// the targets the note's content links to
+    linked: HashSet<String>,
+}
+
+impl NoteLinks {
+    /// the note's own name and aliases are among its valid wikilinks so they're left out
+    pub fn new(markdown_file: &MarkdownFile) -> Self {
+        let own_key = markdown_file
+            .path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(target_key)
+            .unwrap_or_default();
+
+        Self {
+            folder: markdown_file
+                .path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            linked: markdown_file
+                .wikilinks
+                .valid
+                .iter()
+                .map(|wikilink| target_key(&wikilink.target))
+                .filter(|key| *key != own_key)
+                .collect(),
+        }
+    }
+
+    fn links_to(&self, target: &str) -> bool {
+        self.linked.contains(&target_key(target))
+    }
+}
+
+/// what the rules know about the whole vault
+pub struct VaultLinks {
+    /// the folders a note with that name is in
+    folders: HashMap<String, HashSet<PathBuf>>,
+    /// how many notes link to each note
+    link_counts: HashMap<String, usize>,
+}
+
+impl VaultLinks {
+    pub fn new(markdown_files: &MarkdownFiles) -> Self {
+        let mut folders: HashMap<String, HashSet<PathBuf>> = HashMap::new();
+        let mut link_counts: HashMap<String, usize> = HashMap::new();
+
+        for markdown_file in markdown_files.iter() {
+            if let (Some(stem), Some(folder)) = (
+                markdown_file
+                    .path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str()),
+                markdown_file.path.parent(),
+            ) {
+                folders
+                    .entry(target_key(stem))
+                    .or_default()
+                    .insert(folder.to_path_buf());
+            }
+            for key in NoteLinks::new(markdown_file).linked {
+                *link_counts.entry(key).or_default() += 1;
+            }
+        }
+
+        Self {
+            folders,
+            link_counts,
+        }
+    }
+
+    fn is_in_folder(&self, target: &str, folder: &Path) -> bool {
+        self.folders
+            .get(&target_key(target))
+            .is_some_and(|folders| folders.contains(folder))
+    }
+
+    /// None on a tie or when nothing links to any of them
+    fn most_linked<'a>(&self, candidates: &[&'a String]) -> Option<&'a String> {
+        let count = |target: &str| {
+            self.link_counts
+                .get(&target_key(target))
+                .copied()
+                .unwrap_or(0)
+        };
+        let mut by_count: Vec<(&String, usize)> = candidates
+            .iter()
+            .map(|candidate| (*candidate, count(candidate)))
+            .collect();
+        by_count.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+
+        match by_count.as_slice() {
+            [(target, count), (_, runner_up), ..] if count > runner_up => Some(target),
+            [(target, count)] if *count > 0 => Some(target),
+            _ => None,
+        }
+    }
+}
+
+impl ObsidianRepository {
+    /// moves the ambiguous matches a target can be picked for back to the unambiguous ones,
+    /// linking to that target
+    pub fn resolve_ambiguous_matches(&mut self, validated_config: &ValidatedConfig) {
+        let resolver = validated_config.ambiguous_resolver();
+        if resolver.is_empty() {
+            return;
+        }
+
+        let targets_by_text = self.targets_by_display_text();
+        let vault = VaultLinks::new(&self.markdown_files);
+
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file.has_ambiguous_matches() {
+                continue;
+            }
+
+            let note = NoteLinks::new(markdown_file);
+            let mut resolutions: HashMap<String, Option<(String, ResolutionRule)>> = HashMap::new();
+
+            for mut ambiguous in std::mem::take(&mut markdown_file.matches.ambiguous) {
+                let resolution = resolutions
+                    .entry(utils::fold_case(&ambiguous.found_text))
+                    .or_insert_with_key(|text| {
+                        targets_by_text.get(text).and_then(|candidates| {
+                            resolver.resolve(text, candidates, &note, &vault)
+                        })
+                    });
+
+                match resolution {
+                    Some((target, rule)) => {
+                        ambiguous.replacement = markdown_file::back_populate_replacement(
+                            target,
+                            &ambiguous.found_text,
+                            ambiguous.in_markdown_table,
+                        );
+                        ambiguous.target = target.clone();
+                        ambiguous.resolved_by = Some(*rule);
+                        markdown_file.matches.unambiguous.push(ambiguous);
+                    }
+                    None => markdown_file.matches.ambiguous.push(ambiguous),
+                }
+            }
+        }
+    }
+}
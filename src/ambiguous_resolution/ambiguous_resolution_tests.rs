@@ -0,0 +1,185 @@
+use super::*;
+use crate::config::AmbiguousConfig;
+use crate::markdown_file::BackPopulateMatch;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use std::fs;
+use tempfile::TempDir;
+
+/// two notes share the alias Amazon - in the folders given, "" being the vault itself
+fn create_amazon_notes(temp_dir: &TempDir, company_folder: &str, river_folder: &str) {
+    for (folder, name) in [
+        (company_folder, "Amazon (company)"),
+        (river_folder, "Amazon (river)"),
+    ] {
+        fs::create_dir_all(temp_dir.path().join(folder)).unwrap();
+        TestFileBuilder::new()
+            .with_content(format!("# {}", name))
+            .with_aliases(vec!["Amazon".to_string()])
+            .create(
+                temp_dir,
+                Path::new(folder)
+                    .join(format!("{}.md", name))
+                    .to_str()
+                    .unwrap(),
+            );
+    }
+}
+
+fn resolve_matches(
+    temp_dir: &TempDir,
+    targets: &[(&str, &str)],
+    rules: &[ResolutionRule],
+    note: &str,
+) -> (Vec<BackPopulateMatch>, Vec<BackPopulateMatch>) {
+    let resolver = AmbiguousResolver::new(
+        &targets
+            .iter()
+            .map(|(text, target)| (text.to_string(), target.to_string()))
+            .collect(),
+        rules.to_vec(),
+    );
+    let config = validated_config_tests::get_test_validated_config_result(temp_dir, |builder| {
+        builder.ambiguous_resolver(resolver);
+    })
+    .unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let file = repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with(note))
+        .unwrap();
+
+    (
+        file.matches.unambiguous.clone(),
+        file.matches.ambiguous.clone(),
+    )
+}
+
+fn assert_resolved(
+    (unambiguous, ambiguous): (Vec<BackPopulateMatch>, Vec<BackPopulateMatch>),
+    replacement: &str,
+    rule: ResolutionRule,
+) {
+    assert!(ambiguous.is_empty(), "{:?}", ambiguous);
+    let resolved: Vec<_> = unambiguous
+        .iter()
+        .filter(|m| m.found_text == "Amazon")
+        .collect();
+    assert_eq!(resolved.len(), 1, "{:?}", unambiguous);
+    assert_eq!(resolved[0].replacement, replacement);
+    assert_eq!(resolved[0].resolved_by, Some(rule));
+}
+
+#[test]
+fn test_parse_rules() {
+    let config = AmbiguousConfig {
+        rules: Some(vec!["same_folder".to_string(), " most_linked ".to_string()]),
+        targets: None,
+    };
+    assert!(!config.to_resolver().unwrap().is_empty());
+
+    let config = AmbiguousConfig {
+        rules: Some(vec!["configured".to_string()]),
+        targets: None,
+    };
+    assert!(config.to_resolver().is_err());
+
+    assert!(AmbiguousConfig::default().to_resolver().unwrap().is_empty());
+}
+
+#[test]
+fn test_configured_target() {
+    let temp_dir = TempDir::new().unwrap();
+    create_amazon_notes(&temp_dir, "", "");
+    TestFileBuilder::new()
+        .with_content("Amazon is huge")
+        .create(&temp_dir, "note.md");
+
+    assert_resolved(
+        resolve_matches(&temp_dir, &[("AMAZON", "amazon (river)")], &[], "note.md"),
+        "[[Amazon (river)|Amazon]]",
+        ResolutionRule::Configured,
+    );
+
+    // a target that isn't one of the candidates leaves it to the rules
+    let (_, ambiguous) =
+        resolve_matches(&temp_dir, &[("Amazon", "Amazon (jungle)")], &[], "note.md");
+    assert_eq!(ambiguous.len(), 1);
+}
+
+#[test]
+fn test_same_folder() {
+    let temp_dir = TempDir::new().unwrap();
+    create_amazon_notes(&temp_dir, "companies", "places");
+    TestFileBuilder::new()
+        .with_content("Amazon is huge")
+        .create(&temp_dir, "places/note.md");
+
+    assert_resolved(
+        resolve_matches(&temp_dir, &[], &[ResolutionRule::SameFolder], "note.md"),
+        "[[Amazon (river)|Amazon]]",
+        ResolutionRule::SameFolder,
+    );
+}
+
+#[test]
+fn test_linked_in_note() {
+    let temp_dir = TempDir::new().unwrap();
+    create_amazon_notes(&temp_dir, "", "");
+    TestFileBuilder::new()
+        .with_content("[[Amazon (company)|the company]] sells books\nAmazon is huge")
+        .create(&temp_dir, "note.md");
+
+    let rules = [ResolutionRule::SameFolder, ResolutionRule::LinkedInNote];
+    assert_resolved(
+        resolve_matches(&temp_dir, &[], &rules, "note.md"),
+        "[[Amazon (company)|Amazon]]",
+        ResolutionRule::LinkedInNote,
+    );
+}
+
+#[test]
+fn test_most_linked() {
+    let temp_dir = TempDir::new().unwrap();
+    create_amazon_notes(&temp_dir, "", "");
+    TestFileBuilder::new()
+        .with_content("Amazon is huge")
+        .create(&temp_dir, "note.md");
+    TestFileBuilder::new()
+        .with_content("[[Amazon (river)]]")
+        .create(&temp_dir, "a.md");
+
+    // a tie picks nothing
+    TestFileBuilder::new()
+        .with_content("[[Amazon (company)]]")
+        .create(&temp_dir, "b.md");
+    let (unambiguous, ambiguous) =
+        resolve_matches(&temp_dir, &[], &[ResolutionRule::MostLinked], "note.md");
+    assert!(unambiguous.is_empty());
+    assert_eq!(ambiguous.len(), 1);
+
+    TestFileBuilder::new()
+        .with_content("see [[Amazon (river)|the river]]")
+        .create(&temp_dir, "c.md");
+    assert_resolved(
+        resolve_matches(&temp_dir, &[], &[ResolutionRule::MostLinked], "note.md"),
+        "[[Amazon (river)|Amazon]]",
+        ResolutionRule::MostLinked,
+    );
+}
+
+#[test]
+fn test_resolved_in_table() {
+    let temp_dir = TempDir::new().unwrap();
+    create_amazon_notes(&temp_dir, "", "");
+    TestFileBuilder::new()
+        .with_content("| name | length |\n| --- | --- |\n| Amazon | long |")
+        .create(&temp_dir, "note.md");
+
+    assert_resolved(
+        resolve_matches(&temp_dir, &[("amazon", "Amazon (river)")], &[], "note.md"),
+        r"[[Amazon (river)\|Amazon]]",
+        ResolutionRule::Configured,
+    );
+}
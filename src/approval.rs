@@ -0,0 +1,310 @@
+#[cfg(test)]
+mod approval_tests;
+
+use crate::constants::*;
+use crate::frontmatter_repair::FrontmatterRepair;
+use crate::image_file::ImageFile;
+use crate::markdown_file::{
+    BackPopulateMatch, DateCreatedFixValidation, DateValidation, ImageLink, ImageLinkState,
+    MarkdownFile, SimplifyWikilinkMatch,
+};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::property_operations::PropertyChange;
+use crate::property_types::PropertyTypeMismatch;
+use crate::schema::{self, SchemaViolation};
+use lazy_static::lazy_static;
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+lazy_static! {
+    static ref CHECKED_CHANGE_ID: Regex = Regex::new(&format!(
+        r"\[([ xX])\]\s+({}[0-9a-f]{{{}}})",
+        CHANGE_ID_PREFIX, CHANGE_ID_LENGTH
+    ))
+    .unwrap();
+}
+
+/// a change id hashes everything the change depends on - if the file changes between the run
+/// that wrote the output file and the run that applies it, the id no longer matches and the
+/// change is skipped rather than applied to content nobody reviewed
+fn change_id(kind: &str, parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(kind.as_bytes());
+    for part in parts {
+        hasher.update([0]);
+        hasher.update(part.as_bytes());
+    }
+    let hash = format!("{:x}", hasher.finalize());
+    format!("{}{}", CHANGE_ID_PREFIX, &hash[..CHANGE_ID_LENGTH])
+}
+
+/// the approve column of a report row - check the box in obsidian and run `ok apply`
+pub fn task_checkbox(change_id: &str) -> String {
+    format!("[ ] {}", change_id)
+}
+
+/// the change ids checked in a previous "obsidian knife output.md"
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApprovedChanges {
+    ids: HashSet<String>,
+}
+
+impl ApprovedChanges {
+    /// fails when the output file doesn't exist or has no change ids at all - there is nothing
+    /// to select from and applying everything would skip the review
+    pub fn read(output_file: &Path) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        if !output_file.exists() {
+            return Err(format!("{} {}", APPROVAL_OUTPUT_MISSING, output_file.display()).into());
+        }
+
+        Self::parse(&fs::read_to_string(output_file)?)
+            .ok_or_else(|| format!("{} {}", APPROVAL_NO_CHANGE_IDS, output_file.display()).into())
+    }
+
+    /// None when the output has no change ids at all
+    pub fn parse(output: &str) -> Option<Self> {
+        let mut has_change_ids = false;
+        let ids = CHECKED_CHANGE_ID
+            .captures_iter(output)
+            .inspect(|_| has_change_ids = true)
+            .filter(|captures| !captures[1].trim().is_empty())
+            .map(|captures| captures[2].to_string())
+            .collect();
+
+        has_change_ids.then_some(Self { ids })
+    }
+
+    pub fn contains(&self, change_id: &str) -> bool {
+        self.ids.contains(change_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+impl BackPopulateMatch {
+    /// shared by every occurrence of the same text on a line as they're reported as one row
+    pub fn change_id(&self) -> String {
+        change_id(
+            CHANGE_BACK_POPULATE,
+            &[
+                &self.relative_path,
+                &self.line_number.to_string(),
+                &self.line_text,
+                &self.found_text.to_lowercase(),
+            ],
+        )
+    }
+}
+
+impl SimplifyWikilinkMatch {
+    pub fn change_id(&self) -> String {
+        change_id(
+            CHANGE_SIMPLIFY_WIKILINK,
+            &[
+                &self.relative_path,
+                &self.line_number.to_string(),
+                &self.position.to_string(),
+                &self.line_text,
+                &self.wikilink_text,
+            ],
+        )
+    }
+}
+
+impl ImageFile {
+    /// covers deleting the image and updating the references to it
+    pub fn change_id(&self) -> String {
+        change_id(
+            CHANGE_IMAGE,
+            &[
+                &self.path.to_string_lossy(),
+                &self.hash.to_string(),
+                &format!("{:?}", self.image_state),
+            ],
+        )
+    }
+}
+
+impl ImageLink {
+    pub fn missing_reference_change_id(&self, markdown_path: &Path) -> String {
+        change_id(
+            CHANGE_MISSING_IMAGE_REFERENCE,
+            &[
+                &markdown_path.to_string_lossy(),
+                &self.line_number.to_string(),
+                &self.position.to_string(),
+                &self.matched_text,
+            ],
+        )
+    }
+}
+
+impl SchemaViolation {
+    /// covers the value the property is set to
+    pub fn change_id(&self, markdown_path: &Path) -> String {
+        change_id(
+            CHANGE_SCHEMA_FIX,
+            &[
+                &markdown_path.to_string_lossy(),
+                &self.property,
+                &self.issue.to_string(),
+                &self
+                    .fix
+                    .as_ref()
+                    .map(schema::value_text)
+                    .unwrap_or_default(),
+            ],
+        )
+    }
+}
+
+impl FrontmatterRepair {
+    /// covers the yaml before and after so a note edited since the report needs a new approval
+    pub fn change_id(&self, markdown_path: &Path) -> String {
+        change_id(
+            CHANGE_FRONTMATTER_REPAIR,
+            &[&markdown_path.to_string_lossy(), &self.before, &self.after],
+        )
+    }
+}
+
+impl PropertyTypeMismatch {
+    /// covers the value the property is converted to
+    pub fn change_id(&self, markdown_path: &Path) -> String {
+        change_id(
+            CHANGE_PROPERTY_TYPE,
+            &[
+                &markdown_path.to_string_lossy(),
+                &self.property,
+                &schema::value_text(&self.value),
+                &self
+                    .fix
+                    .as_ref()
+                    .map(schema::value_text)
+                    .unwrap_or_default(),
+            ],
+        )
+    }
+}
+
+/// one id for all of a file's date changes - they're made to the frontmatter together
+pub fn date_change_id(
+    path: &Path,
+    date_validation_created: &DateValidation,
+    date_validation_modified: &DateValidation,
+    date_created_fix: &DateCreatedFixValidation,
+) -> String {
+    change_id(
+        CHANGE_DATES,
+        &[
+            &path.to_string_lossy(),
+            date_validation_created
+                .frontmatter_date
+                .as_deref()
+                .unwrap_or_default(),
+            &date_validation_created.file_system_date.to_rfc3339(),
+            date_validation_modified
+                .frontmatter_date
+                .as_deref()
+                .unwrap_or_default(),
+            &date_validation_modified.file_system_date.to_rfc3339(),
+            date_created_fix.date_string.as_deref().unwrap_or_default(),
+        ],
+    )
+}
+
+/// one id for all of a file's property changes - a rename is a removal and an addition that
+/// only make sense together
+pub fn property_change_id(path: &Path, changes: &[PropertyChange]) -> String {
+    let text = |value: &Option<serde_yaml::Value>| {
+        value.as_ref().map(schema::value_text).unwrap_or_default()
+    };
+    let parts: Vec<String> = std::iter::once(path.to_string_lossy().to_string())
+        .chain(changes.iter().flat_map(|change| {
+            [
+                change.property.clone(),
+                text(&change.before),
+                text(&change.after),
+            ]
+        }))
+        .collect();
+    change_id(
+        CHANGE_PROPERTIES,
+        &parts.iter().map(String::as_str).collect::<Vec<_>>(),
+    )
+}
+
+impl MarkdownFile {
+    pub fn date_change_id(&self) -> String {
+        date_change_id(
+            &self.path,
+            &self.date_validation_created,
+            &self.date_validation_modified,
+            &self.date_created_fix,
+        )
+    }
+
+    pub fn property_change_id(&self) -> String {
+        property_change_id(&self.path, &self.property_changes)
+    }
+}
+
+impl ObsidianRepository {
+    /// drops every proposed back populate, simplify and image reference change that wasn't
+    /// approved - runs before the changes are applied to the content
+    pub fn retain_approved_changes(&mut self, approved_changes: &ApprovedChanges) {
+        let unapproved_images: HashSet<String> = self
+            .image_files
+            .iter()
+            .filter(|image_file| !approved_changes.contains(&image_file.change_id()))
+            .filter_map(|image_file| image_file.path.file_name())
+            .map(|file_name| file_name.to_string_lossy().to_string())
+            .collect();
+
+        for markdown_file in &mut self.markdown_files {
+            markdown_file
+                .matches
+                .unambiguous
+                .retain(|m| approved_changes.contains(&m.change_id()));
+            markdown_file
+                .simplify_wikilink_matches
+                .retain(|m| approved_changes.contains(&m.change_id()));
+
+            let path = markdown_file.path.clone();
+            for link in markdown_file.image_links.iter_mut() {
+                let approved = match &link.state {
+                    ImageLinkState::Found => continue,
+                    ImageLinkState::Missing => {
+                        approved_changes.contains(&link.missing_reference_change_id(&path))
+                    }
+                    ImageLinkState::Duplicate { .. } | ImageLinkState::Incompatible { .. } => {
+                        !unapproved_images.contains(&link.filename)
+                    }
+                };
+
+                if !approved {
+                    link.state = ImageLinkState::Found;
+                }
+            }
+        }
+    }
+
+    /// runs after the images are marked for deletion
+    pub fn retain_approved_image_deletions(&mut self, approved_changes: &ApprovedChanges) {
+        for image_file in &mut self.image_files.files {
+            if image_file.delete && !approved_changes.contains(&image_file.change_id()) {
+                image_file.delete = false;
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use crate::approval::ApprovedChanges;
+use crate::cli::RunMode;
+use crate::constants::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use crate::validated_config::ValidatedConfig;
+use std::fs;
+use tempfile::TempDir;
+
+fn get_config(temp_dir: &TempDir, approved_changes: Option<ApprovedChanges>) -> ValidatedConfig {
+    let config = validated_config_tests::get_test_validated_config_result(temp_dir, |builder| {
+        builder.approved_changes(approved_changes);
+    })
+    .unwrap();
+    fs::create_dir_all(config.output_folder()).unwrap();
+    config
+}
+
+fn create_back_populate_files(temp_dir: &TempDir) {
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("# Target".to_string())
+        .create(temp_dir, "Target.md");
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("first Target\nsecond Target".to_string())
+        .create(temp_dir, "other.md");
+}
+
+fn unambiguous_lines(repository: &ObsidianRepository) -> Vec<usize> {
+    let mut lines: Vec<usize> = repository
+        .markdown_files
+        .iter()
+        .flat_map(|file| &file.matches.unambiguous)
+        .map(|m| m.line_number)
+        .collect();
+    lines.sort();
+    lines
+}
+
+#[test]
+fn test_parse_checked_change_ids() {
+    let output = "\
+| approve | file |
+| --- | --- |
+| [x] ok-0123456789 | [[a]] |
+| [ ] ok-abcdefabcd | [[b]] |
+| [X] ok-fedcba9876 | [[c]] |";
+
+    let approved = ApprovedChanges::parse(output).unwrap();
+
+    assert_eq!(approved.len(), 2);
+    assert!(approved.contains("ok-0123456789"));
+    assert!(approved.contains("ok-fedcba9876"));
+    assert!(!approved.contains("ok-abcdefabcd"));
+}
+
+#[test]
+fn test_parse_without_change_ids() {
+    assert_eq!(
+        ApprovedChanges::parse("# no changes here\n[x] not an id"),
+        None
+    );
+}
+
+#[test]
+fn test_parse_nothing_checked() {
+    let approved = ApprovedChanges::parse("[ ] ok-0123456789").unwrap();
+    assert!(approved.is_empty());
+}
+
+#[test]
+fn test_read_without_change_ids_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    let output_file = temp_dir.path().join(OUTPUT_MARKDOWN_FILE);
+
+    let err = ApprovedChanges::read(&output_file).unwrap_err();
+    assert!(err.to_string().starts_with(APPROVAL_OUTPUT_MISSING));
+
+    fs::write(&output_file, "# no changes here\n[x] not an id").unwrap();
+    let err = ApprovedChanges::read(&output_file).unwrap_err();
+    assert!(err.to_string().starts_with(APPROVAL_NO_CHANGE_IDS));
+}
+
+#[test]
+fn test_change_id_depends_on_line_text() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, None)).unwrap();
+    let matches: Vec<_> = repository
+        .markdown_files
+        .iter()
+        .flat_map(|file| file.matches.unambiguous.clone())
+        .collect();
+    assert_eq!(matches.len(), 2);
+
+    let mut changed = matches[0].clone();
+    assert_eq!(changed.change_id(), matches[0].change_id());
+    assert!(changed.change_id().starts_with(CHANGE_ID_PREFIX));
+    assert_ne!(matches[0].change_id(), matches[1].change_id());
+
+    changed.line_text.push_str(" edited");
+    assert_ne!(changed.change_id(), matches[0].change_id());
+}
+
+#[test]
+fn test_only_approved_back_populate_matches_are_kept() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, None)).unwrap();
+    let second_line = repository
+        .markdown_files
+        .iter()
+        .flat_map(|file| &file.matches.unambiguous)
+        .max_by_key(|m| m.line_number)
+        .unwrap();
+    let expected_line = second_line.line_number;
+
+    let approved = ApprovedChanges::parse(&format!("[x] {}", second_line.change_id()));
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, approved)).unwrap();
+
+    assert_eq!(unambiguous_lines(&repository), vec![expected_line]);
+}
+
+#[test]
+fn test_only_approved_image_deletions_are_kept() {
+    let temp_dir = TempDir::new().unwrap();
+    let kept = TestFileBuilder::new()
+        .with_content(vec![1, 2, 3])
+        .create(&temp_dir, "kept.png");
+    let deleted = TestFileBuilder::new()
+        .with_content(vec![4, 5, 6])
+        .create(&temp_dir, "deleted.png");
+
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, None)).unwrap();
+    assert!(repository
+        .image_files
+        .iter()
+        .all(|image_file| image_file.delete));
+
+    let approve = repository
+        .image_files
+        .iter()
+        .find(|image_file| image_file.path == deleted)
+        .unwrap()
+        .change_id();
+
+    let approved = ApprovedChanges::parse(&format!("[x] {}", approve));
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, approved)).unwrap();
+
+    for image_file in repository.image_files.iter() {
+        assert_eq!(image_file.delete, image_file.path == deleted);
+    }
+    assert!(repository.image_files.iter().any(|f| f.path == kept));
+}
+
+#[test]
+fn test_unapproved_dates_are_left_alone() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_frontmatter_dates(
+            Some("[[2020-01-01]]".to_string()),
+            Some("[[2020-01-01]]".to_string()),
+        )
+        .with_fs_dates(test_date, test_date)
+        .with_content("out of date".to_string())
+        .create(&temp_dir, "dated.md");
+
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, None)).unwrap();
+    let file = repository.markdown_files.iter().next().unwrap();
+    assert!(!file.persist_reasons.is_empty());
+    let date_change_id = file.date_change_id();
+
+    let approved = ApprovedChanges::parse("[ ] ok-0123456789");
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, approved)).unwrap();
+    assert!(repository.markdown_files.files_to_persist().is_empty());
+
+    let approved = ApprovedChanges::parse(&format!("[x] {}", date_change_id));
+    let repository = ObsidianRepository::new(&get_config(&temp_dir, approved)).unwrap();
+    assert_eq!(repository.markdown_files.files_to_persist().len(), 1);
+}
+
+#[test]
+fn test_output_file_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let config = get_config(&temp_dir, None);
+    let repository = ObsidianRepository::new(&config).unwrap();
+    repository.write_reports(&config, RunMode::Report).unwrap();
+
+    let output_file = config.output_folder().join(OUTPUT_MARKDOWN_FILE);
+    let output = fs::read_to_string(&output_file).unwrap();
+    assert!(ApprovedChanges::read(&output_file).unwrap().is_empty());
+
+    // check every box
+    fs::write(&output_file, output.replace("[ ] ok-", "[x] ok-")).unwrap();
+    let approved = ApprovedChanges::read(&output_file).unwrap();
+
+    for m in repository
+        .markdown_files
+        .iter()
+        .flat_map(|file| &file.matches.unambiguous)
+    {
+        assert!(approved.contains(&m.change_id()));
+    }
+}
+
+#[test]
+fn test_date_change_id_has_one_checkbox_per_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_frontmatter_dates(
+            Some("[[2020-01-01]]".to_string()),
+            Some("[[2020-01-01]]".to_string()),
+        )
+        .with_fs_dates(test_date, test_date)
+        .with_content("out of date".to_string())
+        .create(&temp_dir, "dated.md");
+
+    let config = get_config(&temp_dir, None);
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let file = repository.markdown_files.iter().next().unwrap();
+    assert!(file.persist_reasons.len() > 1);
+
+    repository.write_reports(&config, RunMode::Report).unwrap();
+    let output = fs::read_to_string(config.output_folder().join(OUTPUT_MARKDOWN_FILE)).unwrap();
+    assert_eq!(output.matches(&file.date_change_id()).count(), 1);
+}
@@ -0,0 +1,365 @@
+#[cfg(test)]
+mod cli_tests;
+
+use crate::config::{Config, PropertyOperationConfig, PropertyTypesConfig};
+use clap::{Args, Parser, Subcommand};
+use serde_yaml::Value;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "ok",
+    version,
+    about = "maintain obsidian repositories - back populate wikilinks, clean up images, fix dates",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// same as `ok check` - for ci and pre-commit hooks
+    #[arg(long)]
+    check: bool,
+
+    // `ok <config_file.md>` - runs everything and applies changes if the config says so
+    #[command(flatten)]
+    run_args: RunArgs,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// analyze the repository without writing anything - exits non-zero if there are findings
+    Check(RunArgs),
+    /// write the output file without changing anything
+    Report(RunArgs),
+    /// apply the changes checked in the output file - fails if it has no change ids
+    Apply(RunArgs),
+    /// write only the image sections of the output file
+    Images(RunArgs),
+    /// write only the date and frontmatter sections of the output file
+    Dates(RunArgs),
+    /// restore the files changed by an apply run - defaults to the most recent run
+    Undo(UndoArgs),
+    /// list the journals kept for undo
+    Journal(JournalArgs),
+}
+
+#[derive(Debug, Args)]
+struct UndoArgs {
+    /// markdown file with the configuration in its yaml frontmatter
+    config_file: PathBuf,
+    /// run id as shown by `ok journal`
+    run_id: Option<String>,
+    /// restore files that were changed after the run, losing those changes
+    #[arg(long)]
+    force: bool,
+}
+
+#[derive(Debug, Args)]
+struct JournalArgs {
+    /// markdown file with the configuration in its yaml frontmatter
+    config_file: PathBuf,
+    /// keep the most recent COUNT journals and delete the rest
+    #[arg(long, value_name = "COUNT")]
+    prune: Option<usize>,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// markdown file with the configuration in its yaml frontmatter
+    #[arg(required = true)]
+    config_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    overrides: ConfigOverrides,
+}
+
+/// command line flags that take precedence over the values in the config file
+#[derive(Debug, Default, Args)]
+pub struct ConfigOverrides {
+    /// apply changes regardless of the config file's apply_changes
+    #[arg(long)]
+    apply: bool,
+    /// limit the number of files processed
+    #[arg(long, value_name = "COUNT")]
+    file_limit: Option<usize>,
+    /// process only this file for back population, e.g. "[[some note]]"
+    #[arg(long, value_name = "FILE")]
+    filter: Option<String>,
+    /// operational timezone, e.g. America/New_York
+    #[arg(long)]
+    timezone: Option<String>,
+    /// output folder, relative to obsidian_path
+    #[arg(long, value_name = "FOLDER")]
+    output: Option<String>,
+    /// write a unified diff of every file to be changed - same as write_patch: true
+    #[arg(long)]
+    patch: bool,
+    /// convert property values to the type the property has elsewhere - same as
+    /// property_types: normalize: true
+    #[arg(long)]
+    normalize_property_types: bool,
+    /// set a property on every note the operation covers, e.g. status=done
+    #[arg(long, value_name = "PROPERTY=VALUE", value_parser = parse_property_value)]
+    set_property: Vec<(String, Value)>,
+    /// rename a property, e.g. type=kind
+    #[arg(long, value_name = "OLD=NEW", value_parser = parse_rename)]
+    rename_property: Vec<(String, String)>,
+    /// remove a property
+    #[arg(long, value_name = "PROPERTY")]
+    remove_property: Vec<String>,
+    /// add a value to a list property, e.g. tags=archived
+    #[arg(long, value_name = "PROPERTY=VALUE", value_parser = parse_property_value)]
+    add_to_list: Vec<(String, Value)>,
+    /// remove a value from a list property
+    #[arg(long, value_name = "PROPERTY=VALUE", value_parser = parse_property_value)]
+    remove_from_list: Vec<(String, Value)>,
+    /// limit the property operation to the notes in this folder - a glob like in schema
+    #[arg(long, value_name = "FOLDER")]
+    in_folder: Option<String>,
+    /// limit the property operation to the notes with this tag
+    #[arg(long, value_name = "TAG")]
+    with_tag: Option<String>,
+    /// limit the property operation to the notes with this property value
+    #[arg(long = "where", value_name = "PROPERTY=VALUE", value_parser = parse_property_value)]
+    where_property: Vec<(String, Value)>,
+}
+
+/// the value is read as yaml so numbers, booleans and [lists] keep their type
+fn parse_property_value(argument: &str) -> Result<(String, Value), String> {
+    let (property, value) = parse_rename(argument)?;
+    let value = serde_yaml::from_str(&value).unwrap_or(Value::String(value));
+    Ok((property, value))
+}
+
+fn parse_rename(argument: &str) -> Result<(String, String), String> {
+    match argument.split_once('=') {
+        Some((property, value)) if !property.trim().is_empty() => {
+            Ok((property.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected PROPERTY=VALUE, got '{}'", argument)),
+    }
+}
+
+/// commands that work on the journals of previous runs rather than analyzing the repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalCommand {
+    List,
+    Prune(usize),
+    /// run id and whether to overwrite files changed since the run
+    Undo(Option<String>, bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunMode {
+    /// legacy `ok <config_file.md>` behavior - apply_changes comes from the config
+    Default,
+    Check,
+    Report,
+    Apply,
+    Images,
+    Dates,
+}
+
+impl RunMode {
+    pub fn can_apply_changes(&self) -> bool {
+        matches!(self, RunMode::Default | RunMode::Apply)
+    }
+
+    /// `ok apply` applies only what was checked in the previous output file
+    pub fn applies_approved_changes(&self) -> bool {
+        matches!(self, RunMode::Apply)
+    }
+
+    pub fn writes_output_file(&self) -> bool {
+        !matches!(self, RunMode::Check)
+    }
+
+    pub fn writes_image_reports(&self) -> bool {
+        !matches!(self, RunMode::Dates)
+    }
+
+    pub fn writes_date_reports(&self) -> bool {
+        !matches!(self, RunMode::Images)
+    }
+
+    pub fn writes_wikilink_reports(&self) -> bool {
+        !matches!(self, RunMode::Images | RunMode::Dates)
+    }
+}
+
+impl Cli {
+    pub fn config_file(&self) -> PathBuf {
+        match &self.command {
+            Some(Command::Undo(args)) => args.config_file.clone(),
+            Some(Command::Journal(args)) => args.config_file.clone(),
+            _ => self
+                .selected_args()
+                .and_then(|args| args.config_file.clone())
+                .expect("clap requires config_file"),
+        }
+    }
+
+    /// None for the journal commands as they don't analyze the repository
+    pub fn overrides(&self) -> Option<&ConfigOverrides> {
+        self.selected_args().map(|args| &args.overrides)
+    }
+
+    pub fn journal_command(&self) -> Option<JournalCommand> {
+        match &self.command {
+            Some(Command::Undo(args)) => {
+                Some(JournalCommand::Undo(args.run_id.clone(), args.force))
+            }
+            Some(Command::Journal(JournalArgs {
+                prune: Some(keep), ..
+            })) => Some(JournalCommand::Prune(*keep)),
+            Some(Command::Journal(_)) => Some(JournalCommand::List),
+            _ => None,
+        }
+    }
+
+    pub fn run_mode(&self) -> RunMode {
+        match &self.command {
+            None if self.check => RunMode::Check,
+            Some(Command::Check(_)) => RunMode::Check,
+            Some(Command::Report(_)) => RunMode::Report,
+            Some(Command::Apply(_)) => RunMode::Apply,
+            Some(Command::Images(_)) => RunMode::Images,
+            Some(Command::Dates(_)) => RunMode::Dates,
+            None | Some(Command::Undo(_) | Command::Journal(_)) => RunMode::Default,
+        }
+    }
+
+    fn selected_args(&self) -> Option<&RunArgs> {
+        match &self.command {
+            None => Some(&self.run_args),
+            Some(
+                Command::Check(args)
+                | Command::Report(args)
+                | Command::Apply(args)
+                | Command::Images(args)
+                | Command::Dates(args),
+            ) => Some(args),
+            Some(Command::Undo(_) | Command::Journal(_)) => None,
+        }
+    }
+}
+
+impl ConfigOverrides {
+    /// flags win over the config file - the run mode has the final say on apply_changes
+    pub fn apply_to(
+        &self,
+        config: &mut Config,
+        run_mode: RunMode,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        if self.apply {
+            if !run_mode.can_apply_changes() {
+                return Err(format!("--apply can't be used with {:?}", run_mode)
+                    .to_lowercase()
+                    .into());
+            }
+            config.apply_changes = Some(true);
+        }
+        if let Some(file_limit) = self.file_limit {
+            config.file_limit = Some(file_limit);
+        }
+        if let Some(filter) = &self.filter {
+            config.back_populate_file_filter = Some(filter.clone());
+        }
+        if let Some(timezone) = &self.timezone {
+            config.operational_timezone = Some(timezone.clone());
+        }
+        if let Some(output) = &self.output {
+            config.output_folder = Some(output.clone());
+        }
+        if self.patch {
+            config.write_patch = Some(true);
+        }
+        if self.normalize_property_types {
+            config
+                .property_types
+                .get_or_insert_with(PropertyTypesConfig::default)
+                .normalize = Some(true);
+        }
+        if let Some(operation) = self.property_operation()? {
+            config
+                .property_operations
+                .get_or_insert_with(Vec::new)
+                .push(operation);
+        }
+
+        match run_mode {
+            RunMode::Apply => config.apply_changes = Some(true),
+            RunMode::Check | RunMode::Report | RunMode::Images | RunMode::Dates => {
+                config.apply_changes = Some(false)
+            }
+            RunMode::Default => {}
+        }
+
+        Ok(())
+    }
+
+    /// the property flags make up one operation that runs after the ones in the config file
+    fn property_operation(
+        &self,
+    ) -> Result<Option<PropertyOperationConfig>, Box<dyn Error + Send + Sync>> {
+        let has_action = !self.set_property.is_empty()
+            || !self.rename_property.is_empty()
+            || !self.remove_property.is_empty()
+            || !self.add_to_list.is_empty()
+            || !self.remove_from_list.is_empty();
+        if !has_action {
+            if self.in_folder.is_some()
+                || self.with_tag.is_some()
+                || !self.where_property.is_empty()
+            {
+                return Err(
+                    "--in-folder, --with-tag and --where need a property operation, \
+                            e.g. --set-property"
+                        .into(),
+                );
+            }
+            return Ok(None);
+        }
+
+        let values = |pairs: &[(String, Value)]| {
+            (!pairs.is_empty()).then(|| pairs.iter().cloned().collect::<BTreeMap<_, _>>())
+        };
+        // a list flag given more than once for the same property adds or removes every value
+        let lists = |pairs: &[(String, Value)]| {
+            (!pairs.is_empty()).then(|| {
+                pairs
+                    .iter()
+                    .fold(
+                        BTreeMap::<String, Vec<Value>>::new(),
+                        |mut lists, (property, value)| {
+                            let items = lists.entry(property.clone()).or_default();
+                            match value {
+                                Value::Sequence(values) => items.extend(values.iter().cloned()),
+                                value => items.push(value.clone()),
+                            }
+                            lists
+                        },
+                    )
+                    .into_iter()
+                    .map(|(property, items)| (property, Value::Sequence(items)))
+                    .collect()
+            })
+        };
+
+        Ok(Some(PropertyOperationConfig {
+            add_to_list: lists(&self.add_to_list),
+            folder: self.in_folder.clone(),
+            remove: (!self.remove_property.is_empty()).then(|| self.remove_property.clone()),
+            remove_from_list: lists(&self.remove_from_list),
+            rename: (!self.rename_property.is_empty())
+                .then(|| self.rename_property.iter().cloned().collect()),
+            set: values(&self.set_property),
+            tag: self.with_tag.clone(),
+            filter: values(&self.where_property),
+        }))
+    }
+}
@@ -0,0 +1,223 @@
+use super::*;
+use crate::yaml_frontmatter::YamlFrontMatter;
+
+fn parse(args: &[&str]) -> Cli {
+    Cli::try_parse_from(std::iter::once("ok").chain(args.iter().copied())).unwrap()
+}
+
+fn get_test_config(apply_changes: bool) -> Config {
+    Config::from_yaml_str(&format!(
+        "obsidian_path: ~/Documents/brain\napply_changes: {}\nfile_limit: 10",
+        apply_changes
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_requires_config_file() {
+    assert!(Cli::try_parse_from(["ok"]).is_err());
+    assert!(Cli::try_parse_from(["ok", "check"]).is_err());
+}
+
+#[test]
+fn test_run_modes() {
+    let test_cases = [
+        (vec!["config.md"], RunMode::Default),
+        (vec!["check", "config.md"], RunMode::Check),
+        (vec!["report", "config.md"], RunMode::Report),
+        (vec!["apply", "config.md"], RunMode::Apply),
+        (vec!["images", "config.md"], RunMode::Images),
+        (vec!["dates", "config.md"], RunMode::Dates),
+    ];
+
+    for (args, expected) in test_cases {
+        let cli = parse(&args);
+        assert_eq!(cli.run_mode(), expected, "Failed for args: {:?}", args);
+        assert_eq!(
+            cli.run_mode().applies_approved_changes(),
+            expected == RunMode::Apply
+        );
+        assert_eq!(cli.config_file(), PathBuf::from("config.md"));
+    }
+}
+
+#[test]
+fn test_overrides_replace_config_values() {
+    let cli = parse(&[
+        "report",
+        "config.md",
+        "--file-limit",
+        "3",
+        "--filter",
+        "[[some note]]",
+        "--timezone",
+        "Europe/London",
+        "--output",
+        "elsewhere",
+        "--patch",
+        "--normalize-property-types",
+    ]);
+
+    let mut config = get_test_config(false);
+    cli.overrides()
+        .unwrap()
+        .apply_to(&mut config, cli.run_mode())
+        .unwrap();
+
+    assert_eq!(config.file_limit, Some(3));
+    assert_eq!(
+        config.back_populate_file_filter,
+        Some("[[some note]]".to_string())
+    );
+    assert_eq!(
+        config.operational_timezone,
+        Some("Europe/London".to_string())
+    );
+    assert_eq!(config.output_folder, Some("elsewhere".to_string()));
+    assert_eq!(config.write_patch, Some(true));
+    assert_eq!(
+        config.property_types.and_then(|types| types.normalize),
+        Some(true)
+    );
+}
+
+#[test]
+fn test_run_mode_controls_apply_changes() {
+    let test_cases = [
+        (vec!["config.md"], true, Some(true)),
+        (vec!["config.md"], false, Some(false)),
+        (vec!["config.md", "--apply"], false, Some(true)),
+        (vec!["apply", "config.md"], false, Some(true)),
+        (vec!["report", "config.md"], true, Some(false)),
+        (vec!["check", "config.md"], true, Some(false)),
+        (vec!["images", "config.md"], true, Some(false)),
+    ];
+
+    for (args, config_applies, expected) in test_cases {
+        let cli = parse(&args);
+        let mut config = get_test_config(config_applies);
+        cli.overrides()
+            .unwrap()
+            .apply_to(&mut config, cli.run_mode())
+            .unwrap();
+        assert_eq!(
+            config.apply_changes, expected,
+            "Failed for args: {:?}",
+            args
+        );
+        assert_eq!(
+            config.file_limit,
+            Some(10),
+            "unset flags keep config values"
+        );
+    }
+}
+
+#[test]
+fn test_apply_flag_rejected_for_read_only_modes() {
+    let cli = parse(&["check", "config.md", "--apply"]);
+    let mut config = get_test_config(false);
+    let result = cli
+        .overrides()
+        .unwrap()
+        .apply_to(&mut config, cli.run_mode());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_check_flag() {
+    let cli = parse(&["--check", "config.md", "--file-limit", "5"]);
+    assert_eq!(cli.run_mode(), RunMode::Check);
+    assert_eq!(cli.config_file(), PathBuf::from("config.md"));
+
+    assert!(Cli::try_parse_from(["ok", "--check", "report", "config.md"]).is_err());
+}
+
+#[test]
+fn test_journal_commands() {
+    let test_cases = [
+        (vec!["undo", "config.md"], JournalCommand::Undo(None, false)),
+        (
+            vec!["undo", "config.md", "20240102-030405-678"],
+            JournalCommand::Undo(Some("20240102-030405-678".to_string()), false),
+        ),
+        (
+            vec!["undo", "config.md", "--force"],
+            JournalCommand::Undo(None, true),
+        ),
+        (vec!["journal", "config.md"], JournalCommand::List),
+        (
+            vec!["journal", "config.md", "--prune", "5"],
+            JournalCommand::Prune(5),
+        ),
+    ];
+
+    for (args, expected) in test_cases {
+        let cli = parse(&args);
+        assert_eq!(
+            cli.journal_command(),
+            Some(expected),
+            "Failed for args: {:?}",
+            args
+        );
+        assert_eq!(cli.config_file(), PathBuf::from("config.md"));
+        assert!(cli.overrides().is_none());
+    }
+
+    assert_eq!(parse(&["apply", "config.md"]).journal_command(), None);
+}
+
+#[test]
+fn test_property_operation_flags() {
+    let cli = parse(&[
+        "report",
+        "config.md",
+        "--set-property",
+        "rating=3",
+        "--add-to-list",
+        "tags=read",
+        "--add-to-list",
+        "tags=[book, owned]",
+        "--remove-property",
+        "draft",
+        "--in-folder",
+        "books",
+        "--where",
+        "status=done",
+    ]);
+
+    let mut config = get_test_config(false);
+    cli.overrides()
+        .unwrap()
+        .apply_to(&mut config, cli.run_mode())
+        .unwrap();
+
+    let operations = config.property_operations.unwrap();
+    assert_eq!(operations.len(), 1);
+    let operation = &operations[0];
+    assert_eq!(operation.folder, Some("books".to_string()));
+    assert_eq!(operation.remove, Some(vec!["draft".to_string()]));
+    // values are read as yaml
+    assert_eq!(
+        operation.set.as_ref().unwrap()["rating"],
+        serde_yaml::from_str::<Value>("3").unwrap()
+    );
+    assert_eq!(
+        operation.add_to_list.as_ref().unwrap()["tags"],
+        serde_yaml::from_str::<Value>("[read, book, owned]").unwrap()
+    );
+    assert_eq!(
+        operation.filter.as_ref().unwrap()["status"],
+        Value::String("done".to_string())
+    );
+
+    // a filter without anything to do is a mistake
+    let cli = parse(&["report", "config.md", "--with-tag", "book"]);
+    let result = cli
+        .overrides()
+        .unwrap()
+        .apply_to(&mut get_test_config(false), cli.run_mode());
+    assert!(result.is_err());
+
+    assert!(Cli::try_parse_from(["ok", "config.md", "--set-property", "rating"]).is_err());
+}
@@ -1,12 +1,19 @@
 #[cfg(test)]
 mod config_tests;
 
+use crate::ambiguous_resolution::AmbiguousResolver;
 use crate::constants::*;
+use crate::date_settings::{DateFormat, DateProperties, DateSettings};
 use crate::frontmatter::FrontMatter;
-use crate::validated_config::{ValidatedConfig, ValidatedConfigBuilder};
+use crate::inflection::{Inflections, SuffixRule};
+use crate::markdown_file::SkipRegion;
+use crate::property_operations::{PropertyAction, PropertyOperation, PropertyOperations};
+use crate::schema::{PropertySchema, Schema, SchemaRule};
+use crate::validated_config::{ValidatedConfig, ValidatedConfigBuilder, ValidationError};
 use crate::yaml_frontmatter::YamlFrontMatter;
 use crate::{utils, yaml_frontmatter_struct};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::path::PathBuf;
 
@@ -16,22 +23,344 @@ yaml_frontmatter_struct! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub apply_changes: Option<bool>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub back_populate: Option<BackPopulateConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub back_populate_file_filter: Option<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
+        pub create_frontmatter: Option<CreateFrontmatterConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub dates: Option<DatesConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
         pub do_not_back_populate: Option<Vec<String>>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub file_limit: Option<usize>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub ignore_folders: Option<Vec<PathBuf>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ignore_rendered_text: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub images: Option<ImagesConfig>,
         pub obsidian_path: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub operational_timezone: Option<String>,
         pub output_folder: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub property_operations: Option<Vec<PropertyOperationConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub property_types: Option<PropertyTypesConfig>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub schema: Option<Vec<SchemaRuleConfig>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub simplify_wikilinks: Option<Vec<String>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub write_patch: Option<bool>,
         #[serde(skip)]
         pub config_file_path: PathBuf,
     }
 }
 
+// each feature defaults to enabled so a config without these sections behaves as it always has
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct BackPopulateConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ambiguous: Option<AmbiguousConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inflections: Option<InflectionsConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link_density: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_regions: Option<Vec<String>>,
+}
+
+// opt-in as every extra form of a name is another chance of linking text that isn't about the page
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct InflectionsConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suffixes: Option<Vec<SuffixRuleConfig>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SuffixRuleConfig {
+    #[serde(default)]
+    pub add: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strip: Option<String>,
+}
+
+impl InflectionsConfig {
+    /// None unless enabled
+    pub fn to_inflections(&self) -> Result<Option<Inflections>, ValidationError> {
+        if !self.enabled.unwrap_or(false) {
+            return Ok(None);
+        }
+
+        let suffix_rules = self
+            .suffixes
+            .iter()
+            .flatten()
+            .map(|suffix| {
+                let rule = SuffixRule {
+                    strip: suffix.strip.clone().unwrap_or_default(),
+                    add: suffix.add.clone(),
+                };
+                if rule.strip.is_empty() && rule.add.is_empty() {
+                    return Err(ValidationError::InvalidInflection(
+                        "a suffix rule needs add or strip".to_string(),
+                    ));
+                }
+                Ok(rule)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Some(Inflections::new(suffix_rules)))
+    }
+}
+
+// without targets or rules ambiguous matches are only reported
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AmbiguousConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub targets: Option<BTreeMap<String, String>>,
+}
+
+impl AmbiguousConfig {
+    pub fn to_resolver(&self) -> Result<AmbiguousResolver, ValidationError> {
+        let rules = self
+            .rules
+            .iter()
+            .flatten()
+            .map(|rule| rule.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(ValidationError::InvalidAmbiguousRule)?;
+
+        Ok(AmbiguousResolver::new(
+            &self.targets.clone().unwrap_or_default(),
+            rules,
+        ))
+    }
+}
+
+// unlike the other features this one is opt-in as it adds frontmatter to notes that were fine without
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CreateFrontmatterConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<serde_yaml::Mapping>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DatesConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_fix_property: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_property: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modified_property: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wikilink: Option<bool>,
+}
+
+impl DatesConfig {
+    pub fn to_date_settings(&self) -> Result<DateSettings, ValidationError> {
+        let defaults = DateSettings::default();
+        Ok(DateSettings {
+            format: self
+                .format
+                .as_deref()
+                .map(DateFormat::from)
+                .unwrap_or(defaults.format),
+            properties: DateProperties {
+                created: self
+                    .created_property
+                    .clone()
+                    .unwrap_or(defaults.properties.created),
+                created_fix: self
+                    .created_fix_property
+                    .clone()
+                    .unwrap_or(defaults.properties.created_fix),
+                modified: self
+                    .modified_property
+                    .clone()
+                    .unwrap_or(defaults.properties.modified),
+            },
+            source: match &self.source {
+                Some(source) => source
+                    .parse()
+                    .map_err(ValidationError::InvalidDateSettings)?,
+                None => defaults.source,
+            },
+            wikilink: self.wikilink.unwrap_or(defaults.wikilink),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ImagesConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete_files: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub update_references: Option<bool>,
+}
+
+// the report is always written - converting values is opt-in
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PropertyTypesConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub normalize: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PropertyOperationConfig {
+    /// a single value or a list of values per property
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_to_list: Option<BTreeMap<String, serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remove_from_list: Option<BTreeMap<String, serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rename: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub set: Option<BTreeMap<String, serde_yaml::Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(rename = "where", skip_serializing_if = "Option::is_none")]
+    pub filter: Option<BTreeMap<String, serde_yaml::Value>>,
+}
+
+impl PropertyOperationConfig {
+    /// renames run first so the other actions can use the new names
+    pub fn to_property_operation(&self) -> Result<PropertyOperation, ValidationError> {
+        let values = |value: &serde_yaml::Value| match value {
+            serde_yaml::Value::Sequence(items) => items.clone(),
+            value => vec![value.clone()],
+        };
+
+        let actions = self
+            .rename
+            .iter()
+            .flatten()
+            .map(|(from, to)| PropertyAction::Rename {
+                from: from.clone(),
+                to: to.clone(),
+            })
+            .chain(
+                self.remove
+                    .iter()
+                    .flatten()
+                    .map(|property| PropertyAction::Remove(property.clone())),
+            )
+            .chain(
+                self.set
+                    .iter()
+                    .flatten()
+                    .map(|(property, value)| PropertyAction::Set {
+                        property: property.clone(),
+                        value: value.clone(),
+                    }),
+            )
+            .chain(self.add_to_list.iter().flatten().map(|(property, value)| {
+                PropertyAction::AddToList {
+                    property: property.clone(),
+                    values: values(value),
+                }
+            }))
+            .chain(
+                self.remove_from_list
+                    .iter()
+                    .flatten()
+                    .map(|(property, value)| PropertyAction::RemoveFromList {
+                        property: property.clone(),
+                        values: values(value),
+                    }),
+            )
+            .collect();
+
+        PropertyOperation::new(
+            self.folder.clone(),
+            self.tag.clone(),
+            self.filter
+                .iter()
+                .flatten()
+                .map(|(property, value)| (property.clone(), value.clone()))
+                .collect(),
+            actions,
+        )
+        .map_err(ValidationError::InvalidPropertyOperation)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SchemaRuleConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub properties: BTreeMap<String, PropertySchemaConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PropertySchemaConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_yaml::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<bool>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub property_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<String>>,
+}
+
+impl SchemaRuleConfig {
+    pub fn to_schema_rule(&self) -> Result<SchemaRule, ValidationError> {
+        let properties = self
+            .properties
+            .iter()
+            .map(|(name, property)| {
+                Ok(PropertySchema {
+                    name: name.clone(),
+                    default: property.default.clone(),
+                    property_type: property
+                        .property_type
+                        .as_deref()
+                        .map(str::parse)
+                        .transpose()
+                        .map_err(ValidationError::InvalidSchema)?,
+                    required: property.required.unwrap_or(false),
+                    values: property.values.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, ValidationError>>()?;
+
+        SchemaRule::new(
+            self.folder.clone(),
+            self.tag.clone(),
+            self.fix.unwrap_or(false),
+            properties,
+        )
+        .map_err(ValidationError::InvalidSchema)
+    }
+}
+
 impl Config {
     pub fn from_frontmatter(
         frontmatter: FrontMatter,
@@ -42,11 +371,74 @@ impl Config {
 
     pub fn validate(&self) -> Result<ValidatedConfig, Box<dyn Error + Send + Sync>> {
         ValidatedConfigBuilder::default()
+            .ambiguous_resolver(
+                self.back_populate
+                    .as_ref()
+                    .and_then(|back_populate| back_populate.ambiguous.as_ref())
+                    .map(AmbiguousConfig::to_resolver)
+                    .transpose()?
+                    .unwrap_or_default(),
+            )
             .apply_changes(self.apply_changes.unwrap_or(false))
+            .back_populate_enabled(
+                self.back_populate
+                    .as_ref()
+                    .and_then(|back_populate| back_populate.enabled)
+                    .unwrap_or(true),
+            )
             .back_populate_file_filter(self.back_populate_file_filter.clone())
+            .create_frontmatter(
+                self.create_frontmatter
+                    .as_ref()
+                    .filter(|create| create.enabled.unwrap_or(false))
+                    .map(|create| create.properties.clone().unwrap_or_default()),
+            )
+            .date_settings(
+                self.dates
+                    .as_ref()
+                    .map(DatesConfig::to_date_settings)
+                    .transpose()?
+                    .unwrap_or_default(),
+            )
+            .dates_enabled(
+                self.dates
+                    .as_ref()
+                    .and_then(|dates| dates.enabled)
+                    .unwrap_or(true),
+            )
             .do_not_back_populate(self.do_not_back_populate.clone())
             .file_limit(self.file_limit)
             .ignore_folders(self.ignore_folders.clone())
+            .ignore_rendered_text(self.ignore_rendered_text.clone())
+            .image_deletion_enabled(
+                self.images
+                    .as_ref()
+                    .and_then(|images| images.delete_files)
+                    .unwrap_or(true),
+            )
+            .inflections(
+                self.back_populate
+                    .as_ref()
+                    .and_then(|back_populate| back_populate.inflections.as_ref())
+                    .map(InflectionsConfig::to_inflections)
+                    .transpose()?
+                    .flatten(),
+            )
+            .link_density(
+                self.back_populate
+                    .as_ref()
+                    .and_then(|back_populate| back_populate.link_density.as_deref())
+                    .map(|link_density| link_density.trim().parse())
+                    .transpose()
+                    .map_err(ValidationError::InvalidLinkDensity)?
+                    .unwrap_or_default(),
+            )
+            .image_references_enabled(
+                self.images
+                    .as_ref()
+                    .and_then(|images| images.update_references)
+                    .unwrap_or(true),
+            )
             .obsidian_path(utils::expand_tilde(&self.obsidian_path))
             .operational_timezone(
                 self.operational_timezone
@@ -60,6 +452,42 @@ impl Config {
                         .unwrap_or(DEFAULT_OUTPUT_FOLDER),
                 ),
             )
+            .property_operations(PropertyOperations::new(
+                self.property_operations
+                    .iter()
+                    .flatten()
+                    .map(PropertyOperationConfig::to_property_operation)
+                    .collect::<Result<_, _>>()?,
+            ))
+            .normalize_property_types(
+                self.property_types
+                    .as_ref()
+                    .and_then(|property_types| property_types.normalize)
+                    .unwrap_or(false),
+            )
+            .schema(Schema::new(
+                self.schema
+                    .iter()
+                    .flatten()
+                    .map(SchemaRuleConfig::to_schema_rule)
+                    .collect::<Result<_, _>>()?,
+            ))
+            .skip_regions(
+                self.back_populate
+                    .as_ref()
+                    .and_then(|back_populate| back_populate.skip_regions.as_ref())
+                    .map(|skip_regions| {
+                        skip_regions
+                            .iter()
+                            .map(|skip_region| skip_region.trim().parse())
+                            .collect::<Result<_, _>>()
+                    })
+                    .transpose()
+                    .map_err(ValidationError::InvalidSkipRegion)?
+                    .unwrap_or_else(SkipRegion::defaults),
+            )
+            .simplify_wikilinks(self.simplify_wikilinks.clone())
+            .write_patch(self.write_patch.unwrap_or(false))
             .build()
             .map_err(|e| Box::new(e) as Box<dyn Error + Send + Sync>)
     }
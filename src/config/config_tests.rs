@@ -1,7 +1,11 @@
-use crate::config::Config;
+use crate::config::{Config, DatesConfig};
 use crate::constants::*;
+use crate::date_settings::DateFormat;
 use crate::frontmatter::FrontMatter;
+use crate::link_density::LinkDensity;
 use crate::markdown_file::MarkdownFile;
+use crate::markdown_file::SkipRegion;
+use crate::property_operations::PropertyAction;
 use crate::test_utils;
 use crate::test_utils::TestFileBuilder;
 use crate::yaml_frontmatter::YamlFrontMatter;
@@ -188,3 +192,249 @@ fn test_process_config_with_valid_setup() {
     assert!(!validated_config.apply_changes());
     assert!(validated_config.obsidian_path().exists());
 }
+
+#[test]
+fn test_feature_switches_default_to_enabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let yaml = format!("obsidian_path: {}", temp_dir.path().display());
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated_config = config.validate().unwrap();
+
+    assert!(validated_config.back_populate_enabled());
+    assert!(validated_config.dates_enabled());
+    assert!(validated_config.image_deletion_enabled());
+    assert!(validated_config.image_references_enabled());
+    assert!(validated_config.disabled_features().is_empty());
+}
+
+#[test]
+fn test_feature_switches_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    let yaml = format!(
+        r#"
+obsidian_path: {}
+back_populate:
+  enabled: false
+dates:
+  enabled: false
+images:
+  delete_files: false"#,
+        temp_dir.path().display()
+    );
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated_config = config.validate().unwrap();
+
+    assert!(!validated_config.back_populate_enabled());
+    assert!(!validated_config.dates_enabled());
+    assert!(!validated_config.image_deletion_enabled());
+    assert!(validated_config.image_references_enabled());
+    assert_eq!(
+        validated_config.disabled_features(),
+        vec![FEATURE_BACK_POPULATE, FEATURE_DATES, FEATURE_IMAGE_DELETION]
+    );
+}
+
+#[test]
+fn test_link_density() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = |link_density: &str| {
+        let yaml = format!(
+            "obsidian_path: {}\nback_populate:\n  link_density: {}",
+            temp_dir.path().display(),
+            link_density
+        );
+        serde_yaml::from_str::<Config>(&yaml).unwrap().validate()
+    };
+
+    assert_eq!(
+        config("first_per_section").unwrap().link_density(),
+        LinkDensity::FirstPerSection
+    );
+    assert!(config("first_per_line").is_err());
+
+    let yaml = format!("obsidian_path: {}", temp_dir.path().display());
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(config.validate().unwrap().link_density(), LinkDensity::All);
+}
+
+#[test]
+fn test_inflections() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = |inflections: &str| {
+        let yaml = format!(
+            "obsidian_path: {}\nback_populate:\n  inflections:\n{}",
+            temp_dir.path().display(),
+            inflections
+        );
+        serde_yaml::from_str::<Config>(&yaml).unwrap().validate()
+    };
+
+    let validated_config =
+        config("    enabled: true\n    suffixes:\n      - { strip: um, add: a }").unwrap();
+    let inflections = validated_config.inflections().unwrap();
+    assert!(inflections.variants("Datum").contains(&"Data".to_string()));
+
+    assert!(config("    enabled: false")
+        .unwrap()
+        .inflections()
+        .is_none());
+    assert!(config("    enabled: true\n    suffixes:\n      - { strip: \"\" }").is_err());
+}
+
+#[test]
+fn test_date_settings() {
+    let temp_dir = TempDir::new().unwrap();
+    let yaml = format!(
+        r#"
+obsidian_path: {}
+dates:
+  created_property: created
+  format: datetime
+  wikilink: false"#,
+        temp_dir.path().display()
+    );
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated_config = config.validate().unwrap();
+    let date_settings = validated_config.date_settings();
+
+    assert_eq!(date_settings.format, DateFormat::DateTime);
+    assert_eq!(date_settings.properties.created, "created");
+    assert_eq!(
+        date_settings.properties.modified,
+        DEFAULT_DATE_MODIFIED_PROPERTY
+    );
+    assert!(!date_settings.wikilink);
+
+    let invalid = config.dates.clone().map(|dates| DatesConfig {
+        format: Some("%H:%M".to_string()),
+        ..dates
+    });
+    let config = Config {
+        dates: invalid,
+        ..config
+    };
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_schema() {
+    let temp_dir = TempDir::new().unwrap();
+    let yaml = format!(
+        r#"
+obsidian_path: {}
+schema:
+  - folder: people
+    fix: true
+    properties:
+      status: {{ required: true, values: [active, archived] }}
+      tags: {{ type: list, default: [person] }}"#,
+        temp_dir.path().display()
+    );
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated_config = config.validate().unwrap();
+    assert!(!validated_config.schema().is_empty());
+
+    let test_cases = [
+        // a rule has to say which notes it covers
+        "  - properties:\n      status: { required: true }",
+        "  - folder: people\n    properties:\n      status: { type: text }",
+        "  - folder: \"people/[\"\n    properties:\n      status: { required: true }",
+    ];
+
+    for rule in test_cases {
+        let yaml = format!(
+            "obsidian_path: {}\nschema:\n{}",
+            temp_dir.path().display(),
+            rule
+        );
+        let config: Config = serde_yaml::from_str(&yaml).unwrap();
+        assert!(config.validate().is_err(), "Failed for: {}", rule);
+    }
+}
+
+#[test]
+fn test_property_operations() {
+    let temp_dir = TempDir::new().unwrap();
+    let yaml = format!(
+        r#"
+obsidian_path: {}
+property_operations:
+  - folder: projects
+    where: {{ status: done }}
+    rename: {{ type: kind }}
+    set: {{ status: archived }}
+    add_to_list: {{ tags: archived }}"#,
+        temp_dir.path().display()
+    );
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated_config = config.validate().unwrap();
+    let operation = validated_config
+        .property_operations()
+        .iter()
+        .next()
+        .unwrap();
+    assert_eq!(
+        operation.filter,
+        vec![(
+            "status".to_string(),
+            serde_yaml::Value::String("done".to_string())
+        )]
+    );
+    // renames come first
+    assert!(matches!(
+        operation.actions[0],
+        PropertyAction::Rename { .. }
+    ));
+    assert_eq!(operation.actions.len(), 3);
+
+    let test_cases = [
+        // nothing to do
+        "  - folder: projects",
+        "  - folder: \"projects/[\"\n    remove: [draft]",
+        // the date properties are ok's to manage
+        "  - set: { date_modified: \"[[2024-01-15]]\" }",
+        "  - rename: { created: date_created }",
+    ];
+
+    for operation in test_cases {
+        let yaml = format!(
+            "obsidian_path: {}\nproperty_operations:\n{}",
+            temp_dir.path().display(),
+            operation
+        );
+        let config: Config = serde_yaml::from_str(&yaml).unwrap();
+        assert!(config.validate().is_err(), "Failed for: {}", operation);
+    }
+}
+
+#[test]
+fn test_skip_regions() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = |skip_regions: &str| {
+        let yaml = format!(
+            "obsidian_path: {}\nback_populate:\n  skip_regions: {}",
+            temp_dir.path().display(),
+            skip_regions
+        );
+        serde_yaml::from_str::<Config>(&yaml).unwrap().validate()
+    };
+
+    assert_eq!(
+        config("[headings, callouts]").unwrap().skip_regions(),
+        [SkipRegion::Headings, SkipRegion::Callouts]
+    );
+    assert!(config("[]").unwrap().skip_regions().is_empty());
+    assert!(config("[tables]").is_err());
+
+    let yaml = format!("obsidian_path: {}", temp_dir.path().display());
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    assert_eq!(
+        config.validate().unwrap().skip_regions(),
+        SkipRegion::defaults()
+    );
+}
@@ -4,44 +4,134 @@ pub const ERROR_DETAILS: &str = "error details:";
 pub const ERROR_OCCURRED: &str = "error occurred";
 pub const ERROR_SOURCE: &str = "error source:";
 pub const ERROR_TYPE: &str = "error type:";
+pub const FORMAT_DATE: &str = "%Y-%m-%d";
 pub const FORMAT_TIME_STAMP: &str = "%Y-%m-%d %H:%M:%S";
 pub const MILLISECONDS: &str = "ms";
 pub const MODE_APPLY_CHANGES: &str = "apply changes is on - changes will be applied";
 pub const MODE_APPLY_CHANGES_OFF: &str = "apply changes is off - no changes will be applied";
+pub const MODE_DISABLED: &str = "disabled in config:";
+pub const PERSIST_CONFLICT: &str = "changed since it was read - not updated:";
 pub const OBSIDIAN_KNIFE: &str = "obsidian knife - aka \"ok\"";
 pub const RELEASE: &str = "release";
 pub const TOTAL_TIME: &str = "total time";
-pub const USAGE: &str = "usage: obsidian_knife <obsidian_folder/config_file.md>";
 
 // config stuff
 // the DEFAULT_MEDIA_PATH could be a configuration parameter as it's really specific to my repo
+pub const DATE_FORMAT_DATE: &str = "date";
+pub const DATE_FORMAT_DATETIME: &str = "datetime";
+pub const DATE_SOURCE_CONTENT_HASH: &str = "content_hash";
+pub const DATE_SOURCE_FILE_SYSTEM: &str = "file_system";
+pub const DATE_SOURCE_GIT: &str = "git";
+pub const DEFAULT_DATE_CREATED_FIX_PROPERTY: &str = "date_created_fix";
+pub const DEFAULT_DATE_CREATED_PROPERTY: &str = "date_created";
+pub const DEFAULT_DATE_MODIFIED_PROPERTY: &str = "date_modified";
 pub const DEFAULT_MEDIA_PATH: &str = "conf/media";
 pub const DEFAULT_OUTPUT_FOLDER: &str = "obsidian_knife";
 pub const DEFAULT_TIMEZONE: &str = "America/New_York";
+pub const FEATURE_BACK_POPULATE: &str = "back_populate";
+pub const FEATURE_DATES: &str = "dates";
+pub const FEATURE_IMAGE_DELETION: &str = "images.delete_files";
+pub const FEATURE_IMAGE_REFERENCES: &str = "images.update_references";
+pub const ERROR_NOT_A_GIT_REPOSITORY: &str =
+    "date source git needs the vault to be in a git repository: ";
 pub const ERROR_NOT_FOUND: &str = "file not found: ";
 pub const ERROR_READING: &str = "error reading config file ";
 pub const ERROR_BACK_POPULATE_FILE_FILTER: &str = "back_populate_filter_filter cannot be empty";
 pub const ERROR_OUTPUT_FOLDER: &str = "output_folder cannot be empty";
+pub const LINK_DENSITY_ALL: &str = "all";
+pub const LINK_DENSITY_FIRST_PER_NOTE: &str = "first_per_note";
+pub const LINK_DENSITY_FIRST_PER_PARAGRAPH: &str = "first_per_paragraph";
+pub const LINK_DENSITY_FIRST_PER_SECTION: &str = "first_per_section";
 pub const OBSIDIAN_HIDDEN_FOLDER: &str = ".obsidian";
+pub const OBSIDIAN_TYPES_FILE: &str = "types.json";
+pub const OBSIDIAN_TYPE_ALIASES: &str = "aliases";
+pub const OBSIDIAN_TYPE_CHECKBOX: &str = "checkbox";
+pub const OBSIDIAN_TYPE_DATE: &str = "date";
+pub const OBSIDIAN_TYPE_DATETIME: &str = "datetime";
+pub const OBSIDIAN_TYPE_MULTITEXT: &str = "multitext";
+pub const OBSIDIAN_TYPE_NUMBER: &str = "number";
+pub const OBSIDIAN_TYPE_TAGS: &str = "tags";
+pub const OBSIDIAN_TYPE_TEXT: &str = "text";
+pub const OUTPUT_JSON_FILE: &str = "obsidian knife output.json";
 pub const OUTPUT_MARKDOWN_FILE: &str = "obsidian knife output.md";
+pub const OUTPUT_PATCH_FILE: &str = "obsidian knife output.patch";
+pub const RESOLUTION_CONFIGURED: &str = "configured";
+pub const RESOLUTION_LINKED_IN_NOTE: &str = "linked_in_note";
+pub const RESOLUTION_MOST_LINKED: &str = "most_linked";
+pub const RESOLUTION_SAME_FOLDER: &str = "same_folder";
+pub const SCHEMA_TYPE_BOOL: &str = "bool";
+pub const SCHEMA_TYPE_DATE: &str = "date";
+pub const SCHEMA_TYPE_LIST: &str = "list";
+pub const SCHEMA_TYPE_NUMBER: &str = "number";
+pub const SCHEMA_TYPE_STRING: &str = "string";
+pub const SCHEMA_TYPE_WIKILINK: &str = "wikilink";
+pub const SKIP_REGION_BLOCKQUOTES: &str = "blockquotes";
+pub const SKIP_REGION_CALLOUTS: &str = "callouts";
+pub const SKIP_REGION_COMMENTS: &str = "comments";
+pub const SKIP_REGION_HEADINGS: &str = "headings";
+pub const SKIP_REGION_HTML: &str = "html";
+pub const SKIP_REGION_INDENTED_CODE: &str = "indented_code";
+pub const SKIP_REGION_INLINE_CODE: &str = "inline_code";
+pub const SKIP_REGION_MATH: &str = "math";
+pub const TAGS_PROPERTY: &str = "tags";
 
 // cache stuff
 pub const CACHE_FOLDER: &str = ".ok";
 pub const CACHE_FILE: &str = "obsidian_knife_cache.json";
+pub const CREATED_DATE_STORE_FILE: &str = "obsidian_knife_created_dates.json";
+pub const DATE_STORE_FILE: &str = "obsidian_knife_dates.json";
+pub const FORMAT_JOURNAL_RUN_ID: &str = "%Y%m%d-%H%M%S-%3f";
+pub const JOURNAL_BLOB_EXTENSION: &str = "blob";
+pub const JOURNAL_CHANGED_SINCE: &str =
+    "not undone - changed since the run, undo with --force to overwrite:";
+pub const JOURNAL_FOLDER: &str = "journal";
+pub const JOURNAL_MANIFEST: &str = "manifest.json";
+pub const JOURNAL_NONE: &str = "there are no journals to undo";
+pub const JOURNAL_NOT_FOUND: &str = "no journal found for run id";
+pub const JOURNAL_PRUNED: &str = "journals pruned:";
+pub const JOURNAL_RECORDED: &str = "journal recorded - undo with run id";
+pub const JOURNAL_RESTORED: &str = "files restored from run id";
 pub const CACHE_INFO_CREATE_NEW: &str = "cache file missing - creating new cache:";
 pub const CACHE_INFO_CORRUPTED: &str = "cache corrupted, creating new cache:";
 
+// exit code bits for `ok check` - combined when more than one category has findings
+// 1 is left alone as it's what rust returns when main returns an error
+pub const EXIT_PENDING_BACK_POPULATE: u8 = 2;
+pub const EXIT_AMBIGUOUS_MATCHES: u8 = 4;
+pub const EXIT_INVALID_WIKILINKS: u8 = 8;
+pub const EXIT_IMAGE_PROBLEMS: u8 = 16;
+pub const EXIT_FRONTMATTER_ISSUES: u8 = 32;
+
 //markdown outline levels
 pub const LEVEL1: &str = "#";
 pub const LEVEL2: &str = "##";
 pub const LEVEL3: &str = "###";
 pub const LEVEL4: &str = "####";
 
+// approval stuff
+pub const APPROVAL_NO_CHANGE_IDS: &str =
+    "nothing to apply - run `ok report` and check the changes to apply in";
+pub const APPROVAL_OUTPUT_MISSING: &str =
+    "nothing to apply - run `ok report` first, the output file doesn't exist:";
+pub const APPROVED_CHANGES: &str = "applying approved changes from the output file:";
+pub const CHANGE_BACK_POPULATE: &str = "back_populate";
+pub const CHANGE_DATES: &str = "dates";
+pub const CHANGE_FRONTMATTER_REPAIR: &str = "frontmatter_repair";
+pub const CHANGE_ID_LENGTH: usize = 10;
+pub const CHANGE_ID_PREFIX: &str = "ok-";
+pub const CHANGE_IMAGE: &str = "image";
+pub const CHANGE_MISSING_IMAGE_REFERENCE: &str = "missing_image_reference";
+pub const CHANGE_PROPERTIES: &str = "properties";
+pub const CHANGE_PROPERTY_TYPE: &str = "property_type";
+pub const CHANGE_SCHEMA_FIX: &str = "schema_fix";
+pub const CHANGE_SIMPLIFY_WIKILINK: &str = "simplify_wikilink";
+
 // files and extensions
 pub const DS_STORE: &str = ".DS_Store";
 pub const IMAGE_EXTENSIONS: [&str; 6] = ["jpg", "png", "jpeg", "tiff", "pdf", "gif"];
 pub const MARKDOWN_EXTENSION: &str = "md";
 pub const MARKDOWN_SUFFIX: &str = ".md";
+pub const TEMP_FILE_EXTENSION: &str = "ok-tmp";
 pub const TIFF_EXTENSION: &str = "tiff";
 
 // matching stuff
@@ -57,6 +147,7 @@ pub const OPENING_WIKILINK: &str = "[[";
 
 // report &str's
 pub const ACTION: &str = "action";
+pub const APPROVE: &str = "approve";
 pub const AFTER: &str = "after";
 pub const BACK_POPULATE: &str = "back populate";
 pub const BACK_POPULATE_FILE_FILTER_PREFIX: &str =
@@ -64,16 +155,26 @@ pub const BACK_POPULATE_FILE_FILTER_PREFIX: &str =
 pub const BACK_POPULATE_FILE_FILTER_SUFFIX: &str =
     "remove it from config if you want to process all files";
 pub const BEFORE: &str = "before";
+pub const CHECK_AMBIGUOUS_MATCHES: &str = "ambiguous matches:";
+pub const CHECK_CLEAN: &str = "no issues found";
+pub const CHECK_EXIT_CODE: &str = "exit code:";
+pub const CHECK_FRONTMATTER_ISSUES: &str = "frontmatter issues:";
+pub const CHECK_IMAGE_PROBLEMS: &str = "image problems:";
+pub const CHECK_INVALID_WIKILINKS: &str = "invalid wikilinks:";
+pub const CHECK_PENDING_BACK_POPULATE: &str = "back populate changes:";
 pub const COLON: &str = ":";
+pub const COMMA: &str = ",";
 pub const CONFIG_EXPECT: &str = "ValidatedConfig required for this report";
 pub const DELETED: &str = "deleted";
 pub const DUPLICATE: &str = "duplicate";
 pub const DUPLICATES: &str = "duplicates";
 pub const DUPLICATE_IMAGES: &str = "duplicate images";
+pub const EXPECTED: &str = "expected";
 pub const FILE: &str = "file";
 pub const FOUND: &str = "found";
 pub const FRONTMATTER: &str = "frontmatter";
 pub const FRONTMATTER_ISSUES: &str = "frontmatter issues";
+pub const FRONTMATTER_REPAIRS: &str = "frontmatter repairs";
 pub const IMAGE: &str = "image";
 pub const IMAGES: &str = "images";
 pub const IMAGE_FILE: &str = "image file";
@@ -82,6 +183,7 @@ pub const IN: &str = "in";
 pub const INFO: &str = "info";
 pub const INVALID: &str = "invalid";
 pub const INVALID_WIKILINKS: &str = "invalid wikilinks";
+pub const ISSUE: &str = "issue";
 pub const IN_CHANGESET: &str = "in changeset";
 pub const LINE: &str = "line";
 pub const MATCHES: &str = "matches";
@@ -96,14 +198,29 @@ pub const OCCURRENCES: &str = "occurrences";
 pub const OF: &str = "of";
 pub const PATH: &str = "path";
 pub const POSITION: &str = "position";
+pub const PROPERTY: &str = "property";
+pub const PROPERTY_CHANGES: &str = "property changes";
+pub const PROPERTY_TYPES: &str = "property types";
 pub const REASON: &str = "reason";
+pub const REPAIRS: &str = "repairs";
+pub const RESOLVED_AMBIGUOUS_MATCHES: &str = "resolved ambiguous matches";
+pub const RESOLVED_BY: &str = "resolved by";
 pub const REFERENCED_BY: &str = "referenced by";
 pub const REFERENCE_CHANGE: &str = "reference change";
 pub const REFERENCE_REMOVED: &str = " - reference removed";
 pub const REFERENCE_WILL_BE_REMOVED: &str = "reference will be removed";
+pub const RENDERS_AS: &str = "renders as";
 pub const SAMPLE: &str = "sample";
+pub const SCHEMA: &str = "schema";
+pub const SCHEMA_FIX_BY_HAND: &str = "the ones without an action have to be fixed by hand";
+pub const SCHEMA_VIOLATIONS: &str = "schema violations";
+pub const SET_TO: &str = "set to";
+pub const SIMPLIFY_WIKILINKS: &str = "simplify wikilinks";
+pub const SKIPPED: &str = "skipped";
+pub const SKIPPED_BY_LINK_DENSITY: &str = "skipped by link_density";
 pub const SOURCE_TEXT: &str = "source text";
 pub const TEXT: &str = "text";
+pub const THAT_OK_CAN_REPAIR: &str = "that ok can repair";
 pub const THUMBNAIL: &str = "thumbnail";
 pub const TIFF: &str = "TIFF";
 pub const TIFF_IMAGES: &str = "TIFF images";
@@ -112,9 +229,13 @@ pub const UNKNOWN: &str = "unknown";
 pub const UNREFERENCED_IMAGES: &str = "unreferenced images";
 pub const UPDATE: &str = "update";
 pub const UPDATED: &str = " - updated";
+pub const VALUE: &str = "value";
 pub const WIKILINKS: &str = "wikilinks";
 pub const WILL_BE_BACK_POPULATED: &str = "will be back populated";
+pub const WILL_BE_SET_TO: &str = "will be set to";
+pub const WILL_BE_SIMPLIFIED: &str = "will be simplified";
 pub const WILL_BE_UPDATED: &str = " - will be updated";
+pub const WITH_INCONSISTENT_TYPES: &str = "with inconsistent types";
 pub const WILL_DELETE: &str = "will delete";
 pub const WILL_REPLACE_WITH: &str = "will replace with";
 pub const YAML_APPLY_CHANGES: &str = "apply_changes: ";
@@ -129,12 +250,14 @@ pub const THUMBNAIL_WIDTH: usize = 50;
 
 #[derive(Debug, Clone, Copy)]
 pub enum Phrase {
+    Change(usize),
     File(usize),
     Has(usize),
     Image(usize),
     Issue(usize),
     Is(usize),
     Match(usize),
+    Property(usize),
     Reason(usize),
     Reference(usize),
     Target(usize),
@@ -146,6 +269,8 @@ pub enum Phrase {
 impl Phrase {
     pub const fn pluralize(&self) -> &'static str {
         match self {
+            Phrase::Change(1) => "change",
+            Phrase::Change(_) => "changes",
             Phrase::File(1) => "file",
             Phrase::File(_) => "files",
             Phrase::Has(1) => "has a",
@@ -158,6 +283,8 @@ impl Phrase {
             Phrase::Is(_) => "are",
             Phrase::Match(1) => "match",
             Phrase::Match(_) => "matches",
+            Phrase::Property(1) => "property",
+            Phrase::Property(_) => "properties",
             Phrase::Reason(1) => "reason",
             Phrase::Reason(_) => "reasons",
             Phrase::Reference(1) => "reference",
@@ -175,12 +302,14 @@ impl Phrase {
 
     pub const fn value(&self) -> usize {
         match self {
-            Phrase::File(value)
+            Phrase::Change(value)
+            | Phrase::File(value)
             | Phrase::Has(value)
             | Phrase::Image(value)
             | Phrase::Issue(value)
             | Phrase::Is(value)
             | Phrase::Match(value)
+            | Phrase::Property(value)
             | Phrase::Reason(value)
             | Phrase::Reference(value)
             | Phrase::Target(value)
@@ -0,0 +1,239 @@
+#[cfg(test)]
+mod date_settings_tests;
+
+use crate::constants::*;
+use crate::date_source::DateSource;
+use crate::markdown_file::DateValidationIssue;
+use crate::wikilink;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
+use chrono_tz::Tz;
+use serde_yaml::{Mapping, Value};
+
+/// how ok writes the dates it manages - and what it accepts when reading them back
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateFormat {
+    /// 2024-01-15
+    Date,
+    /// 2024-01-15T10:30:00-05:00
+    DateTime,
+    /// any chrono format string, e.g. "%B %-d, %Y"
+    Custom(String),
+}
+
+impl From<&str> for DateFormat {
+    fn from(format: &str) -> Self {
+        match format {
+            DATE_FORMAT_DATE => DateFormat::Date,
+            DATE_FORMAT_DATETIME => DateFormat::DateTime,
+            custom => DateFormat::Custom(custom.to_string()),
+        }
+    }
+}
+
+/// the names of the frontmatter properties ok manages
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateProperties {
+    pub created: String,
+    pub created_fix: String,
+    pub modified: String,
+}
+
+impl Default for DateProperties {
+    fn default() -> Self {
+        Self {
+            created: DEFAULT_DATE_CREATED_PROPERTY.to_string(),
+            created_fix: DEFAULT_DATE_CREATED_FIX_PROPERTY.to_string(),
+            modified: DEFAULT_DATE_MODIFIED_PROPERTY.to_string(),
+        }
+    }
+}
+
+impl DateProperties {
+    // FrontMatter always deserializes the dates from the default property names
+    fn pairs(&self) -> [(&str, &str); 3] {
+        [
+            (DEFAULT_DATE_CREATED_PROPERTY, self.created.as_str()),
+            (DEFAULT_DATE_CREATED_FIX_PROPERTY, self.created_fix.as_str()),
+            (DEFAULT_DATE_MODIFIED_PROPERTY, self.modified.as_str()),
+        ]
+    }
+
+    /// swaps each configured property with its default name - swapping twice restores the
+    /// mapping so the same call is used when reading and when writing frontmatter
+    ///
+    /// a note that happens to have an unrelated property with a default name keeps it as
+    /// it is parked under the configured name in the meantime
+    pub fn swap(&self, mapping: &mut Mapping) {
+        for (default_name, configured) in self.pairs() {
+            if default_name == configured {
+                continue;
+            }
+            let default_key = Value::String(default_name.to_string());
+            let configured_key = Value::String(configured.to_string());
+            let default_value = mapping.remove(&default_key);
+            let configured_value = mapping.remove(&configured_key);
+            if let Some(value) = configured_value {
+                mapping.insert(default_key, value);
+            }
+            if let Some(value) = default_value {
+                mapping.insert(configured_key, value);
+            }
+        }
+    }
+
+    /// every name must be unique and none may reuse another property's default name as that
+    /// would make the swap ambiguous
+    pub fn validate(&self) -> Result<(), String> {
+        let pairs = self.pairs();
+        for (idx, (_, configured)) in pairs.iter().enumerate() {
+            if configured.trim().is_empty() {
+                return Err("date property names cannot be empty".to_string());
+            }
+            let conflict = pairs
+                .iter()
+                .enumerate()
+                .any(|(other_idx, (default, other))| {
+                    other_idx != idx && (configured == other || configured == default)
+                });
+            if conflict {
+                return Err(format!(
+                    "date property '{}' is used more than once",
+                    configured
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DateSettings {
+    pub format: DateFormat,
+    pub properties: DateProperties,
+    pub source: DateSource,
+    pub wikilink: bool,
+}
+
+impl Default for DateSettings {
+    fn default() -> Self {
+        Self {
+            format: DateFormat::Date,
+            properties: DateProperties::default(),
+            source: DateSource::default(),
+            wikilink: true,
+        }
+    }
+}
+
+impl DateSettings {
+    /// the date as it's written to the frontmatter, in the operational timezone
+    pub fn format_date(&self, date: DateTime<Utc>, operational_timezone: &str) -> String {
+        let tz: Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
+        let local_date = date.with_timezone(&tz);
+        let formatted = match &self.format {
+            DateFormat::Date => local_date.format(FORMAT_DATE).to_string(),
+            DateFormat::DateTime => local_date.to_rfc3339_opts(SecondsFormat::Secs, false),
+            DateFormat::Custom(format) => local_date.format(format).to_string(),
+        };
+
+        if self.wikilink {
+            format!("{}{}{}", OPENING_WIKILINK, formatted, CLOSING_WIKILINK)
+        } else {
+            formatted
+        }
+    }
+
+    /// the calendar date of a frontmatter value in the operational timezone - the value has to
+    /// match the configured format exactly, brackets included
+    pub fn parse_date(
+        &self,
+        value: &str,
+        operational_timezone: &str,
+    ) -> Result<NaiveDate, DateValidationIssue> {
+        let value = value.trim();
+        let is_wikilink = wikilink::is_wikilink(Some(value));
+
+        if self.wikilink && !is_wikilink {
+            return Err(DateValidationIssue::InvalidWikilink);
+        }
+        if !self.wikilink && is_wikilink {
+            return Err(DateValidationIssue::InvalidDateFormat);
+        }
+
+        self.parse_date_text(strip_wikilink(value), operational_timezone)
+            .ok_or(DateValidationIssue::InvalidDateFormat)
+    }
+
+    /// a valid frontmatter date as noon of that day in the operational timezone
+    pub fn parse_date_time(
+        &self,
+        value: &str,
+        operational_timezone: &str,
+    ) -> Option<DateTime<Utc>> {
+        let tz: Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
+        let date = self.parse_date(value, operational_timezone).ok()?;
+        date.and_hms_opt(12, 0, 0)
+            .and_then(|noon| noon.and_local_timezone(tz).single())
+            .map(|noon| noon.with_timezone(&Utc))
+    }
+
+    /// date_created_fix is typed by hand so it's accepted with or without brackets and in
+    /// either the configured format or a plain iso date
+    pub fn parse_fix_date(&self, value: &str, operational_timezone: &str) -> Option<NaiveDate> {
+        let text = strip_wikilink(value.trim()).trim_matches('"').trim();
+        self.parse_date_text(text, operational_timezone)
+            .or_else(|| NaiveDate::parse_from_str(text, FORMAT_DATE).ok())
+    }
+
+    fn parse_date_text(&self, text: &str, operational_timezone: &str) -> Option<NaiveDate> {
+        let tz: Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
+        match &self.format {
+            DateFormat::Date => NaiveDate::parse_from_str(text, FORMAT_DATE).ok(),
+            DateFormat::DateTime => DateTime::parse_from_rfc3339(text)
+                .ok()
+                .map(|date| date.with_timezone(&tz).date_naive()),
+            DateFormat::Custom(format) => DateTime::parse_from_str(text, format)
+                .map(|date| date.with_timezone(&tz).date_naive())
+                .or_else(|_| NaiveDateTime::parse_from_str(text, format).map(|date| date.date()))
+                .or_else(|_| NaiveDate::parse_from_str(text, format))
+                .ok(),
+        }
+    }
+
+    /// a custom format has to be valid chrono and has to read back to the date it wrote
+    pub fn validate(&self) -> Result<(), String> {
+        self.properties.validate()?;
+
+        if let DateFormat::Custom(format) = &self.format {
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(format!("invalid date format: {}", format));
+            }
+
+            let sample = NaiveDate::from_ymd_opt(2024, 11, 23)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .map(|date| date.and_utc())
+                .unwrap();
+            let formatted = self.format_date(sample, DEFAULT_TIMEZONE);
+            if self.parse_date(&formatted, DEFAULT_TIMEZONE) != Ok(sample.date_naive()) {
+                return Err(format!(
+                    "date format must include the year, month and day: {}",
+                    format
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn strip_wikilink(value: &str) -> &str {
+    if wikilink::is_wikilink(Some(value)) {
+        value
+            .trim_start_matches(OPENING_WIKILINK)
+            .trim_end_matches(CLOSING_WIKILINK)
+            .trim()
+    } else {
+        value
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+use crate::test_utils;
+
+fn settings(format: &str, wikilink: bool) -> DateSettings {
+    DateSettings {
+        format: DateFormat::from(format),
+        wikilink,
+        ..DateSettings::default()
+    }
+}
+
+fn renamed_properties() -> DateProperties {
+    DateProperties {
+        created: "created".to_string(),
+        created_fix: "created_fix".to_string(),
+        modified: "updated".to_string(),
+    }
+}
+
+#[test]
+fn test_format_date() {
+    // 2024-01-15 23:30 in New York is already the 16th in UTC
+    let date = test_utils::eastern_midnight(2024, 1, 15) + chrono::Duration::minutes(23 * 60 + 30);
+
+    let test_cases = [
+        (settings(DATE_FORMAT_DATE, true), "[[2024-01-15]]"),
+        (settings(DATE_FORMAT_DATE, false), "2024-01-15"),
+        (
+            settings(DATE_FORMAT_DATETIME, false),
+            "2024-01-15T23:30:00-05:00",
+        ),
+        (settings("%B %-d, %Y", true), "[[January 15, 2024]]"),
+    ];
+
+    for (settings, expected) in test_cases {
+        assert_eq!(
+            settings.format_date(date, DEFAULT_TIMEZONE),
+            expected,
+            "Failed for format: {:?}",
+            settings.format
+        );
+    }
+}
+
+#[test]
+fn test_parse_date_follows_configuration() {
+    let expected = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+    let test_cases = [
+        (
+            settings(DATE_FORMAT_DATE, true),
+            "[[2024-01-15]]",
+            Ok(expected),
+        ),
+        (
+            settings(DATE_FORMAT_DATE, true),
+            "2024-01-15",
+            Err(DateValidationIssue::InvalidWikilink),
+        ),
+        (
+            settings(DATE_FORMAT_DATE, false),
+            "2024-01-15",
+            Ok(expected),
+        ),
+        (
+            settings(DATE_FORMAT_DATE, false),
+            "[[2024-01-15]]",
+            Err(DateValidationIssue::InvalidDateFormat),
+        ),
+        (
+            settings(DATE_FORMAT_DATE, true),
+            "[[01/15/2024]]",
+            Err(DateValidationIssue::InvalidDateFormat),
+        ),
+        // the offset is converted into the operational timezone before taking the date
+        (
+            settings(DATE_FORMAT_DATETIME, false),
+            "2024-01-16T03:00:00Z",
+            Ok(expected),
+        ),
+        (
+            settings("%d.%m.%Y %H:%M", true),
+            "[[15.01.2024 08:00]]",
+            Ok(expected),
+        ),
+    ];
+
+    for (settings, value, expected) in test_cases {
+        assert_eq!(
+            settings.parse_date(value, DEFAULT_TIMEZONE),
+            expected,
+            "Failed for value: {}",
+            value
+        );
+    }
+}
+
+#[test]
+fn test_parse_fix_date_is_lenient() {
+    let expected = Some(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+    let settings = settings("%d.%m.%Y", false);
+
+    assert_eq!(
+        settings.parse_fix_date("[[15.01.2024]]", DEFAULT_TIMEZONE),
+        expected
+    );
+    assert_eq!(
+        settings.parse_fix_date("2024-01-15", DEFAULT_TIMEZONE),
+        expected
+    );
+    assert_eq!(settings.parse_fix_date("soon", DEFAULT_TIMEZONE), None);
+}
+
+#[test]
+fn test_swap_round_trips() {
+    let yaml = "created: a\nupdated: b\ndate_created: unrelated\ntitle: note";
+    let original: Mapping = serde_yaml::from_str(yaml).unwrap();
+    let properties = renamed_properties();
+
+    let mut swapped = original.clone();
+    properties.swap(&mut swapped);
+
+    assert_eq!(swapped.get("date_created"), Some(&Value::from("a")));
+    assert_eq!(swapped.get("date_modified"), Some(&Value::from("b")));
+    assert_eq!(swapped.get("created"), Some(&Value::from("unrelated")));
+    assert_eq!(swapped.get("title"), Some(&Value::from("note")));
+
+    properties.swap(&mut swapped);
+    assert_eq!(swapped, original);
+}
+
+#[test]
+fn test_validate() {
+    assert!(DateSettings::default().validate().is_ok());
+    assert!(DateSettings {
+        properties: renamed_properties(),
+        ..settings("%Y/%m/%d %H:%M", true)
+    }
+    .validate()
+    .is_ok());
+
+    let invalid = [
+        DateSettings {
+            properties: DateProperties {
+                modified: "created".to_string(),
+                ..renamed_properties()
+            },
+            ..DateSettings::default()
+        },
+        DateSettings {
+            properties: DateProperties {
+                created: DEFAULT_DATE_MODIFIED_PROPERTY.to_string(),
+                ..DateProperties::default()
+            },
+            ..DateSettings::default()
+        },
+        DateSettings {
+            properties: DateProperties {
+                created: " ".to_string(),
+                ..DateProperties::default()
+            },
+            ..DateSettings::default()
+        },
+        settings("%Y-%m-%Q", true),
+        settings("%H:%M", true),
+    ];
+
+    for settings in invalid {
+        assert!(
+            settings.validate().is_err(),
+            "Expected error: {:?}",
+            settings
+        );
+    }
+}
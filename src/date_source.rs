@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod date_source_tests;
+#[cfg(test)]
+mod git_dates_tests;
+
+mod git_dates;
+
+pub use git_dates::GitDates;
+
+use crate::constants::*;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// where ok gets the dates it compares date_created and date_modified against
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum DateSource {
+    /// the dates remembered in the date store - date_modified only moves when the note's own
+    /// content changes so a sync client touching the file doesn't count
+    ContentHash,
+    /// created and modified times of the file
+    #[default]
+    FileSystem,
+    /// the commits in the git repository the vault lives in - the first commit that added the
+    /// note and the last one that changed its content
+    Git,
+}
+
+impl FromStr for DateSource {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            DATE_SOURCE_CONTENT_HASH => Ok(DateSource::ContentHash),
+            DATE_SOURCE_FILE_SYSTEM => Ok(DateSource::FileSystem),
+            DATE_SOURCE_GIT => Ok(DateSource::Git),
+            unknown => Err(format!("unknown date source: {}", unknown)),
+        }
+    }
+}
+
+/// the reference dates a note's frontmatter is validated against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceDates {
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DateStoreEntry {
+    /// hash of the note's content and the frontmatter properties ok doesn't manage
+    pub hash: String,
+    pub created: DateTime<Utc>,
+    pub modified: DateTime<Utc>,
+}
+
+/// the last seen content hash and dates of every note, kept in the .ok folder - keyed by the
+/// path relative to obsidian_path so the vault can move
+#[derive(Debug, Clone)]
+pub struct DateStore {
+    entries: BTreeMap<PathBuf, DateStoreEntry>,
+    obsidian_path: PathBuf,
+    store_file_path: PathBuf,
+}
+
+impl DateStore {
+    pub fn store_file_path(obsidian_path: &Path) -> PathBuf {
+        obsidian_path.join(CACHE_FOLDER).join(DATE_STORE_FILE)
+    }
+
+    /// a missing or corrupted store starts empty - every note is then seen for the first time
+    pub fn load(obsidian_path: &Path) -> Self {
+        let store_file_path = Self::store_file_path(obsidian_path);
+
+        Self {
+            entries: relative_entries(load_entries(&store_file_path), obsidian_path),
+            obsidian_path: obsidian_path.to_path_buf(),
+            store_file_path,
+        }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&DateStoreEntry> {
+        self.entries.get(&relative_path(path, &self.obsidian_path))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// an unchanged hash keeps the remembered modified date, a changed one takes the file's
+    ///
+    /// the first time a note is seen its frontmatter dates are trusted if they're valid so
+    /// switching to this source doesn't rewrite every note
+    pub fn reference_dates(
+        &self,
+        path: &Path,
+        hash: &str,
+        file_system: ReferenceDates,
+        frontmatter_created: Option<DateTime<Utc>>,
+        frontmatter_modified: Option<DateTime<Utc>>,
+    ) -> ReferenceDates {
+        match self.get(path) {
+            Some(entry) => ReferenceDates {
+                created: entry.created,
+                modified: if entry.hash == hash {
+                    entry.modified
+                } else {
+                    file_system.modified
+                },
+            },
+            None => ReferenceDates {
+                created: frontmatter_created.unwrap_or(file_system.created),
+                modified: frontmatter_modified.unwrap_or(file_system.modified),
+            },
+        }
+    }
+
+    pub fn record(&mut self, path: PathBuf, entry: DateStoreEntry) {
+        self.entries
+            .insert(relative_path(&path, &self.obsidian_path), entry);
+    }
+
+    /// forget notes that no longer exist
+    pub fn retain(&mut self, valid_paths: &HashSet<&Path>) {
+        let obsidian_path = &self.obsidian_path;
+        self.entries
+            .retain(|path, _| valid_paths.contains(obsidian_path.join(path).as_path()));
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        save_entries(&self.store_file_path, &self.entries)
+    }
+}
+
+/// the creation date ok intends every note to have, kept in the .ok folder
+///
+/// it stands in for the file's birth time where ok can't set it, so date_created_fix sticks and
+/// writing a note - which gives it a new birth time there - doesn't move date_created
+///
+/// keyed by the path relative to obsidian_path like the date store
+#[derive(Debug, Clone)]
+pub struct CreatedDateStore {
+    entries: BTreeMap<PathBuf, DateTime<Utc>>,
+    obsidian_path: PathBuf,
+    store_file_path: PathBuf,
+}
+
+impl CreatedDateStore {
+    pub fn store_file_path(obsidian_path: &Path) -> PathBuf {
+        obsidian_path
+            .join(CACHE_FOLDER)
+            .join(CREATED_DATE_STORE_FILE)
+    }
+
+    /// a missing or corrupted store starts empty - the birth times are used until the next save
+    pub fn load(obsidian_path: &Path) -> Self {
+        let store_file_path = Self::store_file_path(obsidian_path);
+
+        Self {
+            entries: relative_entries(load_entries(&store_file_path), obsidian_path),
+            obsidian_path: obsidian_path.to_path_buf(),
+            store_file_path,
+        }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<DateTime<Utc>> {
+        self.entries
+            .get(&relative_path(path, &self.obsidian_path))
+            .copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn record(&mut self, path: PathBuf, created: DateTime<Utc>) {
+        self.entries
+            .insert(relative_path(&path, &self.obsidian_path), created);
+    }
+
+    /// forget notes that no longer exist
+    pub fn retain(&mut self, valid_paths: &HashSet<&Path>) {
+        let obsidian_path = &self.obsidian_path;
+        self.entries
+            .retain(|path, _| valid_paths.contains(obsidian_path.join(path).as_path()));
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        save_entries(&self.store_file_path, &self.entries)
+    }
+}
+
+fn load_entries<T: DeserializeOwned + Default>(store_file_path: &Path) -> T {
+    File::open(store_file_path)
+        .ok()
+        .and_then(|file| serde_json::from_reader(BufReader::new(file)).ok())
+        .unwrap_or_default()
+}
+
+fn relative_path(path: &Path, obsidian_path: &Path) -> PathBuf {
+    path.strip_prefix(obsidian_path)
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+/// a store written before entries were relative is keyed by absolute paths
+fn relative_entries<V>(
+    entries: BTreeMap<PathBuf, V>,
+    obsidian_path: &Path,
+) -> BTreeMap<PathBuf, V> {
+    entries
+        .into_iter()
+        .map(|(path, value)| (relative_path(&path, obsidian_path), value))
+        .collect()
+}
+
+fn save_entries<T: Serialize>(
+    store_file_path: &Path,
+    entries: &T,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    if let Some(parent) = store_file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = File::create(store_file_path)?;
+    serde_json::to_writer(file, entries)?;
+    Ok(())
+}
@@ -0,0 +1,287 @@
+use super::*;
+use crate::date_settings::DateSettings;
+use crate::markdown_file::PersistReason;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use crate::validated_config::ValidatedConfig;
+use filetime::FileTime;
+use tempfile::TempDir;
+
+fn file_system_dates() -> ReferenceDates {
+    ReferenceDates {
+        created: test_utils::eastern_midnight(2024, 3, 1),
+        modified: test_utils::eastern_midnight(2024, 3, 2),
+    }
+}
+
+fn entry(hash: &str) -> DateStoreEntry {
+    DateStoreEntry {
+        hash: hash.to_string(),
+        created: test_utils::eastern_midnight(2024, 1, 1),
+        modified: test_utils::eastern_midnight(2024, 1, 2),
+    }
+}
+
+fn content_hash_config(temp_dir: &TempDir) -> ValidatedConfig {
+    validated_config_tests::get_test_validated_config_result(temp_dir, |builder| {
+        builder.date_settings(DateSettings {
+            source: DateSource::ContentHash,
+            ..DateSettings::default()
+        });
+    })
+    .unwrap()
+}
+
+fn modified_date_updated(repository: &ObsidianRepository) -> bool {
+    repository.markdown_files.iter().any(|file| {
+        file.persist_reasons
+            .iter()
+            .any(|reason| matches!(reason, PersistReason::DateModifiedUpdated { .. }))
+    })
+}
+
+#[test]
+fn test_parse_date_source() {
+    assert_eq!(
+        DATE_SOURCE_CONTENT_HASH.parse::<DateSource>(),
+        Ok(DateSource::ContentHash)
+    );
+    assert_eq!(
+        DATE_SOURCE_FILE_SYSTEM.parse::<DateSource>(),
+        Ok(DateSource::FileSystem)
+    );
+    assert!("mtime".parse::<DateSource>().is_err());
+}
+
+#[test]
+fn test_reference_dates() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("note.md");
+    let mut store = DateStore::load(temp_dir.path());
+    let frontmatter_created = Some(test_utils::eastern_midnight(2023, 6, 1));
+
+    // first sighting trusts valid frontmatter dates and falls back to the file system
+    assert_eq!(
+        store.reference_dates(&path, "a", file_system_dates(), frontmatter_created, None),
+        ReferenceDates {
+            created: test_utils::eastern_midnight(2023, 6, 1),
+            modified: test_utils::eastern_midnight(2024, 3, 2),
+        }
+    );
+
+    store.record(path.clone(), entry("a"));
+
+    // unchanged content keeps the remembered dates even though the file was touched
+    assert_eq!(
+        store.reference_dates(&path, "a", file_system_dates(), frontmatter_created, None),
+        ReferenceDates {
+            created: entry("a").created,
+            modified: entry("a").modified,
+        }
+    );
+
+    // changed content takes the file's modified date but never moves created
+    assert_eq!(
+        store.reference_dates(&path, "b", file_system_dates(), frontmatter_created, None),
+        ReferenceDates {
+            created: entry("a").created,
+            modified: file_system_dates().modified,
+        }
+    );
+}
+
+#[test]
+fn test_store_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let kept = temp_dir.path().join("kept.md");
+    let removed = temp_dir.path().join("removed.md");
+
+    let mut store = DateStore::load(temp_dir.path());
+    store.record(kept.clone(), entry("a"));
+    store.record(removed.clone(), entry("b"));
+    store.retain(&HashSet::from([kept.as_path()]));
+    store.save().unwrap();
+
+    let store = DateStore::load(temp_dir.path());
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.get(&kept), Some(&entry("a")));
+
+    // a corrupted store starts over rather than failing the run
+    fs::write(DateStore::store_file_path(temp_dir.path()), "not json").unwrap();
+    assert_eq!(DateStore::load(temp_dir.path()).len(), 0);
+}
+
+#[test]
+fn test_store_survives_vault_move() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut store = DateStore::load(temp_dir.path());
+    store.record(temp_dir.path().join("note.md"), entry("a"));
+    store.save().unwrap();
+
+    let moved_dir = TempDir::new().unwrap();
+    fs::create_dir_all(moved_dir.path().join(CACHE_FOLDER)).unwrap();
+    fs::copy(
+        DateStore::store_file_path(temp_dir.path()),
+        DateStore::store_file_path(moved_dir.path()),
+    )
+    .unwrap();
+
+    let store = DateStore::load(moved_dir.path());
+    assert_eq!(
+        store.get(&moved_dir.path().join("note.md")),
+        Some(&entry("a"))
+    );
+}
+
+#[test]
+fn test_store_written_in_path_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut store = DateStore::load(temp_dir.path());
+    for name in ["c.md", "a.md", "b.md"] {
+        store.record(temp_dir.path().join(name), entry(name));
+    }
+    store.save().unwrap();
+
+    let stored = fs::read_to_string(DateStore::store_file_path(temp_dir.path())).unwrap();
+    let positions: Vec<usize> = ["a.md", "b.md", "c.md"]
+        .iter()
+        .map(|name| stored.find(&format!("\"{}\"", name)).unwrap())
+        .collect();
+    assert!(positions.is_sorted(), "{}", stored);
+}
+
+#[test]
+fn test_absolute_keys_are_made_relative() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("note.md");
+    let absolute: BTreeMap<PathBuf, DateStoreEntry> = BTreeMap::from([(path.clone(), entry("a"))]);
+    save_entries(&DateStore::store_file_path(temp_dir.path()), &absolute).unwrap();
+
+    let store = DateStore::load(temp_dir.path());
+    assert_eq!(store.get(&path), Some(&entry("a")));
+    store.save().unwrap();
+    let stored = fs::read_to_string(DateStore::store_file_path(temp_dir.path())).unwrap();
+    assert!(
+        !stored.contains(&temp_dir.path().display().to_string()),
+        "{}",
+        stored
+    );
+}
+
+#[test]
+fn test_created_date_store_round_trip() {
+    let temp_dir = TempDir::new().unwrap();
+    let kept = temp_dir.path().join("kept.md");
+    let created = test_utils::eastern_midnight(2023, 5, 5);
+
+    let mut store = CreatedDateStore::load(temp_dir.path());
+    store.record(kept.clone(), created);
+    store.record(temp_dir.path().join("removed.md"), created);
+    store.retain(&HashSet::from([kept.as_path()]));
+    store.save().unwrap();
+
+    let store = CreatedDateStore::load(temp_dir.path());
+    assert_eq!(store.len(), 1);
+    assert_eq!(store.get(&kept), Some(created));
+
+    let stored = fs::read_to_string(CreatedDateStore::store_file_path(temp_dir.path())).unwrap();
+    assert!(stored.contains("\"kept.md\""), "{}", stored);
+}
+
+// macos sets the birth time itself so the store is only used elsewhere
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_date_created_fix_sticks_without_settable_birth_time() {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_frontmatter_dates(
+            Some("[[2024-01-01]]".to_string()),
+            Some("[[2024-01-02]]".to_string()),
+        )
+        .with_date_created_fix(Some("2023-05-05".to_string()))
+        .with_fs_dates(
+            test_utils::eastern_midnight(2024, 1, 1),
+            test_utils::eastern_midnight(2024, 1, 2),
+        )
+        .with_content("some content".to_string())
+        .create(&temp_dir, "note.md");
+    let config =
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+    assert!(repository.markdown_files[0]
+        .persist_reasons
+        .contains(&PersistReason::DateCreatedFixApplied));
+    let conflicts = repository.persist().unwrap();
+    repository.save_created_dates(true, &conflicts).unwrap();
+
+    // the write gave the file a new birth time - the recorded date is validated against instead
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let file = &repository.markdown_files[0];
+    assert!(
+        file.persist_reasons.is_empty(),
+        "{:?}",
+        file.persist_reasons
+    );
+    assert_eq!(
+        file.frontmatter.as_ref().unwrap().date_created(),
+        Some(&"[[2023-05-05]]".to_string())
+    );
+}
+
+#[cfg(not(target_os = "macos"))]
+#[test]
+fn test_created_date_store_not_written_without_apply() {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_content("some content".to_string())
+        .create(&temp_dir, "note.md");
+    let config =
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+
+    let repository = ObsidianRepository::new(&config).unwrap();
+    repository.save_created_dates(false, &[]).unwrap();
+    assert!(!CreatedDateStore::store_file_path(temp_dir.path()).exists());
+
+    repository.save_created_dates(true, &[]).unwrap();
+    assert!(CreatedDateStore::store_file_path(temp_dir.path()).exists());
+}
+
+#[test]
+fn test_touched_file_keeps_date_modified() {
+    let temp_dir = TempDir::new().unwrap();
+    let modified = test_utils::eastern_midnight(2024, 1, 2);
+    let file_path = TestFileBuilder::new()
+        .with_frontmatter_dates(
+            Some("[[2024-01-01]]".to_string()),
+            Some("[[2024-01-02]]".to_string()),
+        )
+        .with_fs_dates(test_utils::eastern_midnight(2024, 1, 1), modified)
+        .with_content("some content".to_string())
+        .create(&temp_dir, "note.md");
+    let config = content_hash_config(&temp_dir);
+
+    let repository = ObsidianRepository::new(&config).unwrap();
+    assert!(!modified_date_updated(&repository));
+    repository.save_date_store(false, &[]).unwrap();
+
+    // a sync client touches the file without changing it
+    let touched = test_utils::eastern_midnight(2024, 5, 1);
+    filetime::set_file_mtime(&file_path, FileTime::from_system_time(touched.into())).unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    assert!(!modified_date_updated(&repository));
+    repository.save_date_store(false, &[]).unwrap();
+
+    // an actual edit moves date_modified to the file's modified date
+    let content = fs::read_to_string(&file_path).unwrap();
+    fs::write(
+        &file_path,
+        content.replace("some content", "edited content"),
+    )
+    .unwrap();
+    filetime::set_file_mtime(&file_path, FileTime::from_system_time(touched.into())).unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    assert!(modified_date_updated(&repository));
+}
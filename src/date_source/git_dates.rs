@@ -0,0 +1,226 @@
+use crate::constants::*;
+use crate::date_settings::DateSettings;
+use crate::date_source::ReferenceDates;
+use crate::markdown_file;
+use chrono::{DateTime, Utc};
+use git2::{
+    Commit, Delta, DiffFindOptions, DiffOptions, Oid, Repository, Sort, TreeWalkMode,
+    TreeWalkResult,
+};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// the dates of a note according to the history of the git repository the vault lives in
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitFileDates {
+    /// the first commit that added the note, following renames
+    pub created: DateTime<Utc>,
+    /// the last commit that changed the note's content - commits that only touched the
+    /// properties ok manages don't count so committing ok's own changes doesn't move it again
+    pub modified: DateTime<Utc>,
+    /// content hash of the note as committed at HEAD
+    pub head_hash: String,
+}
+
+/// the git dates of every markdown file committed at HEAD, keyed by absolute path
+#[derive(Debug, Clone, Default)]
+pub struct GitDates {
+    files: HashMap<PathBuf, GitFileDates>,
+}
+
+// a note as the history walk sees it - dates fill in as the walk moves back in time
+struct TrackedFile {
+    path: PathBuf,
+    modified: Option<DateTime<Utc>>,
+    head_hash: String,
+}
+
+impl GitDates {
+    /// walks the local history once, newest commit first - nothing is fetched
+    pub fn load(
+        obsidian_path: &Path,
+        date_settings: &DateSettings,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let repository = Repository::discover(obsidian_path).map_err(|e| {
+            format!(
+                "{}{}: {}",
+                ERROR_NOT_A_GIT_REPOSITORY,
+                obsidian_path.display(),
+                e
+            )
+        })?;
+        let Some(workdir) = repository.workdir() else {
+            return Err(
+                format!("{}{}", ERROR_NOT_A_GIT_REPOSITORY, obsidian_path.display()).into(),
+            );
+        };
+
+        // the vault can be any folder inside the repository
+        let vault_prefix = fs::canonicalize(obsidian_path)?
+            .strip_prefix(fs::canonicalize(workdir)?)?
+            .to_path_buf();
+
+        // an empty repository has no history yet
+        let Ok(head) = repository.head().and_then(|head| head.peel_to_commit()) else {
+            return Ok(Self::default());
+        };
+
+        let hash_blob = |oid: Oid| -> Result<String, git2::Error> {
+            let blob = repository.find_blob(oid)?;
+            let text = String::from_utf8_lossy(blob.content());
+            Ok(markdown_file::full_content_hash(&text, date_settings))
+        };
+
+        // keyed by the path each note had at the point in history the walk has reached
+        let mut tracked: HashMap<PathBuf, TrackedFile> = HashMap::new();
+        let mut walk_error = None;
+        head.tree()?.walk(TreeWalkMode::PreOrder, |root, entry| {
+            let git_path = Path::new(root).join(entry.name().unwrap_or_default());
+            if let Some(path) = vault_markdown_path(&git_path, &vault_prefix)
+                .map(|relative| obsidian_path.join(relative))
+            {
+                match hash_blob(entry.id()) {
+                    Ok(head_hash) => {
+                        tracked.insert(
+                            git_path,
+                            TrackedFile {
+                                path,
+                                modified: None,
+                                head_hash,
+                            },
+                        );
+                    }
+                    Err(e) => {
+                        walk_error = Some(e);
+                        return TreeWalkResult::Abort;
+                    }
+                }
+            }
+            TreeWalkResult::Ok
+        })?;
+        if let Some(e) = walk_error {
+            return Err(e.into());
+        }
+
+        let mut revwalk = repository.revwalk()?;
+        revwalk.push(head.id())?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+
+        let mut files = HashMap::new();
+
+        for oid in revwalk {
+            if tracked.is_empty() {
+                break;
+            }
+
+            let commit = repository.find_commit(oid?)?;
+            // the commits on the merged branch carry the actual changes
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let time = commit_time(&commit);
+
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let mut diff_options = DiffOptions::new();
+            if !vault_prefix.as_os_str().is_empty() {
+                diff_options.pathspec(&vault_prefix);
+            }
+            let mut diff = repository.diff_tree_to_tree(
+                parent_tree.as_ref(),
+                Some(&commit.tree()?),
+                Some(&mut diff_options),
+            )?;
+            diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+            // applied once the whole commit is seen as a note can move to a path another one left
+            let mut renamed = Vec::new();
+            for delta in diff.deltas() {
+                let Some(new_path) = delta.new_file().path() else {
+                    continue;
+                };
+                let Some(file) = tracked.get_mut(new_path) else {
+                    continue;
+                };
+
+                let content_changed = match delta.status() {
+                    Delta::Modified | Delta::Renamed
+                        if delta.old_file().id() != delta.new_file().id() =>
+                    {
+                        hash_blob(delta.old_file().id())? != hash_blob(delta.new_file().id())?
+                    }
+                    _ => false,
+                };
+                if content_changed && file.modified.is_none() {
+                    file.modified = Some(time);
+                }
+
+                match delta.status() {
+                    // anything further back at this path is a different note
+                    Delta::Added => {
+                        if let Some(file) = tracked.remove(new_path) {
+                            files.insert(
+                                file.path,
+                                GitFileDates {
+                                    created: time,
+                                    modified: file.modified.unwrap_or(time),
+                                    head_hash: file.head_hash,
+                                },
+                            );
+                        }
+                    }
+                    Delta::Renamed => {
+                        if let (Some(file), Some(old_path)) =
+                            (tracked.remove(new_path), delta.old_file().path())
+                        {
+                            renamed.push((old_path.to_path_buf(), file));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            tracked.extend(renamed);
+        }
+
+        Ok(Self { files })
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&GitFileDates> {
+        self.files.get(path)
+    }
+
+    /// a note that isn't committed yet falls back to the file system - as does date_modified
+    /// of a note with uncommitted changes to its content
+    pub fn reference_dates(
+        &self,
+        path: &Path,
+        hash: &str,
+        file_system: ReferenceDates,
+    ) -> ReferenceDates {
+        match self.get(path) {
+            Some(dates) => ReferenceDates {
+                created: dates.created,
+                modified: if dates.head_hash == hash {
+                    dates.modified
+                } else {
+                    file_system.modified
+                },
+            },
+            None => file_system,
+        }
+    }
+}
+
+fn commit_time(commit: &Commit) -> DateTime<Utc> {
+    DateTime::from_timestamp(commit.author().when().seconds(), 0).unwrap_or_else(Utc::now)
+}
+
+fn vault_markdown_path<'a>(git_path: &'a Path, vault_prefix: &Path) -> Option<&'a Path> {
+    let relative = git_path.strip_prefix(vault_prefix).ok()?;
+    (relative.extension().and_then(|ext| ext.to_str()) == Some(MARKDOWN_EXTENSION))
+        .then_some(relative)
+}
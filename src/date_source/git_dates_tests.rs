@@ -0,0 +1,191 @@
+use super::git_dates::GitFileDates;
+use super::*;
+use crate::date_settings::DateSettings;
+use crate::markdown_file::{self, PersistReason};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use chrono::Duration;
+use git2::{Commit, IndexAddOption, Repository, Signature, Time};
+use tempfile::TempDir;
+
+fn noon(year: i32, month: u32, day: u32) -> DateTime<Utc> {
+    test_utils::eastern_midnight(year, month, day) + Duration::hours(12)
+}
+
+fn commit_all(repository: &Repository, message: &str, time: DateTime<Utc>) {
+    let mut index = repository.index().unwrap();
+    index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+    index.update_all(["*"], None).unwrap();
+    index.write().unwrap();
+
+    let tree = repository.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature =
+        Signature::new("ok", "ok@example.com", &Time::new(time.timestamp(), 0)).unwrap();
+    let parent = repository
+        .head()
+        .ok()
+        .and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<&Commit> = parent.iter().collect();
+
+    repository
+        .commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+}
+
+fn git_config(temp_dir: &TempDir) -> crate::validated_config::ValidatedConfig {
+    validated_config_tests::get_test_validated_config_result(temp_dir, |builder| {
+        builder.date_settings(DateSettings {
+            source: DateSource::Git,
+            ..DateSettings::default()
+        });
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_parse_git_date_source() {
+    assert_eq!(DATE_SOURCE_GIT.parse::<DateSource>(), Ok(DateSource::Git));
+}
+
+#[test]
+fn test_git_dates_follow_renames() {
+    let temp_dir = TempDir::new().unwrap();
+    let repository = Repository::init(temp_dir.path()).unwrap();
+    let vault = temp_dir.path().join("vault");
+    fs::create_dir(&vault).unwrap();
+
+    fs::write(vault.join("a.md"), "---\ntitle: a\n---\nfirst").unwrap();
+    fs::write(temp_dir.path().join("outside.md"), "not in the vault").unwrap();
+    commit_all(&repository, "add", noon(2024, 1, 1));
+
+    fs::write(vault.join("a.md"), "---\ntitle: a\n---\nsecond").unwrap();
+    commit_all(&repository, "edit", noon(2024, 1, 2));
+
+    fs::rename(vault.join("a.md"), vault.join("b.md")).unwrap();
+    commit_all(&repository, "rename", noon(2024, 1, 3));
+
+    // committing the dates ok wrote doesn't count as a change
+    let text = "---\ntitle: a\ndate_modified: \"[[2024-01-02]]\"\n---\nsecond";
+    fs::write(vault.join("b.md"), text).unwrap();
+    commit_all(&repository, "dates", noon(2024, 1, 4));
+
+    let git_dates = GitDates::load(&vault, &DateSettings::default()).unwrap();
+
+    assert_eq!(
+        git_dates.get(&vault.join("b.md")),
+        Some(&GitFileDates {
+            created: noon(2024, 1, 1),
+            modified: noon(2024, 1, 2),
+            head_hash: markdown_file::full_content_hash(text, &DateSettings::default()),
+        })
+    );
+    assert_eq!(git_dates.get(&vault.join("a.md")), None);
+    assert_eq!(git_dates.get(&vault.join("outside.md")), None);
+}
+
+#[test]
+fn test_git_reference_dates() {
+    let temp_dir = TempDir::new().unwrap();
+    let repository = Repository::init(temp_dir.path()).unwrap();
+    fs::write(temp_dir.path().join("note.md"), "committed").unwrap();
+    commit_all(&repository, "add", noon(2024, 1, 1));
+
+    let git_dates = GitDates::load(temp_dir.path(), &DateSettings::default()).unwrap();
+    let head_hash = git_dates
+        .get(&temp_dir.path().join("note.md"))
+        .unwrap()
+        .head_hash
+        .clone();
+    let file_system = ReferenceDates {
+        created: noon(2024, 3, 1),
+        modified: noon(2024, 3, 2),
+    };
+    let committed = ReferenceDates {
+        created: noon(2024, 1, 1),
+        modified: noon(2024, 1, 1),
+    };
+
+    let test_cases = [
+        ("note.md", head_hash.as_str(), committed),
+        // uncommitted changes take the file's modified date
+        (
+            "note.md",
+            "changed",
+            ReferenceDates {
+                modified: file_system.modified,
+                ..committed
+            },
+        ),
+        // a note that isn't committed yet uses the file system
+        ("new.md", "anything", file_system),
+    ];
+
+    for (file, hash, expected) in test_cases {
+        assert_eq!(
+            git_dates.reference_dates(&temp_dir.path().join(file), hash, file_system),
+            expected,
+            "Failed for: {} {}",
+            file,
+            hash
+        );
+    }
+}
+
+#[test]
+fn test_git_dates_drive_persist_reasons() {
+    let temp_dir = TempDir::new().unwrap();
+    let repository = Repository::init(temp_dir.path()).unwrap();
+    let dates = |created: &str, modified: &str| {
+        (
+            Some(format!("[[{}]]", created)),
+            Some(format!("[[{}]]", modified)),
+        )
+    };
+
+    let (created, modified) = dates("2024-01-01", "2024-01-01");
+    TestFileBuilder::new()
+        .with_frontmatter_dates(created, modified)
+        .with_content("in sync".to_string())
+        .create(&temp_dir, "in_sync.md");
+    let (created, modified) = dates("2024-01-01", "2023-06-01");
+    TestFileBuilder::new()
+        .with_frontmatter_dates(created, modified)
+        .with_content("out of sync".to_string())
+        .create(&temp_dir, "out_of_sync.md");
+    commit_all(&repository, "add", noon(2024, 1, 1));
+
+    let repository = ObsidianRepository::new(&git_config(&temp_dir)).unwrap();
+    assert_eq!(repository.markdown_files.len(), 2);
+
+    for file in repository.markdown_files.iter() {
+        let in_sync = file.path.ends_with("in_sync.md");
+        assert_eq!(
+            file.persist_reasons.is_empty(),
+            in_sync,
+            "Failed for: {:?} {:?}",
+            file.path,
+            file.persist_reasons
+        );
+        if !in_sync {
+            assert!(matches!(
+                file.persist_reasons.as_slice(),
+                [PersistReason::DateModifiedUpdated { .. }]
+            ));
+        }
+    }
+}
+
+#[test]
+fn test_git_date_source_needs_a_repository() {
+    let temp_dir = TempDir::new().unwrap();
+    assert!(ObsidianRepository::new(&git_config(&temp_dir)).is_err());
+}
@@ -1,8 +1,13 @@
+use crate::date_settings::DateSettings;
 use crate::utils;
+use crate::yaml_editor;
+use crate::yaml_frontmatter::{self, YamlFrontMatterError};
 use crate::yaml_frontmatter_struct;
 use chrono::{DateTime, Utc};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::collections::HashMap;
 
 // when we set date_created_fix to None it won't serialize - cool
 // the macro adds support for serializing any fields not explicitly named
@@ -20,8 +25,12 @@ yaml_frontmatter_struct! {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub do_not_back_populate: Option<Vec<String>>,
         #[serde(skip)]
+        pub date_settings: DateSettings,
+        #[serde(skip)]
         pub needs_persist: bool,
         #[serde(skip)]
+        pub original_yaml: Option<String>,
+        #[serde(skip)]
         pub raw_date_created: Option<DateTime<Utc>>,
         #[serde(skip)]
         pub raw_date_modified: Option<DateTime<Utc>>,
@@ -55,10 +64,8 @@ impl FrontMatter {
     // in this case we still need to update the modify date so make sure we set it if it's
     // not already set
     pub fn set_date_created(&mut self, date: DateTime<Utc>, operational_timezone: &str) {
-        let tz: chrono_tz::Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
-        let local_date = date.with_timezone(&tz);
         self.raw_date_created = Some(date);
-        self.date_created = Some(format!("[[{}]]", local_date.format("%Y-%m-%d")));
+        self.date_created = Some(self.date_settings.format_date(date, operational_timezone));
 
         if self.raw_date_modified.is_none() {
             self.set_date_modified_now(operational_timezone);
@@ -75,12 +82,21 @@ impl FrontMatter {
         self.set_date_modified(Utc::now(), operational_timezone);
     }
 
+    /// ok changed the note - date_modified moves to now unless dates are disabled, in which
+    /// case only the file's modified time does
+    pub fn mark_edited(&mut self, update_date_modified: bool, operational_timezone: &str) {
+        if update_date_modified {
+            self.set_date_modified_now(operational_timezone);
+        } else {
+            self.raw_date_modified = Some(Utc::now());
+            self.needs_persist = true;
+        }
+    }
+
     // we use this when set_date_modified is missing
     pub fn set_date_modified(&mut self, date: DateTime<Utc>, operational_timezone: &str) {
-        let tz: chrono_tz::Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
-        let local_date = date.with_timezone(&tz);
         self.raw_date_modified = Some(date);
-        self.date_modified = Some(format!("[[{}]]", local_date.format("%Y-%m-%d")));
+        self.date_modified = Some(self.date_settings.format_date(date, operational_timezone));
         self.needs_persist = true;
     }
 
@@ -88,6 +104,118 @@ impl FrontMatter {
         self.needs_persist
     }
 
+    /// reads the frontmatter of a markdown file - the configured date properties are read into
+    /// the date fields and the yaml is remembered exactly as it was so persisting only
+    /// rewrites what changed
+    pub fn from_markdown_yaml(
+        yaml: &str,
+        date_settings: &DateSettings,
+    ) -> Result<Self, YamlFrontMatterError> {
+        let mut value: serde_yaml::Value =
+            serde_yaml::from_str(yaml).map_err(|e| YamlFrontMatterError::Parse(e.to_string()))?;
+        if let Some(mapping) = value.as_mapping_mut() {
+            date_settings.properties.swap(mapping);
+        }
+
+        let mut frontmatter: FrontMatter = serde_yaml::from_value(value)
+            .map_err(|e| YamlFrontMatterError::Parse(e.to_string()))?;
+        frontmatter.date_settings = date_settings.clone();
+        frontmatter.original_yaml = Some(yaml.to_string());
+        Ok(frontmatter)
+    }
+
+    /// a new frontmatter for a note that has none - there's no original yaml so it's written
+    /// sorted
+    pub fn create(
+        properties: &Mapping,
+        date_settings: &DateSettings,
+    ) -> Result<Self, YamlFrontMatterError> {
+        let mut mapping = properties.clone();
+        date_settings.properties.swap(&mut mapping);
+
+        let mut frontmatter: FrontMatter = serde_yaml::from_value(Value::Mapping(mapping))
+            .map_err(|e| YamlFrontMatterError::Parse(e.to_string()))?;
+        frontmatter.date_settings = date_settings.clone();
+        frontmatter.needs_persist = true;
+        Ok(frontmatter)
+    }
+
+    /// the properties ok doesn't read itself
+    pub fn other_fields(&self) -> &HashMap<String, Value> {
+        &self.other_fields
+    }
+
+    /// every property by the name it has in the note
+    pub fn properties(&self) -> Mapping {
+        let mut mapping = serde_yaml::to_value(self)
+            .ok()
+            .and_then(|value| value.as_mapping().cloned())
+            .unwrap_or_default();
+        self.date_settings.properties.swap(&mut mapping);
+        mapping
+    }
+
+    /// sets a property by the name it has in the note - fails when the value doesn't fit a
+    /// property ok reads, e.g. aliases that aren't a list
+    pub fn set_property(&mut self, name: &str, value: Value) -> Result<(), YamlFrontMatterError> {
+        let mut mapping = self.properties();
+        mapping.insert(Value::String(name.to_string()), value);
+        self.set_properties(mapping)
+    }
+
+    /// replaces every property with `properties`, by the names they have in the note - a
+    /// property that isn't in it is removed
+    pub fn set_properties(&mut self, mut properties: Mapping) -> Result<(), YamlFrontMatterError> {
+        self.date_settings.properties.swap(&mut properties);
+
+        let updated: FrontMatter = serde_yaml::from_value(Value::Mapping(properties))
+            .map_err(|e| YamlFrontMatterError::Parse(e.to_string()))?;
+        *self = FrontMatter {
+            date_settings: self.date_settings.clone(),
+            needs_persist: true,
+            original_yaml: self.original_yaml.take(),
+            raw_date_created: self.raw_date_created,
+            raw_date_modified: self.raw_date_modified,
+            ..updated
+        };
+        Ok(())
+    }
+
+    /// the frontmatter exactly as it was read minus the date properties ok manages - so it
+    /// only changes when the user changes it
+    pub fn user_yaml(&self) -> Option<String> {
+        let original = self.original_yaml.as_ref()?;
+        let mut value: serde_yaml::Value = serde_yaml::from_str(original).ok()?;
+        let mapping = value.as_mapping_mut()?;
+        let properties = &self.date_settings.properties;
+        for property in [
+            &properties.created,
+            &properties.created_fix,
+            &properties.modified,
+        ] {
+            mapping.remove(property.as_str());
+        }
+        yaml_editor::update_yaml(original, &value).ok()
+    }
+
+    /// keeps key order, comments and quoting of the original yaml and only rewrites the keys
+    /// whose values changed - without an original (or one we can't edit line by line)
+    /// this falls back to the sorted serialization
+    pub fn to_persisted_yaml_str(&self) -> Result<String, YamlFrontMatterError> {
+        let mut updated = serde_yaml::to_value(self)
+            .map_err(|e| YamlFrontMatterError::Serialize(e.to_string()))?;
+        if let Some(mapping) = updated.as_mapping_mut() {
+            self.date_settings.properties.swap(mapping);
+        }
+
+        if let Some(original) = &self.original_yaml {
+            if let Ok(yaml) = yaml_editor::update_yaml(original, &updated) {
+                return Ok(yaml);
+            }
+        }
+        yaml_frontmatter::to_sorted_yaml_str(updated)
+    }
+
     pub fn get_do_not_back_populate_regexes(&self) -> Option<Vec<Regex>> {
         // first get do_not_back_populate explicit value
         let mut do_not_populate = self.do_not_back_populate.clone().unwrap_or_default();
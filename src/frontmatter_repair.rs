@@ -0,0 +1,234 @@
+#[cfg(test)]
+mod frontmatter_repair_tests;
+
+use crate::date_settings::DateSettings;
+use crate::frontmatter::FrontMatter;
+use crate::yaml_editor;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use std::fmt;
+
+const DELIMITER: &str = "---";
+
+lazy_static! {
+    // a plain `key: value` line - list items, comments and quoted keys are left alone
+    static ref PLAIN_VALUE: Regex = Regex::new(r"^(\s*[^\s#'\x22\-\[{][^:]*?):[ \t]+(\S.*?)\s*$").unwrap();
+    static ref BLOCK_SCALAR: Regex = Regex::new(r":[ \t]+[|>][-+0-9]*\s*$").unwrap();
+    static ref HEADING: Regex = Regex::new(r"^#{1,6}\s+\S").unwrap();
+}
+
+/// the mistakes ok knows how to repair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RepairKind {
+    DuplicateDelimiter,
+    DuplicateKey,
+    MissingClosingDelimiter,
+    TabIndentation,
+    UnquotedValue,
+}
+
+impl fmt::Display for RepairKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            RepairKind::DuplicateDelimiter => "duplicated ---",
+            RepairKind::DuplicateKey => "duplicate key",
+            RepairKind::MissingClosingDelimiter => "missing closing ---",
+            RepairKind::TabIndentation => "tabs used for indentation",
+            RepairKind::UnquotedValue => "unquoted value with ': ' or '#'",
+        };
+        write!(f, "{}", description)
+    }
+}
+
+/// a frontmatter that couldn't be read and what it's repaired to - both include the ---
+/// delimiters so a moved or removed delimiter shows up
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontmatterRepair {
+    /// why the frontmatter couldn't be read
+    pub error: String,
+    pub before: String,
+    pub after: String,
+    pub repairs: Vec<RepairKind>,
+    pub applied: bool,
+}
+
+/// a repair along with the frontmatter it produces and where the note's content starts
+#[derive(Debug)]
+pub struct RepairedFrontmatter {
+    pub repair: FrontmatterRepair,
+    pub frontmatter: FrontMatter,
+    pub content_start: usize,
+}
+
+impl FrontmatterRepair {
+    /// None unless the repaired yaml reads as frontmatter - anything else has to be fixed by
+    /// hand. a note with \r\n line endings isn't read as having frontmatter so it never gets here
+    pub fn find(
+        full_content: &str,
+        error: &str,
+        date_settings: &DateSettings,
+    ) -> Option<RepairedFrontmatter> {
+        let mut repairs = Vec::new();
+        let mut rest = full_content.strip_prefix("---\n")?;
+        while let Some(after_delimiter) = rest.strip_prefix("---\n") {
+            rest = after_delimiter;
+            repairs.push(RepairKind::DuplicateDelimiter);
+        }
+        let yaml_start = full_content.len() - rest.len();
+
+        let lines: Vec<&str> = rest.split_inclusive('\n').collect();
+        let (yaml_lines, content_start) =
+            match lines.iter().position(|line| line.trim_end() == DELIMITER) {
+                Some(closing) => {
+                    let closing_end: usize = lines[..=closing].iter().map(|line| line.len()).sum();
+                    (&lines[..closing], yaml_start + closing_end)
+                }
+                None => {
+                    let heading = closing_heading(&lines)?;
+                    repairs.push(RepairKind::MissingClosingDelimiter);
+                    let heading_start: usize = lines[..heading].iter().map(|line| line.len()).sum();
+                    (&lines[..heading], yaml_start + heading_start)
+                }
+            };
+
+        let (yaml_lines, line_repairs) = repair_lines(yaml_lines);
+        repairs.extend(line_repairs);
+
+        let mut seen = HashSet::new();
+        repairs.retain(|repair| seen.insert(*repair));
+        if repairs.is_empty() {
+            return None;
+        }
+
+        let yaml = yaml_lines.join("\n");
+        let mut frontmatter = FrontMatter::from_markdown_yaml(&yaml, date_settings).ok()?;
+        frontmatter.needs_persist = true;
+
+        Some(RepairedFrontmatter {
+            repair: FrontmatterRepair {
+                error: error.to_string(),
+                before: full_content[..content_start].trim_end().to_string(),
+                after: format!("{}\n{}\n{}", DELIMITER, yaml, DELIMITER),
+                repairs,
+                applied: false,
+            },
+            frontmatter,
+            content_start,
+        })
+    }
+}
+
+/// yaml doesn't have headings but a `# comment` looks like one - the frontmatter ends at the
+/// last heading with a mapping above it, less any comments right before that heading. a
+/// heading on the first line is always a comment
+fn closing_heading(lines: &[&str]) -> Option<usize> {
+    let is_heading = |idx: usize| idx > 0 && HEADING.is_match(lines[idx]);
+    let mut heading = (1..lines.len())
+        .rev()
+        .filter(|&idx| is_heading(idx))
+        .find(|&idx| reads_as_mapping(&lines[..idx]))?;
+
+    while let Some(previous) = lines[..heading]
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .filter(|&previous| is_heading(previous) && reads_as_mapping(&lines[..previous]))
+    {
+        heading = previous;
+    }
+    Some(heading)
+}
+
+fn reads_as_mapping(lines: &[&str]) -> bool {
+    let (yaml_lines, _) = repair_lines(lines);
+    serde_yaml::from_str::<serde_yaml::Value>(&yaml_lines.join("\n"))
+        .is_ok_and(|value| value.is_mapping())
+}
+
+/// the repairs made line by line - the text of a | or > block is left as it is
+fn repair_lines(lines: &[&str]) -> (Vec<String>, Vec<RepairKind>) {
+    let mut repairs = Vec::new();
+    let mut yaml_lines: Vec<String> = lines
+        .iter()
+        .map(|line| line.trim_end_matches(['\n', '\r']).to_string())
+        .collect();
+    while yaml_lines.last().is_some_and(|line| line.trim().is_empty()) {
+        yaml_lines.pop();
+    }
+
+    let mut block_indentation: Option<usize> = None;
+    for line in yaml_lines.iter_mut() {
+        if let Some(repaired) = replace_tab_indentation(line) {
+            *line = repaired;
+            repairs.push(RepairKind::TabIndentation);
+        }
+
+        let indentation = line.len() - line.trim_start().len();
+        if block_indentation.is_some_and(|block| indentation > block || line.trim().is_empty()) {
+            continue;
+        }
+        block_indentation = BLOCK_SCALAR.is_match(line).then_some(indentation);
+
+        if let Some(repaired) = quote_value(line) {
+            *line = repaired;
+            repairs.push(RepairKind::UnquotedValue);
+        }
+    }
+
+    if remove_duplicate_keys(&mut yaml_lines) {
+        repairs.push(RepairKind::DuplicateKey);
+    }
+
+    (yaml_lines, repairs)
+}
+
+/// every tab in a line's indentation becomes two spaces
+fn replace_tab_indentation(line: &str) -> Option<String> {
+    let content = line.trim_start_matches([' ', '\t']);
+    let indentation = &line[..line.len() - content.len()];
+    indentation
+        .contains('\t')
+        .then(|| format!("{}{}", indentation.replace('\t', "  "), content))
+}
+
+/// a value with `: ` in it reads as a nested mapping and one starting with `#` as a comment -
+/// both are double quoted
+fn quote_value(line: &str) -> Option<String> {
+    let captures = PLAIN_VALUE.captures(line)?;
+    let value = captures.get(2)?.as_str();
+    if value.starts_with(['"', '\'', '[', '{', '|', '>', '&', '*', '!']) {
+        return None;
+    }
+    if !value.contains(": ") && !value.starts_with('#') {
+        return None;
+    }
+
+    let quoted = serde_json::to_string(value).ok()?;
+    Some(format!("{}: {}", &captures[1], quoted))
+}
+
+/// the last value of a key wins like in most yaml readers - returns whether anything was removed
+fn remove_duplicate_keys(lines: &mut Vec<String>) -> bool {
+    let line_refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let keys = yaml_editor::top_level_keys(&line_refs);
+
+    let mut seen = HashSet::new();
+    let mut removed: Vec<usize> = keys
+        .iter()
+        .rev()
+        .filter(|(key, _)| !seen.insert(key.clone()))
+        .flat_map(|(_, range)| range.clone())
+        .collect();
+    if removed.is_empty() {
+        return false;
+    }
+
+    removed.sort_unstable();
+    let mut idx = 0;
+    lines.retain(|_| {
+        let keep = removed.binary_search(&idx).is_err();
+        idx += 1;
+        keep
+    });
+    true
+}
@@ -0,0 +1,197 @@
+use super::*;
+use crate::approval::ApprovedChanges;
+use crate::markdown_file::PersistReason;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::validated_config::validated_config_tests;
+use std::fs;
+use tempfile::TempDir;
+
+fn repair(content: &str) -> Option<RepairedFrontmatter> {
+    FrontmatterRepair::find(content, "error", &DateSettings::default())
+}
+
+#[test]
+fn test_repairs() {
+    let test_cases = [
+        (
+            "---\ntags:\n\t- book\n---\n# Note",
+            vec![RepairKind::TabIndentation],
+            "---\ntags:\n  - book\n---",
+        ),
+        (
+            "---\ntitle: Chapter 1: The Start\n---\nbody",
+            vec![RepairKind::UnquotedValue],
+            "---\ntitle: \"Chapter 1: The Start\"\n---",
+        ),
+        (
+            "---\ncolor: #ff0000\n---\nbody",
+            vec![RepairKind::UnquotedValue],
+            "---\ncolor: \"#ff0000\"\n---",
+        ),
+        (
+            "---\n---\nstatus: open\n---\nbody",
+            vec![RepairKind::DuplicateDelimiter],
+            "---\nstatus: open\n---",
+        ),
+        (
+            "---\nstatus: open\n\n# Heading\nbody",
+            vec![RepairKind::MissingClosingDelimiter],
+            "---\nstatus: open\n---",
+        ),
+        (
+            "---\nstatus: open\ntags:\n  - book\nstatus: done\n---\nbody",
+            vec![RepairKind::DuplicateKey],
+            "---\ntags:\n  - book\nstatus: done\n---",
+        ),
+        (
+            "---\n---\ntitle: a: b\ntags:\n\t- x\n# Heading",
+            vec![
+                RepairKind::DuplicateDelimiter,
+                RepairKind::MissingClosingDelimiter,
+                RepairKind::UnquotedValue,
+                RepairKind::TabIndentation,
+            ],
+            "---\ntitle: \"a: b\"\ntags:\n  - x\n---",
+        ),
+        // a yaml comment isn't where the frontmatter ends
+        (
+            "---\n# reading notes\ntitle: Book\ntags:\n  - a\n\n# Chapter One\nbody text",
+            vec![RepairKind::MissingClosingDelimiter],
+            "---\n# reading notes\ntitle: Book\ntags:\n  - a\n---",
+        ),
+        (
+            "---\ntitle: Book\n# reading notes\ntags:\n  - a\n# Chapter One\n# Part One\nbody",
+            vec![RepairKind::MissingClosingDelimiter],
+            "---\ntitle: Book\n# reading notes\ntags:\n  - a\n---",
+        ),
+        // the text of a block scalar is left alone
+        (
+            "---\nnote: |\n\ta: b\n---\nbody",
+            vec![RepairKind::TabIndentation],
+            "---\nnote: |\n  a: b\n---",
+        ),
+    ];
+
+    for (content, expected_repairs, expected_after) in test_cases {
+        let repaired = repair(content).expect(content);
+        assert_eq!(
+            repaired.repair.repairs, expected_repairs,
+            "Failed for: {}",
+            content
+        );
+        assert_eq!(
+            repaired.repair.after, expected_after,
+            "Failed for: {}",
+            content
+        );
+    }
+}
+
+#[test]
+fn test_content_start() {
+    let content = "---\nstatus: open\n\n# Heading\nbody";
+    let repaired = repair(content).unwrap();
+    assert_eq!(&content[repaired.content_start..], "# Heading\nbody");
+    assert_eq!(repaired.repair.before, "---\nstatus: open");
+
+    let content = "---\n# reading notes\ntitle: Book\n\n# Chapter One\nbody text";
+    let repaired = repair(content).unwrap();
+    assert_eq!(
+        &content[repaired.content_start..],
+        "# Chapter One\nbody text"
+    );
+
+    let content = "---\n---\nstatus: open\n---\nbody";
+    let repaired = repair(content).unwrap();
+    assert_eq!(&content[repaired.content_start..], "body");
+}
+
+#[test]
+fn test_no_repair() {
+    let test_cases = [
+        // nothing ok knows how to repair
+        "---\nstatus: [open\n---\nbody",
+        // no heading to end the frontmatter at
+        "---\nstatus: open\nbody",
+        // a heading on the first line is a comment
+        "---\n# reading notes\ntitle: Book",
+        // no frontmatter
+        "# Heading\nbody",
+    ];
+
+    for content in test_cases {
+        assert!(repair(content).is_none(), "Failed for: {}", content);
+    }
+}
+
+#[test]
+fn test_frontmatter_repair_applied() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("note.md");
+    fs::write(
+        &file_path,
+        "---\ntitle: Chapter 1: The Start\ntags:\n\t- book\n---\n# Note\nbody",
+    )
+    .unwrap();
+    let config = |approved_changes: Option<ApprovedChanges>| {
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+            builder.approved_changes(approved_changes);
+        })
+        .unwrap()
+    };
+
+    // not approved is reported but the frontmatter stays unreadable
+    let repository =
+        ObsidianRepository::new(&config(ApprovedChanges::parse("[ ] ok-0123456789"))).unwrap();
+    let file = &repository.markdown_files[0];
+    let repair = file.frontmatter_repair.as_ref().unwrap();
+    assert!(!repair.applied);
+    assert!(file.frontmatter.is_none());
+    assert!(file.frontmatter_error.is_some());
+    assert!(file.persist_reasons.is_empty());
+
+    let mut repository = ObsidianRepository::new(&config(None)).unwrap();
+    let file = &repository.markdown_files[0];
+    assert!(file.frontmatter_repair.as_ref().unwrap().applied);
+    assert!(file.frontmatter_error.is_none());
+    assert_eq!(file.content, "# Note\nbody");
+    assert!(file
+        .persist_reasons
+        .contains(&PersistReason::FrontmatterRepaired));
+
+    repository.persist().unwrap();
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(
+        content.contains("title: \"Chapter 1: The Start\""),
+        "{}",
+        content
+    );
+    assert!(content.contains("  - book"), "{}", content);
+    assert!(content.ends_with("---\n# Note\nbody"), "{}", content);
+}
+
+#[test]
+fn test_yaml_comment_repair_keeps_properties() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("note.md");
+    fs::write(
+        &file_path,
+        "---\n# reading notes\ntitle: Book\ntags:\n  - a\n\n# Chapter One\nbody text",
+    )
+    .unwrap();
+    let config =
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+    assert_eq!(
+        repository.markdown_files[0].content,
+        "# Chapter One\nbody text"
+    );
+    repository.persist().unwrap();
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    let (yaml, body) = content.rsplit_once("---\n").unwrap();
+    assert!(yaml.contains("title: Book"), "{}", content);
+    assert!(yaml.contains("  - a"), "{}", content);
+    assert_eq!(body, "# Chapter One\nbody text");
+}
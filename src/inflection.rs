@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod inflection_tests;
+
+use crate::utils;
+use crate::wikilink::Wikilink;
+use std::collections::HashSet;
+
+const APOSTROPHES: [char; 2] = ['\'', '’'];
+
+/// replaces `strip` at the end of the last word with `add` - an empty strip just appends
+#[derive(Debug, Clone, PartialEq)]
+pub struct SuffixRule {
+    pub strip: String,
+    pub add: String,
+}
+
+/// the forms of a page name back population matches besides the name itself
+#[derive(Debug, Clone, Default)]
+pub struct Inflections {
+    suffix_rules: Vec<SuffixRule>,
+}
+
+impl Inflections {
+    pub fn new(suffix_rules: Vec<SuffixRule>) -> Self {
+        Self { suffix_rules }
+    }
+
+    /// the english plural and the possessives of the last word plus whatever the suffix rules
+    /// make of it - text that doesn't end in a letter, like "C++" or "2024", has none
+    pub fn variants(&self, display_text: &str) -> Vec<String> {
+        if !display_text.chars().last().is_some_and(char::is_alphabetic) {
+            return Vec::new();
+        }
+
+        let mut variants = Vec::new();
+        let plural = plural(display_text);
+        for apostrophe in APOSTROPHES {
+            variants.push(format!("{}{}s", display_text, apostrophe));
+            if plural.ends_with('s') {
+                variants.push(format!("{}{}", plural, apostrophe));
+            }
+        }
+        variants.push(plural);
+
+        let folded = utils::fold_case(display_text);
+        for rule in &self.suffix_rules {
+            if folded.ends_with(&utils::fold_case(&rule.strip)) {
+                let stem = strip_suffix_chars(display_text, rule.strip.chars().count());
+                variants.push(format!("{}{}", stem, rule.add));
+            }
+        }
+
+        variants
+    }
+
+    /// a variant is left out when it's already the name or alias of a page - it links there
+    pub fn add_variants(&self, wikilinks: &mut HashSet<Wikilink>) {
+        let display_texts: HashSet<String> = wikilinks
+            .iter()
+            .map(|wikilink| utils::fold_case(&wikilink.display_text))
+            .collect();
+
+        let variants: Vec<Wikilink> = wikilinks
+            .iter()
+            .flat_map(|wikilink| {
+                self.variants(&wikilink.display_text)
+                    .into_iter()
+                    .filter(|variant| !display_texts.contains(&utils::fold_case(variant)))
+                    .map(|variant| Wikilink {
+                        display_text: variant,
+                        target: wikilink.target.clone(),
+                    })
+            })
+            .collect();
+
+        wikilinks.extend(variants);
+    }
+}
+
+/// the regular english plural - irregular ones like mice are for the suffix rules
+pub fn plural(text: &str) -> String {
+    let lower = text.to_lowercase();
+    let mut chars = lower.chars().rev();
+    let last = chars.next();
+    let before_last = chars.next();
+
+    let is_vowel = |ch: char| "aeiou".contains(ch);
+    match (before_last, last) {
+        (_, Some('s' | 'x' | 'z')) | (Some('c' | 's'), Some('h')) => format!("{}es", text),
+        (Some(ch), Some('y')) if !is_vowel(ch) => {
+            format!("{}ies", strip_suffix_chars(text, 1))
+        }
+        _ => format!("{}s", text),
+    }
+}
+
+fn strip_suffix_chars(text: &str, count: usize) -> &str {
+    if count == 0 {
+        return text;
+    }
+    let end = text
+        .char_indices()
+        .rev()
+        .nth(count - 1)
+        .map_or(0, |(idx, _)| idx);
+    &text[..end]
+}
@@ -0,0 +1,158 @@
+use super::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+#[test]
+fn test_plural() {
+    let test_cases = [
+        ("OLED Display", "OLED Displays"),
+        ("Bus", "Buses"),
+        ("Box", "Boxes"),
+        ("Church", "Churches"),
+        ("Wish", "Wishes"),
+        ("Company", "Companies"),
+        ("Day", "Days"),
+        ("API", "APIs"),
+        ("Café", "Cafés"),
+    ];
+
+    for (text, expected) in test_cases {
+        assert_eq!(plural(text), expected, "Failed for: {}", text);
+    }
+}
+
+#[test]
+fn test_variants() {
+    let inflections = Inflections::new(vec![
+        SuffixRule {
+            strip: "um".to_string(),
+            add: "a".to_string(),
+        },
+        SuffixRule {
+            strip: String::new(),
+            add: "ing".to_string(),
+        },
+    ]);
+
+    let variants = inflections.variants("Datum");
+    for expected in [
+        "Datums",
+        "Datum's",
+        "Datum’s",
+        "Datums'",
+        "Datums’",
+        "Data",
+        "Datuming",
+    ] {
+        assert!(
+            variants.contains(&expected.to_string()),
+            "{} not in {:?}",
+            expected,
+            variants
+        );
+    }
+
+    // the rule only applies to a name ending in what it strips
+    assert!(!inflections
+        .variants("Display")
+        .contains(&"Displaa".to_string()));
+    assert!(inflections.variants("C++").is_empty());
+    assert!(inflections.variants("2024").is_empty());
+}
+
+#[test]
+fn test_add_variants() {
+    let wikilink = |display_text: &str, target: &str| Wikilink {
+        display_text: display_text.to_string(),
+        target: target.to_string(),
+    };
+    let mut wikilinks: HashSet<Wikilink> =
+        [wikilink("Bill", "Bill"), wikilink("bills", "Invoices")]
+            .into_iter()
+            .collect();
+
+    Inflections::default().add_variants(&mut wikilinks);
+
+    assert!(wikilinks.contains(&wikilink("Bill's", "Bill")));
+    // bills already links to another page
+    assert!(!wikilinks.contains(&wikilink("Bills", "Bill")));
+}
+
+#[test]
+fn test_inflected_back_populate() {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_content("OLED displays are bright".to_string())
+        .create(&temp_dir, "OLED Display.md");
+    TestFileBuilder::new()
+        .with_content("two OLED displays and the OLED display's panel".to_string())
+        .create(&temp_dir, "note.md");
+
+    let replacements = |inflections: Option<Inflections>, name: &str| {
+        let config =
+            validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+                builder.inflections(inflections);
+            })
+            .unwrap();
+        let repository = ObsidianRepository::new(&config).unwrap();
+        let file = repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path.ends_with(name))
+            .unwrap();
+        let mut replacements: Vec<String> = file
+            .matches
+            .unambiguous
+            .iter()
+            .map(|m| m.replacement.clone())
+            .collect();
+        replacements.sort();
+        replacements
+    };
+
+    // only the possessive's name matches
+    assert_eq!(
+        replacements(None, "note.md"),
+        vec!["[[OLED Display|OLED display]]"]
+    );
+    assert_eq!(
+        replacements(Some(Inflections::default()), "note.md"),
+        vec![
+            "[[OLED Display|OLED display's]]",
+            "[[OLED Display|OLED displays]]"
+        ]
+    );
+    // a note doesn't link its own name
+    assert!(replacements(Some(Inflections::default()), "OLED Display.md").is_empty());
+}
+
+#[test]
+fn test_own_target_still_matched_without_inflections() {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_content("the screen is bright".to_string())
+        .create(&temp_dir, "OLED Display.md");
+    TestFileBuilder::new()
+        .with_content("a new [[OLED Display|screen]]".to_string())
+        .create(&temp_dir, "note.md");
+
+    let config =
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |_| {}).unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let file = repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with("OLED Display.md"))
+        .unwrap();
+
+    // only inflected forms of a note's own name are left alone - other text linking to it is matched as before
+    let replacements: Vec<&str> = file
+        .matches
+        .unambiguous
+        .iter()
+        .map(|m| m.replacement.as_str())
+        .collect();
+    assert_eq!(replacements, vec!["[[OLED Display|screen]]"]);
+}
@@ -0,0 +1,313 @@
+#[cfg(test)]
+mod journal_tests;
+
+use crate::constants::*;
+use crate::date_source::{CreatedDateStore, DateStore};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::utils;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// everything needed to put the files touched by one apply run back the way they were
+/// lives in .ok/journal/<run_id>/ next to a copy of each file's original bytes
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalManifest {
+    pub run_id: String,
+    pub timestamp_utc: DateTime<Utc>,
+    pub entries: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct JournalEntry {
+    /// relative to obsidian_path
+    pub path: PathBuf,
+    /// file name of the saved copy within the run's journal folder - None if the run created it
+    pub blob: Option<String>,
+    pub action: JournalAction,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    /// sha256 of the file as ok wrote it - undo won't put back a file that changed since
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalAction {
+    /// didn't exist before the run - undo deletes it
+    Created,
+    Modified,
+    Deleted,
+}
+
+pub struct Journal {
+    obsidian_path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(obsidian_path: &Path) -> Self {
+        Self {
+            obsidian_path: obsidian_path.to_path_buf(),
+        }
+    }
+
+    fn journal_folder(&self) -> PathBuf {
+        self.obsidian_path.join(CACHE_FOLDER).join(JOURNAL_FOLDER)
+    }
+
+    fn run_folder(&self, run_id: &str) -> PathBuf {
+        self.journal_folder().join(run_id)
+    }
+
+    /// saves the original bytes and dates of every file before anything is changed
+    /// the manifest is written last so a journal without one was never completed
+    pub fn record(
+        &self,
+        files: &[(PathBuf, JournalAction)],
+    ) -> Result<JournalManifest, Box<dyn Error + Send + Sync>> {
+        let timestamp_utc = Utc::now();
+        let run_id = timestamp_utc.format(FORMAT_JOURNAL_RUN_ID).to_string();
+        let run_folder = self.run_folder(&run_id);
+        fs::create_dir_all(&run_folder)?;
+
+        let entries = files
+            .iter()
+            .enumerate()
+            .map(|(index, (path, action))| {
+                let relative_path = path
+                    .strip_prefix(&self.obsidian_path)
+                    .unwrap_or(path)
+                    .to_path_buf();
+
+                if *action == JournalAction::Created {
+                    return Ok(JournalEntry {
+                        path: relative_path,
+                        blob: None,
+                        action: action.clone(),
+                        created: None,
+                        modified: None,
+                        written_hash: None,
+                    });
+                }
+
+                let metadata = fs::metadata(path)?;
+                let blob = format!("{:05}.{}", index, JOURNAL_BLOB_EXTENSION);
+                fs::copy(path, run_folder.join(&blob))?;
+
+                Ok(JournalEntry {
+                    path: relative_path,
+                    blob: Some(blob),
+                    action: action.clone(),
+                    created: metadata.created().ok().map(DateTime::<Utc>::from),
+                    modified: Some(DateTime::<Utc>::from(metadata.modified()?)),
+                    written_hash: None,
+                })
+            })
+            .collect::<Result<Vec<_>, Box<dyn Error + Send + Sync>>>()?;
+
+        let manifest = JournalManifest {
+            run_id,
+            timestamp_utc,
+            entries,
+        };
+
+        fs::write(
+            run_folder.join(JOURNAL_MANIFEST),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+
+        Ok(manifest)
+    }
+
+    /// drops the entries for files that weren't changed after all and records the hash of
+    /// what the rest were written as - except ok's own stores, which every run rewrites
+    pub fn complete(
+        &self,
+        manifest: &JournalManifest,
+        conflicts: &[PathBuf],
+    ) -> Result<JournalManifest, Box<dyn Error + Send + Sync>> {
+        let run_folder = self.run_folder(&manifest.run_id);
+        let mut manifest = manifest.clone();
+        manifest.entries.retain(|entry| {
+            let forget = conflicts.contains(&self.obsidian_path.join(&entry.path));
+            if let Some(blob) = entry.blob.as_ref().filter(|_| forget) {
+                let _ = fs::remove_file(run_folder.join(blob));
+            }
+            !forget
+        });
+
+        for entry in &mut manifest.entries {
+            if entry.action != JournalAction::Deleted && !entry.path.starts_with(CACHE_FOLDER) {
+                entry.written_hash = Some(hash_file(&self.obsidian_path.join(&entry.path))?);
+            }
+        }
+
+        fs::write(
+            run_folder.join(JOURNAL_MANIFEST),
+            serde_json::to_string_pretty(&manifest)?,
+        )?;
+
+        Ok(manifest)
+    }
+
+    /// the files that aren't the way the run left them - edited, or a deleted file that's back
+    pub fn changed_since(&self, manifest: &JournalManifest) -> Vec<PathBuf> {
+        manifest
+            .entries
+            .iter()
+            .filter(|entry| {
+                let path = self.obsidian_path.join(&entry.path);
+                match (&entry.action, &entry.written_hash) {
+                    (JournalAction::Deleted, _) => path.exists(),
+                    (_, Some(written_hash)) => hash_file(&path).ok().as_ref() != Some(written_hash),
+                    // one of ok's stores, or journaled before hashes were recorded
+                    (_, None) => false,
+                }
+            })
+            .map(|entry| entry.path.clone())
+            .collect()
+    }
+
+    /// completed journals, oldest first
+    pub fn list(&self) -> Result<Vec<JournalManifest>, Box<dyn Error + Send + Sync>> {
+        let journal_folder = self.journal_folder();
+        if !journal_folder.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut manifests = Vec::new();
+        for entry in fs::read_dir(journal_folder)? {
+            let manifest_path = entry?.path().join(JOURNAL_MANIFEST);
+            if manifest_path.exists() {
+                let contents = fs::read_to_string(manifest_path)?;
+                manifests.push(serde_json::from_str::<JournalManifest>(&contents)?);
+            }
+        }
+
+        manifests.sort_by(|a, b| a.run_id.cmp(&b.run_id));
+        Ok(manifests)
+    }
+
+    /// puts back the bytes and dates of every file in the run - defaults to the most recent run
+    /// the journal is removed once it has been restored. nothing is restored when a file has
+    /// changed since the run unless forced, as that would lose the newer edit
+    pub fn undo(
+        &self,
+        run_id: Option<&str>,
+        force: bool,
+        operational_timezone: &str,
+    ) -> Result<JournalManifest, Box<dyn Error + Send + Sync>> {
+        let manifests = self.list()?;
+        let manifest = match run_id {
+            Some(run_id) => manifests
+                .into_iter()
+                .find(|manifest| manifest.run_id == run_id)
+                .ok_or_else(|| format!("{} {}", JOURNAL_NOT_FOUND, run_id))?,
+            None => manifests.into_iter().last().ok_or(JOURNAL_NONE)?,
+        };
+
+        let changed = self.changed_since(&manifest);
+        if !changed.is_empty() && !force {
+            let paths: Vec<String> = changed
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            return Err(format!("{} {}", JOURNAL_CHANGED_SINCE, paths.join(", ")).into());
+        }
+
+        let run_folder = self.run_folder(&manifest.run_id);
+        for entry in &manifest.entries {
+            let path = self.obsidian_path.join(&entry.path);
+            let (Some(blob), Some(modified)) = (&entry.blob, entry.modified) else {
+                if path.exists() {
+                    fs::remove_file(&path)?;
+                }
+                continue;
+            };
+
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(run_folder.join(blob), &path)?;
+            utils::set_file_dates(&path, entry.created, modified, operational_timezone)?;
+        }
+
+        fs::remove_dir_all(run_folder)?;
+        Ok(manifest)
+    }
+
+    /// keeps the most recent journals and deletes the rest - returns how many were deleted
+    pub fn prune(&self, keep: usize) -> Result<usize, Box<dyn Error + Send + Sync>> {
+        let manifests = self.list()?;
+        let prune_count = manifests.len().saturating_sub(keep);
+
+        for manifest in manifests.iter().take(prune_count) {
+            fs::remove_dir_all(self.run_folder(&manifest.run_id))?;
+        }
+
+        Ok(prune_count)
+    }
+}
+
+fn hash_file(path: &Path) -> Result<String, Box<dyn Error + Send + Sync>> {
+    let mut hasher = Sha256::new();
+    hasher.update(fs::read(path)?);
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+impl ObsidianRepository {
+    /// journals every file that persist() is about to write or delete - along with the config
+    /// file when apply_changes is about to be reset in it and the date stores saved after it
+    pub fn record_journal(
+        &self,
+        obsidian_path: &Path,
+        config_file: Option<&Path>,
+    ) -> Result<Option<JournalManifest>, Box<dyn Error + Send + Sync>> {
+        let mut files: Vec<(PathBuf, JournalAction)> = self
+            .markdown_files
+            .files_to_persist()
+            .iter()
+            .map(|file| (file.path.clone(), JournalAction::Modified))
+            .chain(
+                self.image_files
+                    .iter()
+                    .filter(|image_file| image_file.delete)
+                    .map(|image_file| (image_file.path.clone(), JournalAction::Deleted)),
+            )
+            .collect();
+
+        if files.is_empty() {
+            return Ok(None);
+        }
+
+        let stores = [
+            self.date_store
+                .as_ref()
+                .map(|_| DateStore::store_file_path(obsidian_path)),
+            self.created_dates
+                .as_ref()
+                .map(|_| CreatedDateStore::store_file_path(obsidian_path)),
+        ];
+        for path in config_file
+            .map(Path::to_path_buf)
+            .into_iter()
+            .chain(stores.into_iter().flatten())
+        {
+            if files.iter().any(|(journaled, _)| *journaled == path) {
+                continue;
+            }
+            let action = if path.exists() {
+                JournalAction::Modified
+            } else {
+                JournalAction::Created
+            };
+            files.push((path, action));
+        }
+
+        Journal::new(obsidian_path).record(&files).map(Some)
+    }
+}
@@ -0,0 +1,190 @@
+use super::*;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use crate::DEFAULT_TIMEZONE;
+use tempfile::TempDir;
+
+fn modified_time(path: &Path) -> DateTime<Utc> {
+    DateTime::<Utc>::from(fs::metadata(path).unwrap().modified().unwrap())
+}
+
+#[test]
+fn test_undo_restores_persisted_and_deleted_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let original_date = test_utils::eastern_midnight(2024, 1, 2);
+
+    let tomato_path = TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    let recipe_path = TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("I like tomato\n![[empty.png]]".to_string())
+        .create(&temp_dir, "recipe.md");
+
+    let image_path = TestFileBuilder::new()
+        .with_content(Vec::new())
+        .create(&temp_dir, "empty.png");
+
+    let original_recipe = fs::read(&recipe_path).unwrap();
+    let original_tomato = fs::read(&tomato_path).unwrap();
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+
+    let manifest = repository
+        .record_journal(config.obsidian_path(), None)
+        .unwrap()
+        .expect("there are changes to journal");
+    repository.persist().unwrap();
+    let manifest = Journal::new(config.obsidian_path())
+        .complete(&manifest, &[])
+        .unwrap();
+
+    assert_ne!(fs::read(&recipe_path).unwrap(), original_recipe);
+    assert!(!image_path.exists());
+    assert!(manifest.entries.iter().any(
+        |entry| entry.path == Path::new("empty.png") && entry.action == JournalAction::Deleted
+    ));
+
+    let journal = Journal::new(config.obsidian_path());
+    assert_eq!(journal.list().unwrap(), vec![manifest.clone()]);
+
+    let restored = journal.undo(None, false, DEFAULT_TIMEZONE).unwrap();
+    assert_eq!(restored.run_id, manifest.run_id);
+
+    assert_eq!(fs::read(&recipe_path).unwrap(), original_recipe);
+    assert_eq!(fs::read(&tomato_path).unwrap(), original_tomato);
+    assert_eq!(modified_time(&recipe_path), original_date);
+    assert!(image_path.exists());
+    assert!(
+        journal.list().unwrap().is_empty(),
+        "journal is consumed by undo"
+    );
+}
+
+#[test]
+fn test_undo_unknown_run_id() {
+    let temp_dir = TempDir::new().unwrap();
+    let journal = Journal::new(temp_dir.path());
+
+    assert!(journal.undo(None, false, DEFAULT_TIMEZONE).is_err());
+    assert!(journal.undo(Some("nope"), false, DEFAULT_TIMEZONE).is_err());
+}
+
+#[test]
+fn test_prune_keeps_most_recent() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = TestFileBuilder::new().create(&temp_dir, "note.md");
+    let journal = Journal::new(temp_dir.path());
+
+    let mut run_ids = Vec::new();
+    for _ in 0..3 {
+        let manifest = journal
+            .record(&[(file_path.clone(), JournalAction::Modified)])
+            .unwrap();
+        run_ids.push(manifest.run_id);
+        // run ids have millisecond resolution
+        std::thread::sleep(std::time::Duration::from_millis(5));
+    }
+
+    assert_eq!(journal.prune(1).unwrap(), 2);
+
+    let remaining: Vec<String> = journal
+        .list()
+        .unwrap()
+        .into_iter()
+        .map(|manifest| manifest.run_id)
+        .collect();
+    assert_eq!(remaining, vec![run_ids[2].clone()]);
+}
+
+#[test]
+fn test_conflicting_file_is_skipped_and_forgotten() {
+    let temp_dir = TempDir::new().unwrap();
+    let original_date = test_utils::eastern_midnight(2024, 1, 2);
+
+    TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    let recipe_path = TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("I like tomato".to_string())
+        .create(&temp_dir, "recipe.md");
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+    let manifest = repository
+        .record_journal(config.obsidian_path(), None)
+        .unwrap()
+        .unwrap();
+
+    // edited by a sync client while ok was running
+    fs::write(&recipe_path, "I like tomato soup").unwrap();
+    filetime::set_file_mtime(&recipe_path, filetime::FileTime::now()).unwrap();
+
+    let conflicts = repository.persist().unwrap();
+    assert_eq!(conflicts, vec![recipe_path.clone()]);
+    assert_eq!(
+        fs::read_to_string(&recipe_path).unwrap(),
+        "I like tomato soup"
+    );
+
+    let journal = Journal::new(config.obsidian_path());
+    journal.complete(&manifest, &conflicts).unwrap();
+    let manifests = journal.list().unwrap();
+    assert!(manifests[0]
+        .entries
+        .iter()
+        .all(|entry| entry.path != Path::new("recipe.md")));
+
+    // undo leaves the newer edit alone
+    journal.undo(None, false, DEFAULT_TIMEZONE).unwrap();
+    assert_eq!(
+        fs::read_to_string(&recipe_path).unwrap(),
+        "I like tomato soup"
+    );
+}
+
+#[test]
+fn test_undo_refuses_file_edited_since_run() {
+    let temp_dir = TempDir::new().unwrap();
+    let original_date = test_utils::eastern_midnight(2024, 1, 2);
+
+    TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    let recipe_path = TestFileBuilder::new()
+        .with_matching_dates(original_date)
+        .with_content("I like tomato".to_string())
+        .create(&temp_dir, "recipe.md");
+    let original_recipe = fs::read(&recipe_path).unwrap();
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+    let manifest = repository
+        .record_journal(config.obsidian_path(), None)
+        .unwrap()
+        .unwrap();
+    repository.persist().unwrap();
+    let journal = Journal::new(config.obsidian_path());
+    journal.complete(&manifest, &[]).unwrap();
+
+    // edited after the apply
+    let edited = format!("{}\nmore tomato", fs::read_to_string(&recipe_path).unwrap());
+    fs::write(&recipe_path, &edited).unwrap();
+
+    let error = journal.undo(None, false, DEFAULT_TIMEZONE).unwrap_err();
+    assert!(error.to_string().contains("recipe.md"), "{}", error);
+    assert_eq!(fs::read_to_string(&recipe_path).unwrap(), edited);
+    assert_eq!(journal.list().unwrap().len(), 1, "journal is kept");
+
+    journal.undo(None, true, DEFAULT_TIMEZONE).unwrap();
+    assert_eq!(fs::read(&recipe_path).unwrap(), original_recipe);
+}
@@ -1,69 +1,186 @@
 #[cfg(test)]
+mod lib_tests;
+#[cfg(test)]
 pub mod test_utils;
 
+mod ambiguous_resolution;
+mod approval;
+mod cli;
 mod config;
+mod date_settings;
+mod date_source;
 mod frontmatter;
+mod frontmatter_repair;
 mod image_file;
+mod inflection;
+mod journal;
+mod link_density;
 mod markdown_file;
 mod markdown_files;
 mod obsidian_repository;
+mod property_operations;
+mod property_types;
 mod report;
+mod schema;
 mod validated_config;
 mod wikilink;
+mod yaml_editor;
 mod yaml_frontmatter;
 
 pub mod constants;
 pub mod utils;
 
+pub use crate::cli::Cli;
+
+use crate::approval::ApprovedChanges;
+use crate::cli::JournalCommand;
 use crate::config::Config;
-use crate::constants::DEFAULT_TIMEZONE;
+use crate::constants::*;
 use crate::frontmatter::FrontMatter;
+use crate::journal::Journal;
 use crate::markdown_file::MarkdownFile;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::validated_config::ValidatedConfig;
 use crate::yaml_frontmatter::YamlFrontMatter;
 use std::error::Error;
-use std::path::PathBuf;
 
-pub fn process_obsidian_repository(
-    config_path: PathBuf,
-) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let expanded_path = utils::expand_tilde(config_path);
+/// returns the process exit code - always 0 unless running `ok check`
+pub fn process_obsidian_repository(cli: &Cli) -> Result<u8, Box<dyn Error + Send + Sync>> {
+    let expanded_path = utils::expand_tilde(cli.config_file());
+    let run_mode = cli.run_mode();
 
-    let mut markdown_file = MarkdownFile::new(expanded_path, DEFAULT_TIMEZONE)?;
-    let mut config = if let Some(frontmatter) = &markdown_file.frontmatter {
-        Config::from_frontmatter(frontmatter.clone())?
-    } else {
-        return Err("Config file must have frontmatter".into());
-    };
+    let config = read_config(&MarkdownFile::new(expanded_path.clone(), DEFAULT_TIMEZONE)?)?;
+
+    if let Some(journal_command) = cli.journal_command() {
+        run_journal_command(journal_command, &config.validate()?)?;
+        return Ok(0);
+    }
+
+    // command line flags override the config file for this run only - the config file is
+    // never rewritten with them
+    let mut run_config = config.clone();
+    if let Some(overrides) = cli.overrides() {
+        overrides.apply_to(&mut run_config, run_mode)?;
+    }
 
-    let validated_config = config.validate()?;
+    let mut validated_config = run_config.validate()?;
+
+    if run_mode.applies_approved_changes() {
+        let approved_changes =
+            ApprovedChanges::read(&validated_config.output_folder().join(OUTPUT_MARKDOWN_FILE))?;
+        println!("{} {}", APPROVED_CHANGES, approved_changes.len());
+        validated_config.set_approved_changes(Some(approved_changes));
+    }
 
     // ANALYSIS PHASE
     let mut obsidian_repository = ObsidianRepository::new(&validated_config)?;
 
     // REPORTING PHASE
-    obsidian_repository.write_reports(&validated_config)?;
+    if !run_mode.writes_output_file() {
+        let summary = obsidian_repository.print_check_summary();
+        return Ok(summary.exit_code());
+    }
+
+    obsidian_repository.write_reports(&validated_config, run_mode)?;
+
+    let mut conflicts = Vec::new();
+    let mut manifest = None;
+    if validated_config.apply_changes() {
+        // only reset apply_changes if it was the config file that asked for changes
+        let config_file = std::path::absolute(&expanded_path)?;
+        let resets_config = config.apply_changes == Some(true);
+
+        // journal the originals first so the run can be undone
+        manifest = obsidian_repository.record_journal(
+            validated_config.obsidian_path(),
+            resets_config.then_some(config_file.as_path()),
+        )?;
+        if let Some(manifest) = &manifest {
+            println!("{} {}", JOURNAL_RECORDED, manifest.run_id);
+        }
+
+        conflicts = obsidian_repository.persist()?;
+        for path in &conflicts {
+            println!("{} {}", PERSIST_CONFLICT, path.display());
+        }
 
-    if config.apply_changes == Some(true) {
-        obsidian_repository.persist()?;
-        reset_apply_changes(&mut markdown_file, &mut config)?;
+        // the config file is read again as it can be a note in the vault that was just persisted
+        if resets_config {
+            reset_apply_changes(MarkdownFile::new(config_file, DEFAULT_TIMEZONE)?)?;
+        }
+    }
+
+    obsidian_repository.save_date_store(validated_config.apply_changes(), &conflicts)?;
+    obsidian_repository.save_created_dates(validated_config.apply_changes(), &conflicts)?;
+
+    // undo mustn't put back a file that was never written as that would lose the newer edit
+    if let Some(manifest) = &manifest {
+        Journal::new(validated_config.obsidian_path()).complete(manifest, &conflicts)?;
+    }
+
+    Ok(0)
+}
+
+fn run_journal_command(
+    journal_command: JournalCommand,
+    validated_config: &ValidatedConfig,
+) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let journal = Journal::new(validated_config.obsidian_path());
+
+    match journal_command {
+        JournalCommand::List => {
+            for manifest in journal.list()? {
+                println!(
+                    "{}  {} {}",
+                    manifest.run_id,
+                    manifest.entries.len(),
+                    Phrase::File(manifest.entries.len()).pluralize()
+                );
+            }
+        }
+        JournalCommand::Prune(keep) => {
+            println!("{} {}", JOURNAL_PRUNED, journal.prune(keep)?);
+        }
+        JournalCommand::Undo(run_id, force) => {
+            let manifest = journal.undo(
+                run_id.as_deref(),
+                force,
+                validated_config.operational_timezone(),
+            )?;
+            for entry in &manifest.entries {
+                println!("{}", entry.path.display());
+            }
+            println!("{} {}", JOURNAL_RESTORED, manifest.run_id);
+        }
     }
 
     Ok(())
 }
 
+fn read_config(markdown_file: &MarkdownFile) -> Result<Config, Box<dyn Error + Send + Sync>> {
+    match &markdown_file.frontmatter {
+        Some(frontmatter) => Config::from_frontmatter(frontmatter.clone()),
+        None => Err("Config file must have frontmatter".into()),
+    }
+}
+
 fn reset_apply_changes(
-    markdown_file: &mut MarkdownFile,
-    config: &mut Config,
+    mut markdown_file: MarkdownFile,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let mut config = read_config(&markdown_file)?;
+
     // this whole thing is a bit of a code smell
     // converting from frontmatter to config
     // making sure to update modified date so we can re-use markdown_file persist
     // which in this case doesn't actually matter but does matter for frontmatter...
     config.apply_changes = Some(false);
     let config_yaml = config.to_yaml_str()?;
-    let updated_frontmatter = FrontMatter::from_yaml_str(&config_yaml)?;
+    let mut updated_frontmatter = FrontMatter::from_yaml_str(&config_yaml)?;
+    // keep the original yaml so only apply_changes and date_modified are rewritten
+    updated_frontmatter.original_yaml = markdown_file
+        .frontmatter
+        .as_ref()
+        .and_then(|fm| fm.original_yaml.clone());
     markdown_file.frontmatter = Some(updated_frontmatter);
 
     let operational_timezone = match &config.operational_timezone {
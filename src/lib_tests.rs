@@ -0,0 +1,65 @@
+use crate::config::Config;
+use crate::date_source::{CreatedDateStore, DateStore};
+use crate::markdown_file::MarkdownFile;
+use crate::test_utils::TestFileBuilder;
+use crate::{constants::*, process_obsidian_repository, Cli};
+use clap::Parser;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+// the config note lives in the vault and mentions a page so it gets back populated itself
+fn create_vault_with_config_note(temp_dir: &TempDir) -> PathBuf {
+    fs::create_dir(temp_dir.path().join(".obsidian")).unwrap();
+    fs::create_dir(temp_dir.path().join("conf")).unwrap();
+    fs::create_dir(temp_dir.path().join("output")).unwrap();
+
+    TestFileBuilder::new()
+        .with_content("a fruit".to_string())
+        .create(temp_dir, "Tomato.md");
+    TestFileBuilder::new()
+        .with_custom_frontmatter(format!(
+            "obsidian_path: {}\napply_changes: true\noutput_folder: output\ndates:\n  source: content_hash",
+            temp_dir.path().display()
+        ))
+        .with_content("I grow a Tomato here.".to_string())
+        .create(temp_dir, "conf/config.md")
+}
+
+fn run(args: &[&str]) -> u8 {
+    let cli = Cli::try_parse_from(std::iter::once("ok").chain(args.iter().copied())).unwrap();
+    process_obsidian_repository(&cli).unwrap()
+}
+
+#[test]
+fn test_config_note_in_vault_is_back_populated_and_reset() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = create_vault_with_config_note(&temp_dir);
+
+    assert_eq!(run(&[config_path.to_str().unwrap()]), 0);
+
+    let config_note = MarkdownFile::new(config_path, DEFAULT_TIMEZONE).unwrap();
+    assert!(config_note.content.contains("I grow a [[Tomato]] here."));
+    let config = Config::from_frontmatter(config_note.frontmatter.unwrap()).unwrap();
+    assert_eq!(config.apply_changes, Some(false));
+}
+
+#[test]
+fn test_undo_restores_config_note_and_stores() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_path = create_vault_with_config_note(&temp_dir);
+    let original_config = fs::read(&config_path).unwrap();
+    let stores = [
+        DateStore::store_file_path(temp_dir.path()),
+        CreatedDateStore::store_file_path(temp_dir.path()),
+    ];
+
+    run(&[config_path.to_str().unwrap()]);
+    assert_ne!(fs::read(&config_path).unwrap(), original_config);
+    assert!(stores[0].exists());
+
+    run(&["undo", config_path.to_str().unwrap()]);
+    assert_eq!(fs::read(&config_path).unwrap(), original_config);
+    // the stores didn't exist before the run
+    assert!(stores.iter().all(|store| !store.exists()));
+}
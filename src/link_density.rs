@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod link_density_tests;
+
+use crate::constants::*;
+use crate::markdown_file::{is_heading, CodeBlockExcluder};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::{self, target_key};
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// how often back population links the same target
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinkDensity {
+    #[default]
+    All,
+    FirstPerNote,
+    /// a section starts at each heading
+    FirstPerSection,
+    /// a paragraph ends at a blank line or a heading
+    FirstPerParagraph,
+}
+
+impl FromStr for LinkDensity {
+    type Err = String;
+
+    fn from_str(link_density: &str) -> Result<Self, Self::Err> {
+        match link_density {
+            LINK_DENSITY_ALL => Ok(LinkDensity::All),
+            LINK_DENSITY_FIRST_PER_NOTE => Ok(LinkDensity::FirstPerNote),
+            LINK_DENSITY_FIRST_PER_SECTION => Ok(LinkDensity::FirstPerSection),
+            LINK_DENSITY_FIRST_PER_PARAGRAPH => Ok(LinkDensity::FirstPerParagraph),
+            unknown => Err(format!("unknown link_density: {}", unknown)),
+        }
+    }
+}
+
+impl fmt::Display for LinkDensity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LinkDensity::All => LINK_DENSITY_ALL,
+            LinkDensity::FirstPerNote => LINK_DENSITY_FIRST_PER_NOTE,
+            LinkDensity::FirstPerSection => LINK_DENSITY_FIRST_PER_SECTION,
+            LinkDensity::FirstPerParagraph => LINK_DENSITY_FIRST_PER_PARAGRAPH,
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl LinkDensity {
+    /// the region each content line is in - a target is linked once per region. headings in
+    /// code blocks don't start a section
+    pub fn regions(&self, content: &str) -> Vec<usize> {
+        let mut code_block_tracker = CodeBlockExcluder::new();
+        let mut region = 0;
+
+        content
+            .lines()
+            .map(|line| {
+                code_block_tracker.update(line);
+                let starts_region = match self {
+                    LinkDensity::All | LinkDensity::FirstPerNote => false,
+                    LinkDensity::FirstPerSection => {
+                        !code_block_tracker.is_in_code_block() && is_heading(line)
+                    }
+                    LinkDensity::FirstPerParagraph => {
+                        line.trim().is_empty()
+                            || (!code_block_tracker.is_in_code_block() && is_heading(line))
+                    }
+                };
+                if starts_region {
+                    region += 1;
+                }
+                region
+            })
+            .collect()
+    }
+}
+
+impl ObsidianRepository {
+    /// keeps the first match of each target per region and moves the rest to skipped - a region
+    /// that already links to the target gets no new link
+    pub fn apply_link_density(&mut self, validated_config: &ValidatedConfig) {
+        let link_density = validated_config.link_density();
+        if link_density == LinkDensity::All {
+            return;
+        }
+
+        for markdown_file in &mut self.markdown_files {
+            if !markdown_file.has_unambiguous_matches() {
+                continue;
+            }
+
+            let regions = link_density.regions(&markdown_file.content);
+            let frontmatter_line_count = markdown_file.frontmatter_line_count;
+            let region_of = |line_number: usize| {
+                line_number
+                    .checked_sub(frontmatter_line_count + 1)
+                    .and_then(|line_idx| regions.get(line_idx))
+                    .copied()
+                    .unwrap_or_default()
+            };
+
+            let mut linked: HashSet<(usize, String)> = HashSet::new();
+            let mut code_block_tracker = CodeBlockExcluder::new();
+            for (line_idx, line) in markdown_file.content.lines().enumerate() {
+                code_block_tracker.update(line);
+                if code_block_tracker.is_in_code_block() {
+                    continue;
+                }
+                for existing in wikilink::extract_wikilinks(line).valid {
+                    linked.insert((regions[line_idx], target_key(&existing.target)));
+                }
+            }
+
+            let mut matches = std::mem::take(&mut markdown_file.matches.unambiguous);
+            matches.sort_by_key(|m| (m.line_number, m.position));
+            for back_populate_match in matches {
+                let key = (
+                    region_of(back_populate_match.line_number),
+                    target_key(&back_populate_match.target),
+                );
+                if linked.insert(key) {
+                    markdown_file.matches.unambiguous.push(back_populate_match);
+                } else {
+                    markdown_file.matches.skipped.push(back_populate_match);
+                }
+            }
+        }
+    }
+}
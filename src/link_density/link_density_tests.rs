@@ -0,0 +1,120 @@
+use super::*;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+const CONTENT: &str = "# One\nsugar and sugar\n\nmore sugar\n# Two\nsugar here";
+
+#[test]
+fn test_is_heading() {
+    let test_cases = [
+        ("# Heading", true),
+        ("### Heading", true),
+        ("  ## Heading", true),
+        ("#", true),
+        ("#tag", false),
+        ("####### too deep", false),
+        ("text # not a heading", false),
+    ];
+
+    for (line, expected) in test_cases {
+        assert_eq!(is_heading(line), expected, "Failed for: {}", line);
+    }
+}
+
+#[test]
+fn test_regions() {
+    let content = "intro\n# One\ntext\n\n```\n# not a heading\n```\n## Two\ntext";
+
+    assert_eq!(LinkDensity::FirstPerNote.regions(content), vec![0; 9]);
+    assert_eq!(
+        LinkDensity::FirstPerSection.regions(content),
+        vec![0, 1, 1, 1, 1, 1, 1, 2, 2]
+    );
+    assert_eq!(
+        LinkDensity::FirstPerParagraph.regions(content),
+        vec![0, 1, 1, 2, 2, 2, 2, 3, 3]
+    );
+}
+
+fn linked_and_skipped(content: &str, link_density: LinkDensity) -> (Vec<usize>, usize) {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_content("# Sugar".to_string())
+        .create(&temp_dir, "Sugar.md");
+    TestFileBuilder::new()
+        .with_title("note".to_string())
+        .with_content(content.to_string())
+        .create(&temp_dir, "note.md");
+
+    let config = validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+        builder.link_density(link_density);
+    })
+    .unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let file = repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with("note.md"))
+        .unwrap();
+
+    (
+        file.matches
+            .unambiguous
+            .iter()
+            .map(|m| m.line_number - file.frontmatter_line_count)
+            .collect(),
+        file.matches.skipped.len(),
+    )
+}
+
+#[test]
+fn test_link_density() {
+    let test_cases = [
+        (LinkDensity::All, vec![2, 2, 4, 6], 0),
+        (LinkDensity::FirstPerNote, vec![2], 3),
+        (LinkDensity::FirstPerSection, vec![2, 6], 2),
+        (LinkDensity::FirstPerParagraph, vec![2, 4, 6], 1),
+    ];
+
+    for (link_density, expected_lines, expected_skipped) in test_cases {
+        let (mut lines, skipped) = linked_and_skipped(CONTENT, link_density);
+        lines.sort();
+        assert_eq!(lines, expected_lines, "Failed for: {}", link_density);
+        assert_eq!(skipped, expected_skipped, "Failed for: {}", link_density);
+    }
+}
+
+#[test]
+fn test_existing_links_count() {
+    let content = "# One\nsee [[Sugar|the sweet stuff]]\nsugar\n# Two\nsugar";
+
+    assert_eq!(
+        linked_and_skipped(content, LinkDensity::FirstPerNote),
+        (vec![], 2)
+    );
+    assert_eq!(
+        linked_and_skipped(content, LinkDensity::FirstPerSection),
+        (vec![5], 1)
+    );
+}
+
+#[test]
+fn test_existing_links_to_a_heading_or_block_count() {
+    for link in ["[[Sugar#Types]]", "[[Sugar#^intro|the intro]]"] {
+        let content = format!("# One\nsee {}\nsugar\n# Two\nsugar", link);
+
+        assert_eq!(
+            linked_and_skipped(&content, LinkDensity::FirstPerNote),
+            (vec![], 2),
+            "Failed for: {}",
+            link
+        );
+        assert_eq!(
+            linked_and_skipped(&content, LinkDensity::FirstPerSection),
+            (vec![5], 1),
+            "Failed for: {}",
+            link
+        );
+    }
+}
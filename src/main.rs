@@ -1,27 +1,12 @@
 use crate::constants::*;
 use crate::utils::Timer;
+use clap::Parser;
 use ok::*;
 use std::error::Error;
-use std::path::PathBuf;
-
-// Custom error type for main specific errors
-#[derive(Debug)]
-enum MainError {
-    Usage(String),
-}
-
-impl std::fmt::Display for MainError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            MainError::Usage(msg) => write!(f, "{}", msg),
-        }
-    }
-}
-
-impl Error for MainError {}
+use std::process::ExitCode;
 
 // Separate error handling and reporting logic
-fn handle_error(e: Box<dyn Error + Send + Sync>) -> Result<(), Box<dyn Error + Send + Sync>> {
+fn handle_error(e: Box<dyn Error + Send + Sync>) -> Result<ExitCode, Box<dyn Error + Send + Sync>> {
     eprintln!("{ERROR_OCCURRED}");
     eprintln!("{ERROR_TYPE}");
     eprintln!("{}", std::any::type_name_of_val(&*e));
@@ -33,18 +18,10 @@ fn handle_error(e: Box<dyn Error + Send + Sync>) -> Result<(), Box<dyn Error + S
     Err(e)
 }
 
-// Get config file name with better error handling
-fn get_config_file() -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
-    let args: Vec<String> = std::env::args().collect();
-
-    if args.len() != 2 {
-        return Err(Box::new(MainError::Usage(USAGE.into())));
-    }
+fn main() -> Result<ExitCode, Box<dyn Error + Send + Sync>> {
+    // parse before starting the timer so --help and usage errors exit cleanly
+    let cli = Cli::parse();
 
-    Ok(PathBuf::from(&args[1]))
-}
-
-fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     let _timer = Timer::new(TOTAL_TIME);
 
     #[cfg(debug_assertions)]
@@ -56,23 +33,8 @@ fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
         env!("CARGO_PKG_VERSION")
     );
 
-    let config_path = get_config_file()?;
-
-    match process_obsidian_repository(config_path) {
-        Ok(_) => Ok(()),
+    match process_obsidian_repository(&cli) {
+        Ok(exit_code) => Ok(ExitCode::from(exit_code)),
         Err(e) => handle_error(e), // Removed writer parameter
     }
 }
-
-#[cfg(test)]
-mod main_tests {
-    use super::*;
-
-    #[test]
-    fn test_get_config_file_no_args() {
-        match get_config_file() {
-            Ok(_) => panic!("Expected error for missing arguments"),
-            Err(e) => assert!(e.to_string().contains("usage:")),
-        }
-    }
-}
@@ -17,47 +17,119 @@ mod persist_tests;
 #[cfg(test)]
 mod process_content_tests;
 #[cfg(test)]
+mod simplify_wikilinks_tests;
+#[cfg(test)]
 mod table_handling_tests;
 
 mod markdown_file_types;
 mod text_excluder;
 
 pub use markdown_file_types::*;
-pub use text_excluder::{CodeBlockExcluder, InlineCodeExcluder};
+pub use text_excluder::{
+    inline_exclusion_zones, is_heading, CodeBlockExcluder, InlineCodeExcluder, RegionExcluder,
+    SkipRegion,
+};
 
+use crate::approval::{self, ApprovedChanges};
 use crate::constants::*;
+use crate::date_settings::DateSettings;
+use crate::date_source::{CreatedDateStore, DateStore, GitDates, ReferenceDates};
 use crate::frontmatter::FrontMatter;
+use crate::frontmatter_repair::FrontmatterRepair;
+use crate::inflection::Inflections;
+use crate::property_operations::PropertyChange;
+use crate::property_types::PropertyTypeMismatch;
+use crate::schema::SchemaViolation;
 use crate::utils::{IMAGE_REGEX, MARKDOWN_REGEX};
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink;
 use crate::wikilink::{ExtractedWikilinks, InvalidWikilink, ToWikilink, Wikilink};
 use crate::yaml_frontmatter;
-use crate::yaml_frontmatter::{YamlFrontMatter, YamlFrontMatterError};
+use crate::yaml_frontmatter::YamlFrontMatterError;
 use crate::{obsidian_repository, utils};
 
 use aho_corasick::AhoCorasick;
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use regex::Regex;
+use serde_yaml::Mapping;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::error::Error;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::{fs, io};
 
 #[derive(Debug, Clone)]
 pub struct MarkdownFile {
     pub content: String,
+    /// the content hash as read - only computed when a date store or git dates are in use
+    pub content_hash: Option<String>,
     pub date_created_fix: DateCreatedFixValidation,
     pub date_validation_created: DateValidation,
     pub date_validation_modified: DateValidation,
+    /// false leaves date_modified alone when ok edits the note
+    pub dates_enabled: bool,
     pub do_not_back_populate_regexes: Option<Vec<Regex>>,
     pub frontmatter: Option<FrontMatter>,
     pub frontmatter_error: Option<YamlFrontMatterError>,
     pub frontmatter_line_count: usize,
+    pub frontmatter_repair: Option<FrontmatterRepair>,
     pub image_links: ImageLinks,
     pub wikilinks: Wikilinks,
     pub matches: BackPopulateMatches,
     pub path: PathBuf,
     pub persist_reasons: Vec<PersistReason>,
+    pub property_changes: Vec<PropertyChange>,
+    pub property_type_mismatches: Vec<PropertyTypeMismatch>,
+    pub schema_violations: Vec<SchemaViolation>,
+    pub simplify_wikilink_matches: Vec<SimplifyWikilinkMatch>,
+    pub snapshot: FileSnapshot,
+}
+
+/// config driven behavior for MarkdownFile::with_options
+#[derive(Debug, Clone)]
+pub struct MarkdownFileOptions {
+    pub operational_timezone: String,
+    /// the properties a note without frontmatter is given
+    pub create_frontmatter: Option<Mapping>,
+    pub dates_enabled: bool,
+    pub date_settings: DateSettings,
+    pub created_dates: Option<Arc<CreatedDateStore>>,
+    pub date_store: Option<Arc<DateStore>>,
+    pub git_dates: Option<Arc<GitDates>>,
+    pub approved_changes: Option<ApprovedChanges>,
+}
+
+impl MarkdownFileOptions {
+    pub fn new(operational_timezone: &str) -> Self {
+        Self {
+            operational_timezone: operational_timezone.to_string(),
+            create_frontmatter: None,
+            dates_enabled: true,
+            date_settings: DateSettings::default(),
+            created_dates: None,
+            date_store: None,
+            git_dates: None,
+            approved_changes: None,
+        }
+    }
+}
+
+impl From<&ValidatedConfig> for MarkdownFileOptions {
+    fn from(validated_config: &ValidatedConfig) -> Self {
+        Self {
+            operational_timezone: validated_config.operational_timezone().to_string(),
+            create_frontmatter: validated_config.create_frontmatter().cloned(),
+            dates_enabled: validated_config.dates_enabled(),
+            date_settings: validated_config.date_settings().clone(),
+            // the repository loads the stores or the git dates when the date source needs them
+            created_dates: None,
+            date_store: None,
+            git_dates: None,
+            approved_changes: validated_config.approved_changes().cloned(),
+        }
+    }
 }
 
 impl MarkdownFile {
@@ -65,60 +137,168 @@ impl MarkdownFile {
         path: PathBuf,
         operational_timezone: &str,
     ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Self::with_options(path, &MarkdownFileOptions::new(operational_timezone))
+    }
+
+    pub fn with_options(
+        path: PathBuf,
+        options: &MarkdownFileOptions,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let operational_timezone = options.operational_timezone.as_str();
+        let modified = fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .map_err(|_| format!("{}{}", ERROR_NOT_FOUND, path.display()))?;
         let full_content = utils::read_contents_from_file(&path)?;
+        let snapshot = FileSnapshot::new(modified, &full_content);
 
         let yaml_result = yaml_frontmatter::find_yaml_section(&full_content);
-        let frontmatter_line_count = match &yaml_result {
+        let mut frontmatter_line_count = match &yaml_result {
             Ok(Some((yaml_section, _))) => yaml_section.lines().count() + 2,
             _ => 0,
         };
 
-        let (mut frontmatter, content, frontmatter_error) = match yaml_result {
+        let (mut frontmatter, mut content, mut frontmatter_error) = match yaml_result {
             Ok(Some((yaml_section, after_yaml))) => {
-                match FrontMatter::from_yaml_str(yaml_section) {
+                match FrontMatter::from_markdown_yaml(yaml_section, &options.date_settings) {
                     Ok(fm) => (Some(fm), after_yaml.to_string(), None),
                     Err(e) => (None, after_yaml.to_string(), Some(e)),
                 }
             }
-            Ok(None) => (None, full_content, Some(YamlFrontMatterError::Missing)),
-            Err(e) => (None, full_content, Some(e)),
+            Ok(None) => (
+                None,
+                full_content.clone(),
+                Some(YamlFrontMatterError::Missing),
+            ),
+            Err(e) => (None, full_content.clone(), Some(e)),
         };
 
-        let (date_validation_created, date_validation_modified) =
-            get_date_validations(&frontmatter, &path, operational_timezone)?;
+        // a repaired frontmatter is used from here on as if the note had been written that
+        // way - unless `ok apply` found change ids and the repair isn't one of the approved
+        let mut frontmatter_repair = None;
+        if let Some(error) = frontmatter_error
+            .as_ref()
+            .filter(|error| **error != YamlFrontMatterError::Missing)
+        {
+            if let Some(repaired) =
+                FrontmatterRepair::find(&full_content, &error.to_string(), &options.date_settings)
+            {
+                let mut repair = repaired.repair;
+                repair.applied = options
+                    .approved_changes
+                    .as_ref()
+                    .is_none_or(|approved| approved.contains(&repair.change_id(&path)));
+                if repair.applied {
+                    frontmatter = Some(repaired.frontmatter);
+                    content = full_content[repaired.content_start..].to_string();
+                    frontmatter_error = None;
+                    frontmatter_line_count = full_content[..repaired.content_start].lines().count();
+                }
+                frontmatter_repair = Some(repair);
+            }
+        }
 
-        let date_created_fix = DateCreatedFixValidation::from_frontmatter(
+        let content_hash = (options.date_store.is_some() || options.git_dates.is_some())
+            .then(|| get_content_hash(&frontmatter, &content));
+        let reference_dates =
+            get_reference_dates(&frontmatter, &path, content_hash.as_ref(), options)?;
+
+        // the dates are filled in below like for any note that's missing them - without dates
+        // and without properties there'd be nothing to write
+        let mut frontmatter_created = false;
+        if let (Some(YamlFrontMatterError::Missing), Some(properties)) =
+            (&frontmatter_error, &options.create_frontmatter)
+        {
+            if options.dates_enabled || !properties.is_empty() {
+                let mut created = FrontMatter::create(properties, &options.date_settings)?;
+                // adding frontmatter isn't an edit so the file keeps its modified time
+                created.raw_date_modified = Some(reference_dates.modified);
+                frontmatter = Some(created);
+                frontmatter_error = None;
+                frontmatter_created = true;
+            }
+        }
+
+        let frontmatter_repaired = frontmatter_repair
+            .as_ref()
+            .is_some_and(|repair| repair.applied);
+        if let (true, Some(fm)) = (frontmatter_repaired, frontmatter.as_mut()) {
+            // repairing the yaml isn't an edit so the file keeps its modified time
+            fm.raw_date_modified = Some(reference_dates.modified);
+        }
+
+        let (date_validation_created, date_validation_modified) = get_date_validations(
             &frontmatter,
-            date_validation_created.file_system_date,
+            reference_dates,
             operational_timezone,
+            &options.date_settings,
         );
 
-        let persist_reasons = process_date_validations(
-            &mut frontmatter,
-            &date_validation_created,
-            &date_validation_modified,
-            &date_created_fix,
+        let date_created_fix = DateCreatedFixValidation::from_frontmatter(
+            &frontmatter,
+            date_validation_created.file_system_date,
             operational_timezone,
+            &options.date_settings,
         );
 
+        let dates_approved = options.approved_changes.as_ref().is_none_or(|approved| {
+            approved.contains(&approval::date_change_id(
+                &path,
+                &date_validation_created,
+                &date_validation_modified,
+                &date_created_fix,
+            ))
+        });
+
+        // a created frontmatter is approved with the note's dates as it's created with them
+        let mut persist_reasons = Vec::new();
+        if frontmatter_repaired {
+            persist_reasons.push(PersistReason::FrontmatterRepaired);
+        }
+        if frontmatter_created {
+            if dates_approved {
+                persist_reasons.push(PersistReason::FrontmatterCreated);
+            } else {
+                frontmatter = None;
+                frontmatter_error = Some(YamlFrontMatterError::Missing);
+            }
+        }
+
+        if options.dates_enabled && dates_approved {
+            persist_reasons.extend(process_date_validations(
+                &mut frontmatter,
+                &date_validation_created,
+                &date_validation_modified,
+                &date_created_fix,
+                operational_timezone,
+            ));
+        }
+
         let do_not_back_populate_regexes = frontmatter
             .as_ref()
             .and_then(|fm| fm.get_do_not_back_populate_regexes());
 
         let mut file_info = MarkdownFile {
             content,
+            content_hash,
             date_created_fix,
             do_not_back_populate_regexes,
             date_validation_created,
             date_validation_modified,
+            dates_enabled: options.dates_enabled,
             frontmatter,
             frontmatter_error,
             frontmatter_line_count,
+            frontmatter_repair,
             wikilinks: Wikilinks::default(),
             image_links: ImageLinks::default(),
             matches: BackPopulateMatches::default(),
             path,
             persist_reasons,
+            property_changes: Vec::new(),
+            property_type_mismatches: Vec::new(),
+            schema_violations: Vec::new(),
+            simplify_wikilink_matches: Vec::new(),
+            snapshot,
         };
 
         let extracted_wikilinks = file_info.process_wikilinks()?;
@@ -135,7 +315,7 @@ impl MarkdownFile {
     // Add a method to reconstruct the full markdown content
     pub fn to_full_content(&self) -> String {
         if let Some(ref fm) = self.frontmatter {
-            if let Ok(yaml) = fm.to_yaml_str() {
+            if let Ok(yaml) = fm.to_persisted_yaml_str() {
                 format!("---\n{}\n---\n{}", yaml.trim(), self.content.trim())
             } else {
                 self.content.clone()
@@ -145,16 +325,26 @@ impl MarkdownFile {
         }
     }
 
+    /// fails with PersistConflict if the file changed on disk since it was read
     pub fn persist(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
-        // Write the updated content to the file
-        fs::write(&self.path, self.to_full_content())?;
+        if !self.snapshot.is_current(&self.path)? {
+            return Err(Box::new(PersistConflict {
+                path: self.path.clone(),
+            }));
+        }
 
         let frontmatter = self.frontmatter.as_ref().expect("Frontmatter is required");
         let modified_date = frontmatter
             .raw_date_modified
             .ok_or_else(|| "raw_date_modified must be set for persist".to_string())?;
 
-        let created_date = frontmatter.raw_date_created;
+        // the rename gives the file a new creation date so hang on to the original
+        let created_date = frontmatter.raw_date_created.or(fs::metadata(&self.path)
+            .and_then(|metadata| metadata.created())
+            .ok()
+            .map(DateTime::<Utc>::from));
+
+        utils::write_atomically(&self.path, &self.to_full_content())?;
 
         // Use `set_file_dates` for both macOS and non-macOS platforms
         utils::set_file_dates(&self.path, created_date, modified_date, "America/New_York")?;
@@ -162,32 +352,73 @@ impl MarkdownFile {
         Ok(())
     }
 
-    pub fn mark_as_back_populated(&mut self, operational_timezone: &str) {
+    pub(crate) fn mark_edited(&mut self, reason: PersistReason, operational_timezone: &str) {
         let fm = self.frontmatter.as_mut().unwrap_or_else(|| {
             panic!(
-                "Attempted to mark file '{}' as back populated without frontmatter",
+                "Attempted to mark file '{}' as edited without frontmatter",
                 self.path.display()
             )
         });
 
         // Remove any DateModifiedUpdated reasons since we'll be setting the date to now
         // this way we won't show extraneous results in persist_reasons_report
-        self.persist_reasons
-            .retain(|reason| !matches!(reason, PersistReason::DateModifiedUpdated { .. }));
+        if matches!(reason, PersistReason::BackPopulated) {
+            self.persist_reasons
+                .retain(|reason| !matches!(reason, PersistReason::DateModifiedUpdated { .. }));
+        }
 
-        fm.set_date_modified_now(operational_timezone);
-        self.persist_reasons.push(PersistReason::BackPopulated);
+        fm.mark_edited(self.dates_enabled, operational_timezone);
+        self.persist_reasons.push(reason);
     }
 
-    pub fn mark_image_reference_as_updated(&mut self, operational_timezone: &str) {
-        let fm = self
-            .frontmatter
-            .as_mut()
-            .expect("Attempted to record image references change on a file without frontmatter");
+    // any wikilink that renders as part of a simplify_wikilinks string is replaced with its display text
+    // e.g., with "Ed:" configured, [[Ed Barnes|Ed]]: is replaced with Ed:
+    pub(crate) fn process_file_for_simplify_wikilinks(&mut self, config: &ValidatedConfig) {
+        let regexes = match config.simplify_wikilinks_regexes() {
+            Some(regexes) if !regexes.is_empty() => regexes,
+            _ => return,
+        };
 
-        fm.set_date_modified_now(operational_timezone);
-        self.persist_reasons
-            .push(PersistReason::ImageReferencesModified);
+        let relative_path =
+            obsidian_repository::format_relative_path(&self.path, config.obsidian_path());
+        let mut code_block_tracker = CodeBlockExcluder::new();
+
+        for (line_idx, line) in self.content.lines().enumerate() {
+            code_block_tracker.update(line);
+            if code_block_tracker.is_in_code_block() || !line.contains(OPENING_WIKILINK) {
+                continue;
+            }
+
+            let rendered = wikilink::render_wikilinks(line);
+            let mut positions = HashSet::new();
+
+            for regex in regexes {
+                for mat in regex.find_iter(&rendered.text) {
+                    for segment in rendered.wikilink_segments_within(mat.start(), mat.end()) {
+                        let (start, end) = segment.source_span;
+                        if !positions.insert(start) {
+                            continue;
+                        }
+
+                        let display_text = segment
+                            .wikilink
+                            .as_ref()
+                            .map(|wikilink| wikilink.display_text.clone())
+                            .unwrap_or_default();
+
+                        self.simplify_wikilink_matches.push(SimplifyWikilinkMatch {
+                            display_text,
+                            line_number: self.get_real_line_number(line_idx),
+                            line_text: line.to_string(),
+                            position: start,
+                            relative_path: relative_path.clone(),
+                            rendered_text: mat.as_str().to_string(),
+                            wikilink_text: line[start..end].to_string(),
+                        });
+                    }
+                }
+            }
+        }
     }
 
     pub(crate) fn process_file_for_back_populate_replacements(
@@ -197,17 +428,12 @@ impl MarkdownFile {
         ac: &AhoCorasick,
     ) {
         let content = self.content.clone();
-        let mut code_block_tracker = CodeBlockExcluder::new();
+        let mut region_excluder = RegionExcluder::new(config.skip_regions());
 
         for (line_idx, line) in content.lines().enumerate() {
-            // Skip empty/whitespace lines early
-            if line.trim().is_empty() {
-                continue;
-            }
-
-            // Update state and skip if needed
-            code_block_tracker.update(line);
-            if code_block_tracker.is_in_code_block() {
+            // blank lines end html blocks and start indented code so the excluder sees them too
+            region_excluder.update(line);
+            if line.trim().is_empty() || region_excluder.is_excluded() {
                 continue;
             }
 
@@ -325,11 +551,15 @@ impl MarkdownFile {
         let mut matches = Vec::new();
         let exclusion_zones = self.collect_exclusion_zones(line, config);
 
+        // the automaton's patterns are case folded - see sort_and_build_wikilinks_ac
+        let folded = utils::FoldedText::new(line);
+
         // Collect all valid matches
-        for mat in ac.find_iter(line) {
+        for mat in ac.find_iter(&folded.text) {
             let wikilink = sorted_wikilinks[mat.pattern()];
-            let starts_at = mat.start();
-            let ends_at = mat.end();
+            let Some((starts_at, ends_at)) = folded.original_span(mat.start(), mat.end()) else {
+                continue;
+            };
 
             if range_overlaps(&exclusion_zones, starts_at, ends_at) {
                 continue;
@@ -340,17 +570,18 @@ impl MarkdownFile {
                 continue;
             }
 
-            if self.should_create_match(line, starts_at, matched_text) {
-                let mut replacement = if matched_text == wikilink.target {
-                    wikilink.target.to_wikilink()
-                } else {
-                    wikilink.target.to_aliased_wikilink(matched_text)
-                };
+            // an inflected form of the note's own name - "OLED displays" in OLED Display.md
+            if config
+                .inflections()
+                .is_some_and(|inflections| self.is_own_name_variant(matched_text, inflections))
+            {
+                continue;
+            }
 
+            if self.should_create_match(line, starts_at, matched_text) {
                 let in_markdown_table = is_in_markdown_table(line, matched_text);
-                if in_markdown_table {
-                    replacement = replacement.replace('|', r"\|");
-                }
+                let replacement =
+                    back_populate_replacement(&wikilink.target, matched_text, in_markdown_table);
 
                 let relative_path =
                     obsidian_repository::format_relative_path(&self.path, config.obsidian_path());
@@ -363,6 +594,8 @@ impl MarkdownFile {
                     in_markdown_table,
                     relative_path,
                     replacement,
+                    resolved_by: None,
+                    target: wikilink.target.clone(),
                 });
             }
         }
@@ -404,6 +637,28 @@ impl MarkdownFile {
             exclusion_zones.push((mat.start(), mat.end()));
         }
 
+        exclusion_zones.extend(inline_exclusion_zones(line, config.skip_regions()));
+
+        // ignore_rendered_text is matched against the line as obsidian renders it - and
+        // text that renders as a simplify_wikilinks string is being un-linked so don't link it again
+        let rendered_text_regexes: Vec<&Regex> = [
+            config.ignore_rendered_text_regexes(),
+            config.simplify_wikilinks_regexes(),
+        ]
+        .into_iter()
+        .flatten()
+        .flatten()
+        .collect();
+
+        if !rendered_text_regexes.is_empty() {
+            let rendered = wikilink::render_wikilinks(line);
+            for regex in rendered_text_regexes {
+                for mat in regex.find_iter(&rendered.text) {
+                    exclusion_zones.push(rendered.source_span(mat.start(), mat.end()));
+                }
+            }
+        }
+
         // they need to be ordered!
         exclusion_zones.sort_by_key(|&(start, _)| start);
         exclusion_zones
@@ -412,7 +667,7 @@ impl MarkdownFile {
     fn should_create_match(&self, line: &str, absolute_start: usize, matched_text: &str) -> bool {
         // Check if this is the text's own page or matches any frontmatter aliases
         if let Some(stem) = self.path.file_stem().and_then(|s| s.to_str()) {
-            if stem.eq_ignore_ascii_case(matched_text) {
+            if utils::eq_fold_case(stem, matched_text) {
                 return false;
             }
 
@@ -421,7 +676,7 @@ impl MarkdownFile {
                 if let Some(aliases) = frontmatter.aliases() {
                     if aliases
                         .iter()
-                        .any(|alias| alias.eq_ignore_ascii_case(matched_text))
+                        .any(|alias| utils::eq_fold_case(alias, matched_text))
                     {
                         return false;
                     }
@@ -432,6 +687,16 @@ impl MarkdownFile {
         !wikilink::is_within_wikilink(line, absolute_start)
     }
 
+    fn is_own_name_variant(&self, matched_text: &str, inflections: &Inflections) -> bool {
+        let stem = self.path.file_stem().and_then(|stem| stem.to_str());
+        let aliases = self.frontmatter.as_ref().and_then(|fm| fm.aliases());
+
+        stem.into_iter()
+            .chain(aliases.into_iter().flatten().map(String::as_str))
+            .flat_map(|name| inflections.variants(name))
+            .any(|variant| utils::eq_fold_case(&variant, matched_text))
+    }
+
     pub fn has_ambiguous_matches(&self) -> bool {
         !self.matches.ambiguous.is_empty()
     }
@@ -439,44 +704,136 @@ impl MarkdownFile {
     pub fn has_unambiguous_matches(&self) -> bool {
         !self.matches.unambiguous.is_empty()
     }
+
+    pub fn has_simplify_wikilink_matches(&self) -> bool {
+        !self.simplify_wikilink_matches.is_empty()
+    }
+
+    /// the content hash of the note as it is now, including changes ok made
+    pub fn current_content_hash(&self) -> String {
+        get_content_hash(&self.frontmatter, &self.content)
+    }
 }
 
-fn get_date_validations(
+// on linux created() is the statx birth time - file systems that don't record one fall back to
+// the modified time as that's the earliest date known to be true
+fn file_system_dates(path: &PathBuf) -> Result<ReferenceDates, io::Error> {
+    let metadata = fs::metadata(path)?;
+    let modified = metadata
+        .modified()
+        .map(|t| t.into())
+        .unwrap_or_else(|_| Utc::now());
+
+    Ok(ReferenceDates {
+        created: metadata.created().map(|t| t.into()).unwrap_or(modified),
+        modified,
+    })
+}
+
+/// the dates from the configured date source - the file system unless a date store or git
+/// dates are in use
+///
+/// a created date ok recorded for the note wins over the birth time it couldn't set
+fn get_reference_dates(
     frontmatter: &Option<FrontMatter>,
     path: &PathBuf,
-    operational_timezone: &str,
-) -> Result<(DateValidation, DateValidation), io::Error> {
-    let metadata = fs::metadata(path)?;
+    content_hash: Option<&String>,
+    options: &MarkdownFileOptions,
+) -> Result<ReferenceDates, io::Error> {
+    let mut file_system = file_system_dates(path)?;
+    if let Some(created) = options
+        .created_dates
+        .as_ref()
+        .and_then(|created_dates| created_dates.get(path))
+    {
+        file_system.created = created;
+    }
+
+    let Some(content_hash) = content_hash else {
+        return Ok(file_system);
+    };
+
+    if let Some(git_dates) = &options.git_dates {
+        return Ok(git_dates.reference_dates(path, content_hash, file_system));
+    }
+
+    let Some(date_store) = &options.date_store else {
+        return Ok(file_system);
+    };
+
+    let parse = |date: Option<&String>| {
+        date.and_then(|date| {
+            options
+                .date_settings
+                .parse_date_time(date, &options.operational_timezone)
+        })
+    };
+    let fm = frontmatter.as_ref();
+
+    Ok(date_store.reference_dates(
+        path,
+        content_hash,
+        file_system,
+        parse(fm.and_then(|fm| fm.date_created())),
+        parse(fm.and_then(|fm| fm.date_modified())),
+    ))
+}
 
+/// hash of the note as the user wrote it - the content plus any frontmatter ok doesn't manage
+fn get_content_hash(frontmatter: &Option<FrontMatter>, content: &str) -> String {
+    let user_yaml = frontmatter
+        .as_ref()
+        .and_then(|fm| fm.user_yaml())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(user_yaml.as_bytes());
+    hasher.update(b"\0");
+    hasher.update(content.trim().as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// the content hash of a note's full text, computed the same way as when the note is read
+pub fn full_content_hash(full_content: &str, date_settings: &DateSettings) -> String {
+    match yaml_frontmatter::find_yaml_section(full_content) {
+        Ok(Some((yaml_section, after_yaml))) => get_content_hash(
+            &FrontMatter::from_markdown_yaml(yaml_section, date_settings).ok(),
+            after_yaml,
+        ),
+        _ => get_content_hash(&None, full_content),
+    }
+}
+
+fn get_date_validations(
+    frontmatter: &Option<FrontMatter>,
+    reference_dates: ReferenceDates,
+    operational_timezone: &str,
+    date_settings: &DateSettings,
+) -> (DateValidation, DateValidation) {
     let dates = [
         (
             frontmatter
                 .as_ref()
                 .and_then(|fm| fm.date_created().cloned()),
-            metadata
-                .created()
-                .map(|t| t.into())
-                .unwrap_or_else(|_| Utc::now()),
+            reference_dates.created,
         ),
         (
             frontmatter
                 .as_ref()
                 .and_then(|fm| fm.date_modified().cloned()),
-            metadata
-                .modified()
-                .map(|t| t.into())
-                .unwrap_or_else(|_| Utc::now()),
+            reference_dates.modified,
         ),
     ];
 
     // skip when the create date has a date_created_fix in place, we don't need to validate as it's moot
-    Ok(dates
+    dates
         .into_iter()
         .map(|(frontmatter_date, fs_date)| {
             let issue = get_date_validation_issue(
                 frontmatter_date.as_ref(),
                 &fs_date,
                 operational_timezone,
+                date_settings,
             );
             DateValidation {
                 frontmatter_date,
@@ -486,13 +843,14 @@ fn get_date_validations(
             }
         })
         .collect_tuple()
-        .unwrap())
+        .unwrap()
 }
 
 fn get_date_validation_issue(
     date_opt: Option<&String>,
     fs_date: &DateTime<Utc>,
     operational_timezone: &str,
+    date_settings: &DateSettings,
 ) -> Option<DateValidationIssue> {
     // Check if the date is missing
     let date_str = match date_opt {
@@ -500,22 +858,10 @@ fn get_date_validation_issue(
         None => return Some(DateValidationIssue::Missing),
     };
 
-    // Check if the date string is a valid wikilink
-    if !wikilink::is_wikilink(Some(date_str)) {
-        return Some(DateValidationIssue::InvalidWikilink);
-    }
-
-    let extracted_date = extract_date(date_str);
-
-    // Validate the extracted date format
-    if !is_valid_date(extracted_date) {
-        return Some(DateValidationIssue::InvalidDateFormat);
-    }
-
-    // Parse the frontmatter date string into a NaiveDate
-    let frontmatter_date = match NaiveDate::parse_from_str(extracted_date.trim(), "%Y-%m-%d") {
+    // Parse the frontmatter date in the configured format into a NaiveDate
+    let frontmatter_date = match date_settings.parse_date(date_str, operational_timezone) {
         Ok(date) => date,
-        Err(_) => return Some(DateValidationIssue::InvalidDateFormat),
+        Err(issue) => return Some(issue),
     };
 
     // Parse timezone string into a Tz
@@ -537,24 +883,6 @@ fn get_date_validation_issue(
     None
 }
 
-// Extracts the date string from a possible wikilink format
-fn extract_date(date_str: &str) -> &str {
-    let date_str = date_str.trim();
-    if wikilink::is_wikilink(Some(date_str)) {
-        date_str
-            .trim_start_matches(OPENING_WIKILINK)
-            .trim_end_matches(CLOSING_WIKILINK)
-            .trim()
-    } else {
-        date_str
-    }
-}
-
-// Validates if a string is a valid YYYY-MM-DD date
-fn is_valid_date(date_str: &str) -> bool {
-    NaiveDate::parse_from_str(date_str.trim(), "%Y-%m-%d").is_ok()
-}
-
 fn process_date_validations(
     frontmatter: &mut Option<FrontMatter>,
     created_validation: &DateValidation,
@@ -618,7 +946,7 @@ fn is_word_boundary(line: &str, starts_at: usize, ends_at: usize) -> bool {
     let after_chars = &line[ends_at..];
 
     // Check start boundary
-    let start_is_boundary = starts_at == 0 || before.map_or(true, |ch| !is_word_char(ch));
+    let start_is_boundary = starts_at == 0 || before.is_none_or(|ch| !is_word_char(ch));
 
     // Check end boundary
     // No need to check for possessives as they should be valid candidates for replacement
@@ -637,6 +965,25 @@ fn range_overlaps(ranges: &[(usize, usize)], start: usize, end: usize) -> bool {
     })
 }
 
+/// a pipe in a table would end the cell so it's escaped
+pub fn back_populate_replacement(
+    target: &str,
+    matched_text: &str,
+    in_markdown_table: bool,
+) -> String {
+    let replacement = if matched_text == target {
+        target.to_wikilink()
+    } else {
+        target.to_aliased_wikilink(matched_text)
+    };
+
+    if in_markdown_table {
+        replacement.replace('|', r"\|")
+    } else {
+        replacement
+    }
+}
+
 fn is_in_markdown_table(line: &str, matched_text: &str) -> bool {
     let trimmed = line.trim();
     trimmed.starts_with('|')
@@ -5,6 +5,7 @@ use crate::{ValidatedConfig, DEFAULT_TIMEZONE};
 
 use crate::test_utils;
 use crate::test_utils::TestFileBuilder;
+use crate::utils;
 use crate::validated_config::ValidatedConfigBuilder;
 use aho_corasick::AhoCorasick;
 use aho_corasick::{AhoCorasickBuilder, MatchKind};
@@ -15,10 +16,12 @@ use tempfile::TempDir;
 
 // Common helper function to build Aho-Corasick automaton from CompiledWikilinks
 pub fn build_aho_corasick(wikilinks: &[Wikilink]) -> AhoCorasick {
-    let patterns: Vec<&str> = wikilinks.iter().map(|w| w.display_text.as_str()).collect();
+    let patterns: Vec<String> = wikilinks
+        .iter()
+        .map(|w| utils::fold_case(&w.display_text))
+        .collect();
 
     AhoCorasickBuilder::new()
-        .ascii_case_insensitive(true)
         .match_kind(MatchKind::LeftmostLongest)
         .build(&patterns)
         .expect("Failed to build Aho-Corasick automaton")
@@ -58,6 +58,49 @@ fn get_case_sensitivity_test_cases() -> Vec<TestCase> {
             ],
             description: "Case handling in tables",
         },
+        TestCase {
+            content: "zürich and ZÜRICH and Zu\u{308}rich",
+            wikilink: Wikilink {
+                display_text: "Zürich".to_string(),
+                target: "Zürich".to_string(),
+            },
+            expected_matches: vec![
+                ("zürich", "[[Zürich|zürich]]"),
+                ("ZÜRICH", "[[Zürich|ZÜRICH]]"),
+                ("Zu\u{308}rich", "[[Zürich|Zu\u{308}rich]]"),
+            ],
+            description: "Unicode case folding and decomposed characters",
+        },
+        TestCase {
+            content: "éclair, москва and ΟΔΥΣΣΕΥΣ",
+            wikilink: Wikilink {
+                display_text: "Éclair".to_string(),
+                target: "Éclair".to_string(),
+            },
+            expected_matches: vec![("éclair", "[[Éclair|éclair]]")],
+            description: "Accented first letter",
+        },
+        TestCase {
+            content: "В Москве и в МОСКВЕ",
+            wikilink: Wikilink {
+                display_text: "Москве".to_string(),
+                target: "Москва".to_string(),
+            },
+            expected_matches: vec![
+                ("Москве", "[[Москва|Москве]]"),
+                ("МОСКВЕ", "[[Москва|МОСКВЕ]]"),
+            ],
+            description: "Cyrillic",
+        },
+        TestCase {
+            content: "the odyssey of ΟΔΥΣΣΕΥΣ",
+            wikilink: Wikilink {
+                display_text: "Οδυσσευς".to_string(),
+                target: "Οδυσσευς".to_string(),
+            },
+            expected_matches: vec![("ΟΔΥΣΣΕΥΣ", "[[Οδυσσευς|ΟΔΥΣΣΕΥΣ]]")],
+            description: "Greek final sigma",
+        },
     ]
 }
 
@@ -158,7 +201,7 @@ fn test_case_sensitivity_behavior() {
 
         // Create a custom wikilink and build AC automaton directly
         let wikilink = case.wikilink;
-        let ac = back_populate_tests::build_aho_corasick(&[wikilink.clone()]);
+        let ac = back_populate_tests::build_aho_corasick(std::slice::from_ref(&wikilink));
 
         let markdown_info =
             MarkdownFile::new(file_path.clone(), config.operational_timezone()).unwrap();
@@ -190,3 +233,55 @@ fn test_case_sensitivity_behavior() {
         }
     }
 }
+
+#[test]
+fn test_unicode_own_page_and_ambiguity() {
+    let (temp_dir, config, _) =
+        back_populate_tests::create_test_environment(false, None, Some(vec![]), None);
+
+    // macOS stores file names decomposed
+    TestFileBuilder::new()
+        .with_content("# Zürich\nzürich is on a lake")
+        .create(&temp_dir, "Zu\u{308}rich.md");
+    TestFileBuilder::new()
+        .with_content("# Éclair\néclair")
+        .with_aliases(vec!["ÉCLAIR".to_string()])
+        .create(&temp_dir, "Pastry.md");
+    TestFileBuilder::new()
+        .with_content("# Other\n[[Éclair]]")
+        .create(&temp_dir, "Other.md");
+    TestFileBuilder::new()
+        .with_content("# Trip\nZÜRICH and éclair")
+        .create(&temp_dir, "Trip.md");
+
+    let mut repository = ObsidianRepository::new(&config).unwrap();
+    repository.identify_ambiguous_matches();
+    let file = |name: &str| {
+        repository
+            .markdown_files
+            .iter()
+            .find(|f| f.path.ends_with(name))
+            .unwrap()
+    };
+
+    // a note's own name and aliases aren't linked
+    assert!(file("Zu\u{308}rich.md").matches.unambiguous.is_empty());
+    assert!(file("Pastry.md").matches.unambiguous.is_empty());
+
+    let trip = file("Trip.md");
+    let unambiguous: Vec<&str> = trip
+        .matches
+        .unambiguous
+        .iter()
+        .map(|m| m.found_text.as_str())
+        .collect();
+    assert_eq!(unambiguous, vec!["ZÜRICH"]);
+    // Éclair is both an alias of Pastry and a note of its own
+    let ambiguous: Vec<&str> = trip
+        .matches
+        .ambiguous
+        .iter()
+        .map(|m| m.found_text.as_str())
+        .collect();
+    assert_eq!(ambiguous, vec!["éclair"]);
+}
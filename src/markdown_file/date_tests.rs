@@ -5,7 +5,7 @@ use crate::test_utils;
 use crate::test_utils::TestFileBuilder;
 use crate::yaml_frontmatter::YamlFrontMatter;
 use crate::DEFAULT_TIMEZONE;
-use chrono::TimeZone;
+use chrono::{NaiveDate, TimeZone};
 use tempfile::TempDir;
 
 // into_iter() consumes the array and yields owned values
@@ -189,6 +189,7 @@ fn test_process_date_validations() {
                 case.date_created.as_ref(),
                 &case.file_system_create_date,
                 DEFAULT_TIMEZONE,
+                &DateSettings::default(),
             ),
             operational_timezone: DEFAULT_TIMEZONE.to_string(),
         };
@@ -200,6 +201,7 @@ fn test_process_date_validations() {
                 case.date_modified.as_ref(),
                 &case.file_system_mod_date,
                 DEFAULT_TIMEZONE,
+                &DateSettings::default(),
             ),
             operational_timezone: DEFAULT_TIMEZONE.to_string(),
         };
@@ -391,8 +393,12 @@ fn run_date_validation_test_cases(test_cases: Vec<DateValidationTestCase>, timez
             .create(&temp_dir, "test.md");
 
         let fm = create_frontmatter(&case.date_modified, &case.date_created);
-        let (created_validation, modified_validation) =
-            get_date_validations(&Some(fm), &file_path, timezone).unwrap();
+        let (created_validation, modified_validation) = get_date_validations(
+            &Some(fm),
+            file_system_dates(&file_path).unwrap(),
+            timezone,
+            &DateSettings::default(),
+        );
 
         test_utils::assert_test_case(
             created_validation.issue,
@@ -453,3 +459,44 @@ fn test_late_night_date_created_fix() {
         "Should have DateCreatedFixApplied reason"
     );
 }
+
+#[test]
+fn test_configured_date_properties_and_format() {
+    let temp_dir = TempDir::new().unwrap();
+    let modified = test_utils::eastern_midnight(2024, 1, 2);
+    let file_path = TestFileBuilder::new()
+        .with_custom_frontmatter(
+            "created: \"2024-01-01\"\nupdated: \"2024-01-01\"\ndate_modified: not ours".to_string(),
+        )
+        .with_fs_dates(test_utils::eastern_midnight(2024, 1, 1), modified)
+        .create(&temp_dir, "test.md");
+
+    let options = MarkdownFileOptions {
+        date_settings: DateSettings {
+            properties: crate::date_settings::DateProperties {
+                created: "created".to_string(),
+                created_fix: "created_fix".to_string(),
+                modified: "updated".to_string(),
+            },
+            wikilink: false,
+            ..DateSettings::default()
+        },
+        ..MarkdownFileOptions::new(DEFAULT_TIMEZONE)
+    };
+    let markdown_file = MarkdownFile::with_options(file_path.clone(), &options).unwrap();
+
+    assert_eq!(
+        markdown_file.date_validation_modified.issue,
+        Some(DateValidationIssue::FileSystemMismatch)
+    );
+    assert!(markdown_file
+        .persist_reasons
+        .iter()
+        .any(|reason| reason.describe(&options.date_settings.properties) == "updated updated"));
+
+    markdown_file.persist().unwrap();
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("updated: \"2024-01-02\""));
+    assert!(content.contains("date_modified: not ours"));
+}
@@ -1,4 +1,6 @@
 use crate::markdown_file::back_populate_tests;
+use crate::markdown_file::SkipRegion;
+use crate::validated_config::validated_config_tests;
 use crate::wikilink::{InvalidWikilink, InvalidWikilinkReason};
 
 #[test]
@@ -97,3 +99,153 @@ fn test_exclusion_zones_only_matches_current_line() {
         "Should not have exclusion zones for different line"
     );
 }
+
+#[test]
+fn test_exclusion_zones_with_ignore_rendered_text() {
+    let line = "[[Ed Barnes|Ed]]: music reco: Test Link and Test Link";
+    let (temp_dir, _, mut repository) = back_populate_tests::create_test_environment(
+        true,
+        None,
+        None,
+        Some("[[Ed Barnes|Ed]]: music reco: Test Link and Test Link"),
+    );
+
+    let config = validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+        builder
+            .apply_changes(true)
+            .ignore_rendered_text(Some(vec!["Ed: music reco: Test Link".to_string()]));
+    })
+    .unwrap();
+
+    let zones = repository.markdown_files[0].collect_exclusion_zones(line, &config);
+    assert_eq!(
+        zones,
+        vec![(0, 39)],
+        "rendered match should map back to the source text including the wikilink"
+    );
+
+    repository.find_all_back_populate_matches(&config);
+    repository.apply_replaceable_matches(config.operational_timezone());
+
+    assert_eq!(
+        repository.markdown_files[0].content,
+        "[[Ed Barnes|Ed]]: music reco: Test Link and [[Test Link]]"
+    );
+}
+
+const SKIP_REGIONS_CONTENT: &str = r#"Test Link, `Test Link`, ``a `Test Link` b``, %%Test Link%%, <b title="Test Link">Test Link</b>, $Test Link$
+%%
+Test Link
+%%
+$$
+Test Link
+$$
+<div>
+Test Link
+</div>
+
+~~~
+Test Link
+~~~
+````md
+```
+Test Link
+```
+````
+# Test Link
+> Test Link
+> [!note] Test Link
+> Test Link
+
+    Test Link
+- item
+
+    Test Link"#;
+
+fn back_populated_lines(skip_regions: Vec<SkipRegion>) -> Vec<(usize, usize)> {
+    let (temp_dir, _, mut repository) =
+        back_populate_tests::create_test_environment(false, None, None, Some(SKIP_REGIONS_CONTENT));
+    let config = validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+        builder.skip_regions(skip_regions);
+    })
+    .unwrap();
+
+    repository.find_all_back_populate_matches(&config);
+    let markdown_file = &repository.markdown_files[0];
+    let mut lines: Vec<(usize, usize)> = markdown_file
+        .matches
+        .unambiguous
+        .iter()
+        .map(|m| {
+            (
+                m.line_number - markdown_file.frontmatter_line_count,
+                m.position,
+            )
+        })
+        .collect();
+    lines.sort();
+    lines
+}
+
+#[test]
+fn test_skip_regions() {
+    // ~~~ and ```` fences are always skipped, as is the ``` inside the ```` one
+    let outside_regions = vec![(1, 0), (1, 81)];
+    let structural = vec![(20, 2), (21, 2), (22, 10), (23, 2)];
+    let indented_code = vec![(25, 4)];
+    let list_item = vec![(28, 4)];
+
+    assert_eq!(
+        back_populated_lines(SkipRegion::defaults()),
+        [
+            outside_regions.clone(),
+            structural.clone(),
+            list_item.clone()
+        ]
+        .concat()
+    );
+    assert_eq!(
+        back_populated_lines(Vec::new()),
+        [
+            vec![(1, 0), (1, 12), (1, 29), (1, 47), (1, 70), (1, 81), (1, 97)],
+            vec![(3, 0), (6, 0), (9, 0)],
+            structural,
+            indented_code,
+            list_item.clone(),
+        ]
+        .concat()
+    );
+    assert_eq!(
+        back_populated_lines(vec![
+            SkipRegion::Blockquotes,
+            SkipRegion::Callouts,
+            SkipRegion::Comments,
+            SkipRegion::Headings,
+            SkipRegion::Html,
+            SkipRegion::IndentedCode,
+            SkipRegion::InlineCode,
+            SkipRegion::Math,
+        ]),
+        [outside_regions, list_item].concat()
+    );
+}
+
+#[test]
+fn test_paragraph_starting_with_inline_tag() {
+    let (_, config, mut repository) = back_populate_tests::create_test_environment(
+        false,
+        None,
+        None,
+        Some("<mark>Test Link</mark> is important\nand Test Link is here"),
+    );
+
+    repository.find_all_back_populate_matches(&config);
+    let lines: Vec<usize> = repository.markdown_files[0]
+        .matches
+        .unambiguous
+        .iter()
+        .map(|m| m.line_number)
+        .collect();
+    // the text between the tags is linked and so is the rest of the paragraph
+    assert_eq!(lines.len(), 2, "{:?}", lines);
+}
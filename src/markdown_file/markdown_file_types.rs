@@ -1,34 +1,100 @@
+use crate::ambiguous_resolution::ResolutionRule;
+use crate::constants::*;
+use crate::date_settings::{DateProperties, DateSettings};
 use crate::frontmatter::FrontMatter;
 use crate::image_file::IncompatibilityReason;
 use crate::utils::EnumFilter;
 use crate::wikilink::{InvalidWikilink, Wikilink};
-use crate::{constants::*, markdown_file, wikilink};
-use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
+use sha2::{Digest, Sha256};
+use std::error::Error;
 use std::fmt;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use thiserror::Error;
 use vecollect::collection;
 
+/// what a markdown file looked like when it was read - persist refuses to overwrite a file
+/// that obsidian or a sync client changed in the meantime
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileSnapshot {
+    pub hash: String,
+    pub modified: SystemTime,
+}
+
+impl FileSnapshot {
+    /// modified has to be taken before the file is read so a change in between is caught
+    pub fn new(modified: SystemTime, content: &str) -> Self {
+        Self {
+            hash: content_hash(content),
+            modified,
+        }
+    }
+
+    /// an unchanged mtime is good enough - otherwise the content decides so a touched file
+    /// isn't treated as a conflict
+    pub fn is_current(&self, path: &Path) -> Result<bool, Box<dyn Error + Send + Sync>> {
+        if fs::metadata(path)?.modified()? == self.modified {
+            return Ok(true);
+        }
+
+        Ok(content_hash(&fs::read_to_string(path)?) == self.hash)
+    }
+}
+
+fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+#[derive(Error, Debug)]
+#[error("{} {}", PERSIST_CONFLICT, .path.display())]
+pub struct PersistConflict {
+    pub path: PathBuf,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PersistReason {
     DateCreatedUpdated { reason: DateValidationIssue },
     DateModifiedUpdated { reason: DateValidationIssue },
     DateCreatedFixApplied,
+    FrontmatterCreated,
+    FrontmatterRepaired,
     BackPopulated,
     ImageReferencesModified,
+    WikilinksSimplified,
+    SchemaFixed,
+    PropertiesUpdated,
+    PropertyTypesNormalized,
 }
 
-impl fmt::Display for PersistReason {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl PersistReason {
+    /// date reasons are named after the configured property
+    pub fn describe(&self, properties: &DateProperties) -> String {
         match self {
-            PersistReason::DateCreatedUpdated { .. } => write!(f, "date_created updated"),
-            PersistReason::DateModifiedUpdated { .. } => write!(f, "date_modified updated"),
-            PersistReason::DateCreatedFixApplied => write!(f, "date_created_fix applied"),
-            PersistReason::BackPopulated => write!(f, "back populated"),
-            PersistReason::ImageReferencesModified => write!(f, "image references updated"),
+            PersistReason::DateCreatedUpdated { .. } => format!("{} updated", properties.created),
+            PersistReason::DateModifiedUpdated { .. } => {
+                format!("{} updated", properties.modified)
+            }
+            PersistReason::DateCreatedFixApplied => format!("{} applied", properties.created_fix),
+            PersistReason::FrontmatterCreated => "frontmatter created".to_string(),
+            PersistReason::FrontmatterRepaired => "frontmatter repaired".to_string(),
+            PersistReason::BackPopulated => "back populated".to_string(),
+            PersistReason::ImageReferencesModified => "image references updated".to_string(),
+            PersistReason::WikilinksSimplified => "wikilinks simplified".to_string(),
+            PersistReason::SchemaFixed => "schema violations fixed".to_string(),
+            PersistReason::PropertiesUpdated => "properties updated".to_string(),
+            PersistReason::PropertyTypesNormalized => "property types normalized".to_string(),
         }
     }
 }
 
+impl fmt::Display for PersistReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.describe(&DateProperties::default()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum DateValidationIssue {
     Missing,
@@ -83,21 +149,16 @@ impl DateCreatedFixValidation {
         frontmatter: &Option<FrontMatter>,
         file_created_date: DateTime<Utc>,
         operational_timezone: &str,
+        date_settings: &DateSettings,
     ) -> Self {
         let fix_str = frontmatter
             .as_ref()
             .and_then(|fm| fm.date_created_fix().cloned());
 
         let parsed_date = fix_str.as_ref().and_then(|date_str| {
-            let date = if wikilink::is_wikilink(Some(date_str)) {
-                markdown_file::extract_date(date_str)
-            } else {
-                date_str.trim().trim_matches('"')
-            };
-
             // First parse the date string
-            NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
-                .ok()
+            date_settings
+                .parse_fix_date(date_str, operational_timezone)
                 .map(|naive_date| {
                     let tz: chrono_tz::Tz = operational_timezone.parse().unwrap_or(chrono_tz::UTC);
 
@@ -137,6 +198,7 @@ impl DateCreatedFixValidation {
 pub enum MatchType {
     BackPopulate,
     ImageReference,
+    SimplifyWikilink,
 }
 
 pub trait ReplaceableContent {
@@ -156,6 +218,9 @@ pub struct BackPopulateMatch {
     pub position: usize,
     pub relative_path: String,
     pub replacement: String,
+    /// set when the text has several targets and a rule picked this one
+    pub resolved_by: Option<ResolutionRule>,
+    pub target: String,
 }
 
 impl ReplaceableContent for BackPopulateMatch {
@@ -180,9 +245,45 @@ impl ReplaceableContent for BackPopulateMatch {
     }
 }
 
+// a wikilink that renders as one of the simplify_wikilinks strings so it is replaced with its display text
+#[derive(Clone, Debug, Default)]
+pub struct SimplifyWikilinkMatch {
+    pub display_text: String,
+    pub line_number: usize,
+    pub line_text: String,
+    pub position: usize,
+    pub relative_path: String,
+    pub rendered_text: String,
+    pub wikilink_text: String,
+}
+
+impl ReplaceableContent for SimplifyWikilinkMatch {
+    fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    fn get_replacement(&self) -> String {
+        self.display_text.clone()
+    }
+
+    fn matched_text(&self) -> String {
+        self.wikilink_text.clone()
+    }
+
+    fn match_type(&self) -> MatchType {
+        MatchType::SimplifyWikilink
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct BackPopulateMatches {
     pub ambiguous: Vec<BackPopulateMatch>,
+    /// left alone as link_density already links the target nearby
+    pub skipped: Vec<BackPopulateMatch>,
     pub unambiguous: Vec<BackPopulateMatch>,
 }
 
@@ -85,7 +85,7 @@ fn test_back_populate_persist_reason() -> Result<(), Box<dyn Error + Send + Sync
         .create(&temp_dir, "back_populate.md");
 
     let mut file_info = test_utils::get_test_markdown_file(file_path);
-    file_info.mark_as_back_populated(DEFAULT_TIMEZONE);
+    file_info.mark_edited(PersistReason::BackPopulated, DEFAULT_TIMEZONE);
 
     assert!(file_info
         .persist_reasons
@@ -105,7 +105,7 @@ fn test_image_references_persist_reason() -> Result<(), Box<dyn Error + Send + S
         .create(&temp_dir, "image_refs.md");
 
     let mut file_info = test_utils::get_test_markdown_file(file_path);
-    file_info.mark_image_reference_as_updated(DEFAULT_TIMEZONE);
+    file_info.mark_edited(PersistReason::ImageReferencesModified, DEFAULT_TIMEZONE);
 
     assert!(file_info
         .persist_reasons
@@ -132,10 +132,10 @@ fn test_multiple_persist_reasons() -> Result<(), Box<dyn Error + Send + Sync>> {
         }));
 
     // Add back populate reason
-    file_info.mark_as_back_populated(DEFAULT_TIMEZONE);
+    file_info.mark_edited(PersistReason::BackPopulated, DEFAULT_TIMEZONE);
 
     // Add image reference change
-    file_info.mark_image_reference_as_updated(DEFAULT_TIMEZONE);
+    file_info.mark_edited(PersistReason::ImageReferencesModified, DEFAULT_TIMEZONE);
 
     // Verify all reasons are present
     // the 3 reasons are DateCreatedUpdated { reason: Missing }, BackPopulated, ImageReferencesModified
@@ -204,6 +204,35 @@ fn test_persist_frontmatter_preserves_format() -> Result<(), Box<dyn Error + Sen
     Ok(())
 }
 
+#[test]
+fn test_persist_only_rewrites_changed_frontmatter_keys() -> Result<(), Box<dyn Error + Send + Sync>>
+{
+    let temp_dir = TempDir::new()?;
+    let file_path = temp_dir.path().join("test.md");
+    fs::write(
+        &file_path,
+        "---\n# written by hand\ntitle: 'My Note'\ntags:\n  - zebra\n  - apple\ndate_modified: \"[[2024-01-01]]\"\n---\nbody",
+    )?;
+
+    let mut file_info = test_utils::get_test_markdown_file(file_path.clone());
+    let fm = file_info.frontmatter.as_mut().unwrap();
+    fm.set_date_modified(test_utils::eastern_midnight(2024, 1, 2), DEFAULT_TIMEZONE);
+    // the missing created date is filled in from the file system when the file is read
+    let date_created = fm.date_created().unwrap().clone();
+
+    file_info.persist()?;
+
+    assert_eq!(
+        fs::read_to_string(&file_path)?,
+        format!(
+            "---\n# written by hand\ntitle: 'My Note'\ntags:\n  - zebra\n  - apple\ndate_modified: \"[[2024-01-02]]\"\ndate_created: \"{}\"\n---\nbody",
+            date_created
+        )
+    );
+
+    Ok(())
+}
+
 #[test]
 #[cfg_attr(target_os = "linux", ignore)]
 fn test_persist_with_created_and_modified_dates() -> Result<(), Box<dyn Error + Send + Sync>> {
@@ -312,3 +341,137 @@ fn test_persist_preserves_file_content() -> Result<(), Box<dyn Error + Send + Sy
 
     Ok(())
 }
+
+fn create_file_to_persist(temp_dir: &TempDir, name: &str) -> MarkdownFile {
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    let file_path = TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_fs_dates(test_date, test_date)
+        .with_content("original".to_string())
+        .create(temp_dir, name);
+
+    let mut file_info = test_utils::get_test_markdown_file(file_path);
+    file_info.content = "updated".to_string();
+    file_info.mark_edited(PersistReason::BackPopulated, DEFAULT_TIMEZONE);
+    file_info
+}
+
+#[test]
+fn test_persist_refuses_file_changed_since_read() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let temp_dir = TempDir::new()?;
+    let file_info = create_file_to_persist(&temp_dir, "conflict.md");
+
+    // obsidian saves an edit after ok read the file
+    fs::write(&file_info.path, "edited in obsidian")?;
+    filetime::set_file_mtime(&file_info.path, FileTime::now())?;
+
+    let error = file_info.persist().unwrap_err();
+    let conflict = error.downcast_ref::<PersistConflict>().unwrap();
+    assert_eq!(conflict.path, file_info.path);
+    assert_eq!(fs::read_to_string(&file_info.path)?, "edited in obsidian");
+
+    Ok(())
+}
+
+#[test]
+fn test_persist_allows_touched_file() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let temp_dir = TempDir::new()?;
+    let file_info = create_file_to_persist(&temp_dir, "touched.md");
+
+    // same content with a new mtime isn't a conflict
+    filetime::set_file_mtime(&file_info.path, FileTime::now())?;
+
+    file_info.persist()?;
+    assert!(fs::read_to_string(&file_info.path)?.ends_with("updated"));
+
+    Ok(())
+}
+
+#[test]
+fn test_persist_leaves_no_temp_file() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let temp_dir = TempDir::new()?;
+    let file_info = create_file_to_persist(&temp_dir, "atomic.md");
+
+    file_info.persist()?;
+
+    let file_names: Vec<String> = fs::read_dir(temp_dir.path())?
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert_eq!(file_names, vec!["atomic.md"]);
+
+    Ok(())
+}
+
+#[test]
+fn test_create_frontmatter() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let temp_dir = TempDir::new()?;
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("# Target".to_string())
+        .create(&temp_dir, "Target.md");
+    let file_path = TestFileBuilder::new()
+        .with_content("mentions Target".to_string())
+        .with_fs_dates(test_date, test_date)
+        .create(&temp_dir, "no_frontmatter.md");
+    let properties: Mapping = serde_yaml::from_str("tags: [inbox]")?;
+    let config = |create_frontmatter: Option<Mapping>| {
+        crate::validated_config::validated_config_tests::get_test_validated_config_result(
+            &temp_dir,
+            |builder| {
+                builder.create_frontmatter(create_frontmatter);
+            },
+        )
+        .unwrap()
+    };
+    let note = |repository: &crate::obsidian_repository::ObsidianRepository| {
+        repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path == file_path)
+            .cloned()
+            .unwrap()
+    };
+
+    // without create_frontmatter the match is reported but the note is left alone
+    let repository = crate::obsidian_repository::ObsidianRepository::new(&config(None))?;
+    let file = note(&repository);
+    assert_eq!(file.frontmatter_error, Some(YamlFrontMatterError::Missing));
+    assert_eq!(file.matches.unambiguous.len(), 1);
+    assert!(file.persist_reasons.is_empty());
+    assert_eq!(file.content.trim(), "mentions Target");
+
+    let mut repository =
+        crate::obsidian_repository::ObsidianRepository::new(&config(Some(properties)))?;
+    let file = note(&repository);
+    assert!(file.frontmatter_error.is_none());
+    assert_eq!(
+        file.persist_reasons,
+        vec![
+            PersistReason::FrontmatterCreated,
+            PersistReason::DateCreatedUpdated {
+                reason: DateValidationIssue::Missing
+            },
+            // back populating sets date_modified to now
+            PersistReason::BackPopulated,
+        ]
+    );
+
+    // only macos can set the birth time so date_created is whatever the file system has
+    let date_created = DateSettings::default().format_date(
+        file.date_validation_created.file_system_date,
+        DEFAULT_TIMEZONE,
+    );
+    repository.persist()?;
+    let content = fs::read_to_string(&file_path)?;
+    assert!(content.starts_with("---\n"), "{}", content);
+    assert!(
+        content.contains(&format!("date_created: '{}'", date_created)),
+        "{}",
+        content
+    );
+    assert!(content.contains("tags:\n- inbox"), "{}", content);
+    assert!(content.ends_with("---\nmentions [[Target]]"), "{}", content);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::{ValidatedConfig, ValidatedConfigBuilder};
+use tempfile::TempDir;
+
+fn get_simplify_config(temp_dir: &TempDir, simplify_wikilinks: Vec<&str>) -> ValidatedConfig {
+    ValidatedConfigBuilder::default()
+        .obsidian_path(temp_dir.path().to_path_buf())
+        .output_folder(temp_dir.path().join("output"))
+        .simplify_wikilinks(Some(
+            simplify_wikilinks.into_iter().map(String::from).collect(),
+        ))
+        .build()
+        .unwrap()
+}
+
+fn create_note(temp_dir: &TempDir, file_name: &str, content: &str) {
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content(content.to_string())
+        .create(temp_dir, file_name);
+}
+
+fn get_content(repository: &ObsidianRepository, file_name: &str) -> String {
+    repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with(file_name))
+        .map(|file| file.content.trim_end().to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_simplify_wikilinks() {
+    struct TestCase {
+        name: &'static str,
+        simplify: Vec<&'static str>,
+        content: &'static str,
+        expected_matches: usize,
+        expected_content: &'static str,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            name: "aliased wikilink followed by punctuation",
+            simplify: vec!["Ed:"],
+            content: "[[Ed Barnes|Ed]]: music reco",
+            expected_matches: 1,
+            expected_content: "Ed: music reco",
+        },
+        TestCase {
+            name: "plain wikilink",
+            simplify: vec!["Bob Rock"],
+            content: "produced by [[Bob Rock]] in 1991",
+            expected_matches: 1,
+            expected_content: "produced by Bob Rock in 1991",
+        },
+        TestCase {
+            name: "case insensitive",
+            simplify: vec!["ed:"],
+            content: "[[Ed Barnes|Ed]]: music reco",
+            expected_matches: 1,
+            expected_content: "Ed: music reco",
+        },
+        TestCase {
+            name: "rendered text does not match",
+            simplify: vec!["Ed:"],
+            content: "[[Ed Barnes|Ed]] said so",
+            expected_matches: 0,
+            expected_content: "[[Ed Barnes|Ed]] said so",
+        },
+        TestCase {
+            name: "partial word does not match",
+            simplify: vec!["Bob Rock"],
+            content: "[[Bob Rockwell]] said so",
+            expected_matches: 0,
+            expected_content: "[[Bob Rockwell]] said so",
+        },
+        TestCase {
+            name: "code blocks are skipped",
+            simplify: vec!["Ed:"],
+            content: "```\n[[Ed Barnes|Ed]]: in code\n```",
+            expected_matches: 0,
+            expected_content: "```\n[[Ed Barnes|Ed]]: in code\n```",
+        },
+    ];
+
+    for case in test_cases {
+        let temp_dir = TempDir::new().unwrap();
+        let config = get_simplify_config(&temp_dir, case.simplify);
+        create_note(&temp_dir, "note.md", case.content);
+
+        let repository = ObsidianRepository::new(&config).unwrap();
+        let note = repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path.ends_with("note.md"))
+            .unwrap();
+
+        assert_eq!(
+            note.simplify_wikilink_matches.len(),
+            case.expected_matches,
+            "Failed test: {}",
+            case.name
+        );
+        assert_eq!(
+            get_content(&repository, "note.md"),
+            case.expected_content,
+            "Failed test: {}",
+            case.name
+        );
+    }
+}
+
+#[test]
+fn test_simplify_wikilinks_prevents_back_populate() {
+    let temp_dir = TempDir::new().unwrap();
+    let config = get_simplify_config(&temp_dir, vec!["Ed:"]);
+
+    create_note(&temp_dir, "Ed Barnes.md", "a person");
+    create_note(
+        &temp_dir,
+        "note.md",
+        "[[Ed Barnes|Ed]]: music reco\nEd: another reco\nEd wrote this",
+    );
+
+    let repository = ObsidianRepository::new(&config).unwrap();
+
+    assert_eq!(
+        get_content(&repository, "note.md"),
+        "Ed: music reco\nEd: another reco\n[[Ed Barnes|Ed]] wrote this",
+        "text rendering as 'Ed:' should not be back populated"
+    );
+}
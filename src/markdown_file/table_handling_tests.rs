@@ -37,6 +37,8 @@ fn test_back_populate_content() {
             replacement: "[[Test Link\\|Another Name]]".into(),
             position: 1,
             in_markdown_table: true,
+            resolved_by: None,
+            target: "Test Link".into(),
         }],
         "Table content replacement",
     )];
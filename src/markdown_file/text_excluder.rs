@@ -1,28 +1,72 @@
+use crate::constants::*;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::fmt;
+use std::str::FromStr;
+
+lazy_static! {
+    // commonmark html blocks - a comment, a block level tag, or a line that's only a tag. a
+    // line that starts with an inline tag like <mark> is a paragraph
+    static ref HTML_BLOCK_REGEX: Regex = Regex::new(concat!(
+        r"(?i)^(?:<!--",
+        r"|</?(?:address|article|aside|base|basefont|blockquote|body|caption|center|col|colgroup",
+        r"|dd|details|dialog|dir|div|dl|dt|fieldset|figcaption|figure|footer|form|frame",
+        r"|frameset|h[1-6]|head|header|hr|html|iframe|legend|li|link|main|menu|menuitem|nav",
+        r"|noframes|ol|optgroup|option|p|param|pre|script|search|section|style|summary|table",
+        r"|tbody|td|textarea|tfoot|th|thead|title|tr|track|ul)(?:\s|/?>|$)",
+        r"|</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>\s*$)"
+    ))
+    .unwrap();
+    static ref HTML_TAG_REGEX: Regex =
+        Regex::new(r"<!--.*?-->|</?[A-Za-z][A-Za-z0-9-]*(?:\s[^<>]*)?/?>").unwrap();
+    static ref INLINE_COMMENT_REGEX: Regex = Regex::new(r"%%.*?%%").unwrap();
+    // obsidian only renders $...$ as math when there's no space just inside the dollars
+    static ref INLINE_MATH_REGEX: Regex =
+        Regex::new(r"\$\$.*?\$\$|\$[^\s$](?:[^$]*[^\s$])?\$").unwrap();
+    static ref LIST_ITEM_REGEX: Regex = Regex::new(r"^(?:[-*+]|\d+[.)])(?:\s|$)").unwrap();
+}
+
 #[derive(Debug, PartialEq)]
 pub enum CodeBlockDelimiter {
-    Backtick,
-    TripleBacktick,
+    /// ``` or ~~~ - closed by the same character repeated at least as many times
+    Fence(char, usize),
+    /// %% that doesn't close on the same line
+    Comment,
+    /// $$ that doesn't close on the same line
+    Math,
 }
 
 impl TryFrom<&str> for CodeBlockDelimiter {
     type Error = (); // Using unit type for error since we don't care if it fails
 
     fn try_from(s: &str) -> Result<Self, Self::Error> {
-        if s.trim().starts_with("```") {
-            Ok(CodeBlockDelimiter::TripleBacktick)
+        let trimmed = s.trim();
+        for fence_char in ['`', '~'] {
+            let count = trimmed.chars().take_while(|&c| c == fence_char).count();
+            if count >= 3 {
+                return Ok(CodeBlockDelimiter::Fence(fence_char, count));
+            }
+        }
+
+        // an even count opens and closes on the same line - that's inline
+        if trimmed.matches("%%").count() % 2 == 1 {
+            Ok(CodeBlockDelimiter::Comment)
+        } else if trimmed.matches("$$").count() % 2 == 1 {
+            Ok(CodeBlockDelimiter::Math)
         } else {
             Err(())
         }
     }
 }
 
-impl TryFrom<char> for CodeBlockDelimiter {
-    type Error = ();
-
-    fn try_from(c: char) -> Result<Self, Self::Error> {
-        match c {
-            '`' => Ok(CodeBlockDelimiter::Backtick),
-            _ => Err(()),
+impl CodeBlockDelimiter {
+    fn closes(&self, opening: &CodeBlockDelimiter) -> bool {
+        match (self, opening) {
+            (
+                CodeBlockDelimiter::Fence(fence_char, count),
+                CodeBlockDelimiter::Fence(opening_char, opening_count),
+            ) => fence_char == opening_char && count >= opening_count,
+            _ => self == opening,
         }
     }
 }
@@ -35,20 +79,30 @@ enum BlockLocation {
 }
 
 pub trait BlockDelimiter {
-    fn delimiter_type(&self) -> CodeBlockDelimiter;
+    fn opens(&self, delimiter: &CodeBlockDelimiter) -> bool;
 }
 
-pub struct TripleBacktickDelimiter;
-impl BlockDelimiter for TripleBacktickDelimiter {
-    fn delimiter_type(&self) -> CodeBlockDelimiter {
-        CodeBlockDelimiter::TripleBacktick
+#[derive(Debug)]
+pub struct FenceDelimiter;
+impl BlockDelimiter for FenceDelimiter {
+    fn opens(&self, delimiter: &CodeBlockDelimiter) -> bool {
+        matches!(delimiter, CodeBlockDelimiter::Fence(..))
     }
 }
 
-pub struct SingleBacktickDelimiter;
-impl BlockDelimiter for SingleBacktickDelimiter {
-    fn delimiter_type(&self) -> CodeBlockDelimiter {
-        CodeBlockDelimiter::Backtick
+#[derive(Debug)]
+pub struct CommentDelimiter;
+impl BlockDelimiter for CommentDelimiter {
+    fn opens(&self, delimiter: &CodeBlockDelimiter) -> bool {
+        *delimiter == CodeBlockDelimiter::Comment
+    }
+}
+
+#[derive(Debug)]
+pub struct MathDelimiter;
+impl BlockDelimiter for MathDelimiter {
+    fn opens(&self, delimiter: &CodeBlockDelimiter) -> bool {
+        *delimiter == CodeBlockDelimiter::Math
     }
 }
 
@@ -56,6 +110,7 @@ impl BlockDelimiter for SingleBacktickDelimiter {
 pub struct BlockTracker<D: BlockDelimiter> {
     location: BlockLocation,
     delimiter: D,
+    opening: Option<CodeBlockDelimiter>,
 }
 
 impl<D: BlockDelimiter> BlockTracker<D> {
@@ -63,6 +118,7 @@ impl<D: BlockDelimiter> BlockTracker<D> {
         Self {
             location: BlockLocation::Outside,
             delimiter,
+            opening: None,
         }
     }
 
@@ -70,21 +126,24 @@ impl<D: BlockDelimiter> BlockTracker<D> {
     /// encounter a delimiter, we go back to inside - this is intentional for the case
     /// where another code block is opened up right after the last one - it's possible in markdown
     /// so we don't treat this as a "nested" case we treat it as an opening of a code block
-    pub fn update<T>(&mut self, content: T)
-    where
-        T: TryInto<CodeBlockDelimiter>,
-    {
-        if let Ok(delimiter) = content.try_into() {
-            if delimiter == self.delimiter.delimiter_type() {
-                match self.location {
-                    BlockLocation::Inside => {
+    pub fn update(&mut self, line: &str) {
+        if let Ok(delimiter) = CodeBlockDelimiter::try_from(line) {
+            match self.location {
+                BlockLocation::Inside => {
+                    if self
+                        .opening
+                        .as_ref()
+                        .is_some_and(|opening| delimiter.closes(opening))
+                    {
                         self.location = BlockLocation::ClosingDelimiterFound;
                     }
-                    BlockLocation::Outside => {
-                        self.location = BlockLocation::Inside;
-                    }
-                    BlockLocation::ClosingDelimiterFound => {
+                }
+                BlockLocation::Outside | BlockLocation::ClosingDelimiterFound => {
+                    if self.delimiter.opens(&delimiter) {
                         self.location = BlockLocation::Inside;
+                        self.opening = Some(delimiter);
+                    } else {
+                        self.location = BlockLocation::Outside;
                     }
                 }
             }
@@ -94,7 +153,7 @@ impl<D: BlockDelimiter> BlockTracker<D> {
     }
 
     // we want to be clear that the ClosingDelimiterFound should also be skipped
-    // if we didn't skip it then the closing TripleBacktickDelimiter ``` would be
+    // if we didn't skip it then the closing fence ``` would be
     // considered "outside" and it would then be prased by the
     // character iterator and would treat this as an open/close/open of a code block
     pub fn is_in_code_block(&self) -> bool {
@@ -103,25 +162,296 @@ impl<D: BlockDelimiter> BlockTracker<D> {
             BlockLocation::Inside | BlockLocation::ClosingDelimiterFound
         )
     }
+}
 
-    pub fn is_inside(&self) -> bool {
-        self.location == BlockLocation::Inside
+pub type CodeBlockExcluder = BlockTracker<FenceDelimiter>;
+pub type CommentExcluder = BlockTracker<CommentDelimiter>;
+pub type MathBlockExcluder = BlockTracker<MathDelimiter>;
+
+impl CodeBlockExcluder {
+    pub fn new() -> Self {
+        Self::new_with_delimiter(FenceDelimiter)
     }
 }
 
-pub type CodeBlockExcluder = BlockTracker<TripleBacktickDelimiter>;
-pub type InlineCodeExcluder = BlockTracker<SingleBacktickDelimiter>;
+impl CommentExcluder {
+    pub fn new() -> Self {
+        Self::new_with_delimiter(CommentDelimiter)
+    }
+}
 
-impl CodeBlockExcluder {
+impl MathBlockExcluder {
     pub fn new() -> Self {
-        Self::new_with_delimiter(TripleBacktickDelimiter)
+        Self::new_with_delimiter(MathDelimiter)
     }
 }
 
+/// inline code opens with a run of backticks and closes with a run of the same length - so
+/// ``a `b` c`` is one span. fed a line one character at a time
+#[derive(Debug, Default)]
+pub struct InlineCodeExcluder {
+    /// length of the run that opened the span - 0 when outside one
+    opening: usize,
+    /// whether the opening run has ended
+    opened: bool,
+    /// backticks in a row since the span opened
+    closing: usize,
+}
+
 impl InlineCodeExcluder {
     pub fn new() -> Self {
-        Self::new_with_delimiter(SingleBacktickDelimiter)
+        Self::default()
+    }
+
+    pub fn update(&mut self, ch: char) {
+        if self.opening == 0 {
+            if ch == '`' {
+                *self = Self {
+                    opening: 1,
+                    ..Self::default()
+                };
+            }
+        } else if !self.opened {
+            if ch == '`' {
+                self.opening += 1;
+            } else {
+                self.opened = true;
+            }
+        } else if ch == '`' {
+            self.closing += 1;
+        } else if self.closing == self.opening {
+            *self = Self::default();
+        } else {
+            self.closing = 0;
+        }
     }
+
+    /// the backticks on either side are part of the span
+    pub fn is_in_code_block(&self) -> bool {
+        self.opening > 0
+    }
+
+    /// true while the span is still waiting for its closing run
+    pub fn is_inside(&self) -> bool {
+        self.opening > 0 && !(self.opened && self.closing == self.opening)
+    }
+}
+
+/// byte spans of the inline code in a line - an unclosed span runs to the end of it
+pub fn inline_code_spans(line: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut inline_code = InlineCodeExcluder::new();
+    let mut start = None;
+
+    for (idx, ch) in line.char_indices() {
+        inline_code.update(ch);
+        match (inline_code.is_in_code_block(), start) {
+            (true, None) => start = Some(idx),
+            (false, Some(span_start)) => {
+                spans.push((span_start, idx));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(span_start) = start {
+        spans.push((span_start, line.len()));
+    }
+
+    spans
+}
+
+/// `# heading` but not `#tag`
+pub fn is_heading(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    let level = trimmed.chars().take_while(|&c| c == '#').count();
+    (1..=6).contains(&level)
+        && trimmed[level..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
+}
+
+/// the markdown back population leaves alone - fenced code always is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SkipRegion {
+    /// `>` lines that aren't part of a callout
+    Blockquotes,
+    /// `> [!note]` and the `>` lines after it
+    Callouts,
+    /// obsidian `%% comments %%`
+    Comments,
+    Headings,
+    /// html blocks up to the next blank line and inline tags
+    Html,
+    /// lines indented four spaces after a blank line - list items excepted
+    IndentedCode,
+    InlineCode,
+    /// `$inline$` and `$$block$$` math
+    Math,
+}
+
+impl SkipRegion {
+    /// linking any of these breaks what obsidian renders - the rest are prose
+    pub fn defaults() -> Vec<SkipRegion> {
+        vec![
+            SkipRegion::Comments,
+            SkipRegion::Html,
+            SkipRegion::IndentedCode,
+            SkipRegion::InlineCode,
+            SkipRegion::Math,
+        ]
+    }
+}
+
+impl FromStr for SkipRegion {
+    type Err = String;
+
+    fn from_str(skip_region: &str) -> Result<Self, Self::Err> {
+        match skip_region {
+            SKIP_REGION_BLOCKQUOTES => Ok(SkipRegion::Blockquotes),
+            SKIP_REGION_CALLOUTS => Ok(SkipRegion::Callouts),
+            SKIP_REGION_COMMENTS => Ok(SkipRegion::Comments),
+            SKIP_REGION_HEADINGS => Ok(SkipRegion::Headings),
+            SKIP_REGION_HTML => Ok(SkipRegion::Html),
+            SKIP_REGION_INDENTED_CODE => Ok(SkipRegion::IndentedCode),
+            SKIP_REGION_INLINE_CODE => Ok(SkipRegion::InlineCode),
+            SKIP_REGION_MATH => Ok(SkipRegion::Math),
+            unknown => Err(format!("unknown skip region: {}", unknown)),
+        }
+    }
+}
+
+impl fmt::Display for SkipRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            SkipRegion::Blockquotes => SKIP_REGION_BLOCKQUOTES,
+            SkipRegion::Callouts => SKIP_REGION_CALLOUTS,
+            SkipRegion::Comments => SKIP_REGION_COMMENTS,
+            SkipRegion::Headings => SKIP_REGION_HEADINGS,
+            SkipRegion::Html => SKIP_REGION_HTML,
+            SkipRegion::IndentedCode => SKIP_REGION_INDENTED_CODE,
+            SkipRegion::InlineCode => SKIP_REGION_INLINE_CODE,
+            SkipRegion::Math => SKIP_REGION_MATH,
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// tracks fenced code and the skipped regions that are whole lines - fed every line of the
+/// content in order, blank ones included
+#[derive(Debug)]
+pub struct RegionExcluder<'a> {
+    regions: &'a [SkipRegion],
+    code_block_tracker: CodeBlockExcluder,
+    comment_tracker: CommentExcluder,
+    math_tracker: MathBlockExcluder,
+    in_html_block: bool,
+    in_callout: bool,
+    in_list: bool,
+    in_indented_code: bool,
+    previous_blank: bool,
+    excluded: bool,
+}
+
+impl<'a> RegionExcluder<'a> {
+    pub fn new(regions: &'a [SkipRegion]) -> Self {
+        Self {
+            regions,
+            code_block_tracker: CodeBlockExcluder::new(),
+            comment_tracker: CommentExcluder::new(),
+            math_tracker: MathBlockExcluder::new(),
+            in_html_block: false,
+            in_callout: false,
+            in_list: false,
+            in_indented_code: false,
+            previous_blank: true,
+            excluded: false,
+        }
+    }
+
+    fn skips(&self, region: SkipRegion) -> bool {
+        self.regions.contains(&region)
+    }
+
+    pub fn update(&mut self, line: &str) {
+        self.code_block_tracker.update(line);
+        self.excluded = self.code_block_tracker.is_in_code_block();
+        if self.excluded {
+            return;
+        }
+
+        let trimmed = line.trim_start();
+        let blank = trimmed.is_empty();
+
+        if self.skips(SkipRegion::Comments) {
+            self.comment_tracker.update(line);
+            self.excluded |= self.comment_tracker.is_in_code_block();
+        }
+
+        if self.skips(SkipRegion::Math) {
+            self.math_tracker.update(line);
+            self.excluded |= self.math_tracker.is_in_code_block();
+        }
+
+        self.in_html_block = if self.in_html_block {
+            !blank
+        } else {
+            HTML_BLOCK_REGEX.is_match(trimmed)
+        };
+        self.excluded |= self.skips(SkipRegion::Html) && self.in_html_block;
+
+        let quoted = trimmed.starts_with('>');
+        self.in_callout =
+            quoted && (self.in_callout || trimmed[1..].trim_start().starts_with("[!"));
+        self.excluded |= if self.in_callout {
+            self.skips(SkipRegion::Callouts)
+        } else {
+            quoted && self.skips(SkipRegion::Blockquotes)
+        };
+
+        self.excluded |= self.skips(SkipRegion::Headings) && is_heading(line);
+
+        // an indented line in a list continues the item rather than starting code
+        let indented = line.starts_with("    ") || line.starts_with('\t');
+        if !blank && !indented {
+            self.in_list = LIST_ITEM_REGEX.is_match(trimmed);
+        }
+        if !blank {
+            self.in_indented_code =
+                indented && !self.in_list && (self.previous_blank || self.in_indented_code);
+        }
+        self.excluded |= self.skips(SkipRegion::IndentedCode) && self.in_indented_code;
+
+        self.previous_blank = blank;
+    }
+
+    /// true when back population skips the whole of the last line
+    pub fn is_excluded(&self) -> bool {
+        self.excluded
+    }
+}
+
+/// byte spans of the skipped regions within a line - inline code, comments, math and html tags
+pub fn inline_exclusion_zones(line: &str, regions: &[SkipRegion]) -> Vec<(usize, usize)> {
+    let mut zones = Vec::new();
+
+    if regions.contains(&SkipRegion::InlineCode) {
+        zones.extend(inline_code_spans(line));
+    }
+
+    let regexes = [
+        (SkipRegion::Comments, &*INLINE_COMMENT_REGEX),
+        (SkipRegion::Html, &*HTML_TAG_REGEX),
+        (SkipRegion::Math, &*INLINE_MATH_REGEX),
+    ];
+    for (region, regex) in regexes {
+        if regions.contains(&region) {
+            zones.extend(regex.find_iter(line).map(|mat| (mat.start(), mat.end())));
+        }
+    }
+
+    zones
 }
 
 #[test]
@@ -197,3 +527,141 @@ fn test_inline_code_tracking() {
         "Should not skip regular text after an inline code block"
     );
 }
+
+#[test]
+fn test_fence_tracking() {
+    let lines = [
+        ("````md", true),
+        ("```", true),
+        ("not closed by the shorter fence", true),
+        ("~~~", true),
+        ("````", true),
+        ("text", false),
+        ("~~~", true),
+        ("```", true),
+        ("~~~~", true),
+        ("text", false),
+    ];
+
+    let mut tracker = CodeBlockExcluder::new();
+    for (line, expected) in lines {
+        tracker.update(line);
+        assert_eq!(tracker.is_in_code_block(), expected, "Failed for: {}", line);
+    }
+}
+
+#[test]
+fn test_multiple_backtick_inline_code() {
+    let test_cases = [
+        ("``a `b` c`` d", vec![(0, 11)]),
+        ("`a` and ``b``", vec![(0, 3), (8, 13)]),
+        ("`a`` b` c", vec![(0, 7)]),
+        ("``unclosed` x", vec![(0, 13)]),
+        ("no code", vec![]),
+    ];
+
+    for (line, expected) in test_cases {
+        assert_eq!(inline_code_spans(line), expected, "Failed for: {}", line);
+    }
+
+    let mut tracker = InlineCodeExcluder::new();
+    "``a`".chars().for_each(|ch| tracker.update(ch));
+    assert!(tracker.is_inside(), "a single backtick doesn't close ``");
+    "` ".chars().for_each(|ch| tracker.update(ch));
+    assert!(!tracker.is_inside());
+}
+
+#[test]
+fn test_region_excluder() {
+    let all_regions = [
+        SkipRegion::Blockquotes,
+        SkipRegion::Callouts,
+        SkipRegion::Comments,
+        SkipRegion::Headings,
+        SkipRegion::Html,
+        SkipRegion::IndentedCode,
+        SkipRegion::InlineCode,
+        SkipRegion::Math,
+    ];
+    let lines = [
+        ("text", false),
+        ("%% a comment", true),
+        ("over lines %%", true),
+        ("$$", true),
+        ("x^2", true),
+        ("$$", true),
+        ("<div>", true),
+        ("html", true),
+        ("", false),
+        ("# heading", true),
+        ("> quote", true),
+        ("> [!note] callout", true),
+        ("> more callout", true),
+        ("", false),
+        ("    indented code", true),
+        ("- list item", false),
+        ("    continues the item", false),
+        ("text", false),
+    ];
+
+    let mut excluder = RegionExcluder::new(&all_regions);
+    let mut no_regions = RegionExcluder::new(&[]);
+    for (line, expected) in lines {
+        excluder.update(line);
+        no_regions.update(line);
+        assert_eq!(excluder.is_excluded(), expected, "Failed for: {}", line);
+        assert!(!no_regions.is_excluded(), "Failed for: {}", line);
+    }
+
+    // blockquotes and callouts are told apart
+    let mut excluder = RegionExcluder::new(&[SkipRegion::Callouts]);
+    for (line, expected) in [("> quote", false), ("> [!tip]", true), ("> tip", true)] {
+        excluder.update(line);
+        assert_eq!(excluder.is_excluded(), expected, "Failed for: {}", line);
+    }
+}
+
+#[test]
+fn test_inline_exclusion_zones() {
+    let line = "`a` %%b%% <i>c</i> $d$ $5 and $6";
+    let zones = |regions: &[SkipRegion]| {
+        let mut zones = inline_exclusion_zones(line, regions);
+        zones.sort();
+        zones
+    };
+
+    assert_eq!(
+        zones(&SkipRegion::defaults()),
+        vec![(0, 3), (4, 9), (10, 13), (14, 18), (19, 22)]
+    );
+    assert_eq!(zones(&[SkipRegion::Math]), vec![(19, 22)]);
+    assert!(zones(&[]).is_empty());
+}
+
+#[test]
+fn test_html_block_start() {
+    let test_cases = [
+        ("<div>", true),
+        ("<DIV class=\"note\">text", true),
+        ("</table>", true),
+        ("<!-- a comment", true),
+        ("<custom-element>", true),
+        ("<mark>Foo</mark> is important", false),
+        ("<span>a</span> and <b>b</b>", false),
+        ("<https://example.com>", false),
+    ];
+
+    for (line, expected) in test_cases {
+        let mut excluder = RegionExcluder::new(&[SkipRegion::Html]);
+        excluder.update(line);
+        assert_eq!(excluder.is_excluded(), expected, "Failed for: {}", line);
+    }
+}
+
+#[test]
+fn test_parse_skip_region() {
+    for region in SkipRegion::defaults() {
+        assert_eq!(region.to_string().parse::<SkipRegion>(), Ok(region));
+    }
+    assert!("tables".parse::<SkipRegion>().is_err());
+}
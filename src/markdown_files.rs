@@ -1,10 +1,13 @@
-use crate::markdown_file::{BackPopulateMatch, MarkdownFile};
+use crate::markdown_file::{
+    BackPopulateMatch, MarkdownFile, PersistConflict, SimplifyWikilinkMatch,
+};
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::Wikilink;
 
 use aho_corasick::AhoCorasick;
 use rayon::prelude::*;
 use std::error::Error;
+use std::path::PathBuf;
 use vecollect::collection;
 
 #[derive(Debug, Default)]
@@ -27,12 +30,8 @@ impl MarkdownFiles {
     ) {
         // this use of rayon generally makes it go about 100ms faster
         self.par_iter_mut().for_each(|markdown_file| {
-            if !cfg!(test) {
-                if let Some(filter) = config.back_populate_file_filter() {
-                    if !markdown_file.path.ends_with(filter) {
-                        return;
-                    }
-                }
+            if is_filtered_out(markdown_file, config) {
+                return;
             }
 
             markdown_file.process_file_for_back_populate_replacements(
@@ -43,17 +42,41 @@ impl MarkdownFiles {
         });
     }
 
+    pub fn process_files_for_simplify_wikilinks(&mut self, config: &ValidatedConfig) {
+        self.par_iter_mut().for_each(|markdown_file| {
+            if is_filtered_out(markdown_file, config) {
+                return;
+            }
+
+            markdown_file.process_file_for_simplify_wikilinks(config);
+        });
+    }
+
+    pub fn simplify_wikilink_matches(&self) -> Vec<SimplifyWikilinkMatch> {
+        self.iter()
+            .flat_map(|file| file.simplify_wikilink_matches.clone())
+            .collect()
+    }
+
     pub fn unambiguous_matches(&self) -> Vec<BackPopulateMatch> {
         self.iter()
             .flat_map(|file| file.matches.unambiguous.clone())
             .collect()
     }
 
-    pub fn persist_all(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// files that changed on disk since they were read are skipped and returned - everything
+    /// else is still written
+    pub fn persist_all(&self) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
+        let mut conflicts = Vec::new();
         for file_info in &self.files {
-            file_info.persist()?;
+            if let Err(e) = file_info.persist() {
+                match e.downcast_ref::<PersistConflict>() {
+                    Some(conflict) => conflicts.push(conflict.path.clone()),
+                    None => return Err(e),
+                }
+            }
         }
-        Ok(())
+        Ok(conflicts)
     }
 
     pub fn total_files_to_persist(&self) -> usize {
@@ -62,7 +85,7 @@ impl MarkdownFiles {
                 file_info
                     .frontmatter
                     .as_ref()
-                    .map_or(false, |fm| fm.needs_persist())
+                    .is_some_and(|fm| fm.needs_persist())
             })
             .count()
     }
@@ -74,7 +97,7 @@ impl MarkdownFiles {
                 file_info
                     .frontmatter
                     .as_ref()
-                    .map_or(false, |fm| fm.needs_persist())
+                    .is_some_and(|fm| fm.needs_persist())
             })
             .cloned()
             .collect();
@@ -90,3 +113,14 @@ impl MarkdownFiles {
         }
     }
 }
+
+// back_populate_file_filter narrows both back populate and simplify_wikilinks to the matching file
+fn is_filtered_out(markdown_file: &MarkdownFile, config: &ValidatedConfig) -> bool {
+    if cfg!(test) {
+        return false;
+    }
+
+    config
+        .back_populate_file_filter()
+        .is_some_and(|filter| !markdown_file.path.ends_with(filter))
+}
@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod ambiguous_matches_tests;
 #[cfg(test)]
+mod feature_switch_tests;
+#[cfg(test)]
 mod file_limit_tests;
 #[cfg(test)]
 mod image_tests;
@@ -16,8 +18,12 @@ mod update_modified_tests;
 use crate::{
     constants::*,
     image_file::{ImageFile, ImageFileState, ImageFiles},
+    inflection::Inflections,
     markdown_file::BackPopulateMatch,
-    markdown_file::{ImageLinkState, MarkdownFile, MatchType, ReplaceableContent},
+    markdown_file::{
+        ImageLinkState, MarkdownFile, MarkdownFileOptions, MatchType, PersistReason,
+        ReplaceableContent,
+    },
     markdown_files::MarkdownFiles,
     utils,
     utils::Timer,
@@ -26,6 +32,7 @@ use crate::{
     wikilink::Wikilink,
 };
 
+use crate::date_source::{CreatedDateStore, DateSource, DateStore, DateStoreEntry, GitDates};
 use crate::image_file::ImageHash;
 use crate::utils::Sha256Cache;
 use aho_corasick::{AhoCorasick, AhoCorasickBuilder, MatchKind};
@@ -37,6 +44,8 @@ use std::sync::{Arc, Mutex};
 
 #[derive(Default)]
 pub struct ObsidianRepository {
+    pub created_dates: Option<Arc<CreatedDateStore>>,
+    pub date_store: Option<Arc<DateStore>>,
     pub markdown_files: MarkdownFiles,
     pub image_files: ImageFiles,
     #[allow(dead_code)]
@@ -52,17 +61,41 @@ impl ObsidianRepository {
 
         let files = utils::collect_repository_files(validated_config, ignore_folders)?;
 
+        let mut options = MarkdownFileOptions::from(validated_config);
+        match validated_config.date_settings().source {
+            DateSource::ContentHash => {
+                options.date_store =
+                    Some(Arc::new(DateStore::load(validated_config.obsidian_path())));
+            }
+            DateSource::Git => {
+                options.git_dates = Some(Arc::new(GitDates::load(
+                    validated_config.obsidian_path(),
+                    validated_config.date_settings(),
+                )?));
+            }
+            DateSource::FileSystem => {
+                if !utils::can_set_created_date() {
+                    options.created_dates = Some(Arc::new(CreatedDateStore::load(
+                        validated_config.obsidian_path(),
+                    )));
+                }
+            }
+        }
+
         // Process markdown files
         let markdown_files = Self::initialize_markdown_files(
             &files.markdown_files,
-            validated_config.operational_timezone(),
+            &options,
             validated_config.file_limit(),
         )?;
 
-        let (sorted, ac) = Self::initialize_wikilinks(&markdown_files);
+        let (sorted, ac) =
+            Self::initialize_wikilinks(&markdown_files, validated_config.inflections());
 
         // Initialize instance with defaults
         let mut repository = Self {
+            created_dates: options.created_dates,
+            date_store: options.date_store,
             markdown_files,
             image_files: ImageFiles::default(),
             other_files: files.other_files,
@@ -80,14 +113,14 @@ impl ObsidianRepository {
 
     fn initialize_markdown_files(
         markdown_paths: &[PathBuf],
-        timezone: &str,
+        options: &MarkdownFileOptions,
         file_limit: Option<usize>,
     ) -> Result<MarkdownFiles, Box<dyn Error + Send + Sync>> {
         // Use Arc<Mutex<...>> for safe shared collection
         let markdown_files = Arc::new(Mutex::new(MarkdownFiles::default()));
 
         markdown_paths.par_iter().try_for_each(|file_path| {
-            match MarkdownFile::new(file_path.clone(), timezone) {
+            match MarkdownFile::with_options(file_path.clone(), options) {
                 Ok(file_info) => {
                     markdown_files.lock().unwrap().push(file_info);
                     Ok(())
@@ -110,11 +143,18 @@ impl ObsidianRepository {
         Ok(markdown_files)
     }
 
-    fn initialize_wikilinks(markdown_files: &MarkdownFiles) -> (Vec<Wikilink>, AhoCorasick) {
-        let all_wikilinks: HashSet<Wikilink> = markdown_files
+    /// inflected names go into the same automaton as the names themselves
+    fn initialize_wikilinks(
+        markdown_files: &MarkdownFiles,
+        inflections: Option<&Inflections>,
+    ) -> (Vec<Wikilink>, AhoCorasick) {
+        let mut all_wikilinks: HashSet<Wikilink> = markdown_files
             .iter()
             .flat_map(|file_info| file_info.wikilinks.valid.clone())
             .collect();
+        if let Some(inflections) = inflections {
+            inflections.add_variants(&mut all_wikilinks);
+        }
         sort_and_build_wikilinks_ac(all_wikilinks)
     }
 
@@ -123,11 +163,30 @@ impl ObsidianRepository {
         validated_config: &ValidatedConfig,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let _timer = Timer::new("analyze");
-        self.find_all_back_populate_matches(validated_config);
-        self.identify_ambiguous_matches();
-        self.identify_image_reference_replacements();
+        self.find_all_simplify_wikilink_matches(validated_config);
+        if validated_config.back_populate_enabled() {
+            self.find_all_back_populate_matches(validated_config);
+            self.identify_ambiguous_matches();
+            self.resolve_ambiguous_matches(validated_config);
+            self.apply_link_density(validated_config);
+        }
+        if validated_config.image_references_enabled() {
+            self.identify_image_reference_replacements();
+        }
+        // before the schema so it's checked against the updated properties
+        self.apply_property_operations(validated_config);
+        self.check_property_types(validated_config);
+        self.validate_schema(validated_config);
+        if let Some(approved_changes) = validated_config.approved_changes() {
+            self.retain_approved_changes(approved_changes);
+        }
         self.apply_replaceable_matches(validated_config.operational_timezone());
-        self.mark_image_files_for_deletion();
+        if validated_config.image_deletion_enabled() {
+            self.mark_image_files_for_deletion(validated_config.image_references_enabled());
+        }
+        if let Some(approved_changes) = validated_config.approved_changes() {
+            self.retain_approved_image_deletions(approved_changes);
+        }
         Ok(())
     }
 
@@ -267,11 +326,13 @@ fn sort_and_build_wikilinks_ac(all_wikilinks: HashSet<Wikilink>) -> (Vec<Wikilin
     // uses
     wikilinks.sort_unstable();
 
-    let mut patterns = Vec::with_capacity(wikilinks.len());
-    patterns.extend(wikilinks.iter().map(|w| w.display_text.as_str()));
+    // aho-corasick only knows ascii case so the patterns and the text searched are both folded
+    let patterns: Vec<String> = wikilinks
+        .iter()
+        .map(|w| utils::fold_case(&w.display_text))
+        .collect();
 
     let ac = AhoCorasickBuilder::new()
-        .ascii_case_insensitive(true)
         .match_kind(MatchKind::LeftmostLongest)
         .build(&patterns)
         .expect("Failed to build Aho-Corasick automaton for wikilinks");
@@ -280,22 +341,21 @@ fn sort_and_build_wikilinks_ac(all_wikilinks: HashSet<Wikilink>) -> (Vec<Wikilin
 }
 
 impl ObsidianRepository {
-    pub fn identify_ambiguous_matches(&mut self) {
-        // Create target and display_text maps as before...
+    /// every target a case folded display text links to - targets differing only in case are
+    /// one target
+    pub(crate) fn targets_by_display_text(&self) -> HashMap<String, HashSet<String>> {
         let mut target_map: HashMap<String, String> = HashMap::new();
         for wikilink in &self.wikilinks_sorted {
-            let lower_target = wikilink.target.to_lowercase();
-            if !target_map.contains_key(&lower_target)
-                || wikilink.target.to_lowercase() == wikilink.target
-            {
+            let lower_target = utils::fold_case(&wikilink.target);
+            if !target_map.contains_key(&lower_target) || lower_target == wikilink.target {
                 target_map.insert(lower_target.clone(), wikilink.target.clone());
             }
         }
 
         let mut display_text_map: HashMap<String, HashSet<String>> = HashMap::new();
         for wikilink in &self.wikilinks_sorted {
-            let lower_display_text = wikilink.display_text.to_lowercase();
-            let lower_target = wikilink.target.to_lowercase();
+            let lower_display_text = utils::fold_case(&wikilink.display_text);
+            let lower_target = utils::fold_case(&wikilink.target);
             if let Some(canonical_target) = target_map.get(&lower_target) {
                 display_text_map
                     .entry(lower_display_text.clone())
@@ -304,6 +364,12 @@ impl ObsidianRepository {
             }
         }
 
+        display_text_map
+    }
+
+    pub fn identify_ambiguous_matches(&mut self) {
+        let display_text_map = self.targets_by_display_text();
+
         // Process each file's matches
         for markdown_file in &mut self.markdown_files {
             // Create a map to group matches by their lowercased found_text within this file
@@ -312,7 +378,7 @@ impl ObsidianRepository {
             // Drain matches from the file into our temporary map
             let file_matches = std::mem::take(&mut markdown_file.matches.unambiguous);
             for match_info in file_matches {
-                let lower_found_text = match_info.found_text.to_lowercase();
+                let lower_found_text = utils::fold_case(&match_info.found_text);
                 matches_by_text
                     .entry(lower_found_text)
                     .or_default()
@@ -343,6 +409,11 @@ impl ObsidianRepository {
         }
     }
 
+    pub fn find_all_simplify_wikilink_matches(&mut self, config: &ValidatedConfig) {
+        self.markdown_files
+            .process_files_for_simplify_wikilinks(config);
+    }
+
     pub fn find_all_back_populate_matches(&mut self, config: &ValidatedConfig) {
         let ac = self
             .wikilinks_ac
@@ -358,6 +429,12 @@ impl ObsidianRepository {
 
     pub fn apply_replaceable_matches(&mut self, operational_timezone: &str) {
         for markdown_file in &mut self.markdown_files {
+            // a note without frontmatter isn't persisted so its matches are only reported -
+            // create_frontmatter gives it one
+            if markdown_file.frontmatter.is_none() {
+                continue;
+            }
+
             let has_replaceable_image_links = markdown_file.image_links.iter().any(|link| {
                 matches!(
                     link.state,
@@ -367,7 +444,10 @@ impl ObsidianRepository {
                 )
             });
 
-            if !markdown_file.has_unambiguous_matches() && !has_replaceable_image_links {
+            if !markdown_file.has_unambiguous_matches()
+                && !markdown_file.has_simplify_wikilink_matches()
+                && !has_replaceable_image_links
+            {
                 continue;
             }
 
@@ -381,6 +461,7 @@ impl ObsidianRepository {
             let mut content_line_number = 1;
             let mut has_back_populate_changes = false;
             let mut has_image_reference_changes = false;
+            let mut has_simplify_wikilink_changes = false;
 
             // Process line by line
             for (zero_based_idx, line) in markdown_file.content.lines().enumerate() {
@@ -414,6 +495,7 @@ impl ObsidianRepository {
                         match m.match_type() {
                             MatchType::BackPopulate => has_back_populate_changes = true,
                             MatchType::ImageReference => has_image_reference_changes = true,
+                            MatchType::SimplifyWikilink => has_simplify_wikilink_changes = true,
                         }
                     }
 
@@ -432,10 +514,14 @@ impl ObsidianRepository {
             markdown_file.content = updated_content.trim_end().to_string();
 
             if has_back_populate_changes {
-                markdown_file.mark_as_back_populated(operational_timezone);
+                markdown_file.mark_edited(PersistReason::BackPopulated, operational_timezone);
             }
             if has_image_reference_changes {
-                markdown_file.mark_image_reference_as_updated(operational_timezone);
+                markdown_file
+                    .mark_edited(PersistReason::ImageReferencesModified, operational_timezone);
+            }
+            if has_simplify_wikilink_changes {
+                markdown_file.mark_edited(PersistReason::WikilinksSimplified, operational_timezone);
             }
         }
     }
@@ -455,6 +541,15 @@ impl ObsidianRepository {
                 .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
         );
 
+        // Add wikilinks that are being simplified to their display text
+        matches.extend(
+            markdown_file
+                .simplify_wikilink_matches
+                .iter()
+                .cloned()
+                .map(|m| Box::new(m) as Box<dyn ReplaceableContent>),
+        );
+
         // Add the image link states that need replacement
         matches.extend(
             markdown_file
@@ -478,11 +573,99 @@ impl ObsidianRepository {
         matches
     }
 
-    pub fn persist(&mut self) -> Result<(), Box<dyn Error + Send + Sync>> {
+    /// returns the markdown files that were skipped because they changed since they were read
+    pub fn persist(&mut self) -> Result<Vec<PathBuf>, Box<dyn Error + Send + Sync>> {
         self.image_files.delete_marked()?;
         self.markdown_files.files_to_persist().persist_all()
     }
 
+    /// remembers every note's content hash and dates for the next run - files written by this
+    /// run are recorded as written, everything else as it was read
+    pub fn save_date_store(
+        &self,
+        applied: bool,
+        conflicts: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(date_store) = &self.date_store else {
+            return Ok(());
+        };
+        let mut date_store = date_store.as_ref().clone();
+        let persisted = self.persisted_paths(applied, conflicts);
+
+        for file in &self.markdown_files {
+            let Some(content_hash) = &file.content_hash else {
+                continue;
+            };
+
+            let created = file.date_validation_created.file_system_date;
+            let modified = file.date_validation_modified.file_system_date;
+            let entry = match file.frontmatter.as_ref() {
+                Some(fm) if persisted.contains(&file.path) => DateStoreEntry {
+                    hash: file.current_content_hash(),
+                    created: fm.raw_date_created.unwrap_or(created),
+                    modified: fm.raw_date_modified.unwrap_or(modified),
+                },
+                _ => DateStoreEntry {
+                    hash: content_hash.clone(),
+                    created,
+                    modified,
+                },
+            };
+            date_store.record(file.path.clone(), entry);
+        }
+
+        date_store.retain(&self.markdown_paths());
+        date_store.save()
+    }
+
+    /// remembers the created date of every note for the next run - files written by this run
+    /// are recorded with the date they were written with, everything else with the date that
+    /// was validated against. a run that doesn't apply changes leaves the store alone
+    pub fn save_created_dates(
+        &self,
+        applied: bool,
+        conflicts: &[PathBuf],
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let Some(created_dates) = self.created_dates.as_ref().filter(|_| applied) else {
+            return Ok(());
+        };
+        let mut created_dates = created_dates.as_ref().clone();
+        let persisted = self.persisted_paths(applied, conflicts);
+
+        for file in &self.markdown_files {
+            let created = file.date_validation_created.file_system_date;
+            let created = match file.frontmatter.as_ref() {
+                Some(fm) if persisted.contains(&file.path) => {
+                    fm.raw_date_created.unwrap_or(created)
+                }
+                _ => created,
+            };
+            created_dates.record(file.path.clone(), created);
+        }
+
+        created_dates.retain(&self.markdown_paths());
+        created_dates.save()
+    }
+
+    fn persisted_paths(&self, applied: bool, conflicts: &[PathBuf]) -> HashSet<PathBuf> {
+        if !applied {
+            return HashSet::new();
+        }
+        self.markdown_files
+            .files_to_persist()
+            .iter()
+            .map(|file| file.path.clone())
+            .filter(|path| !conflicts.contains(path))
+            .collect()
+    }
+
+    fn markdown_paths(&self) -> HashSet<&Path> {
+        self.markdown_files
+            .iter()
+            .map(|file| file.path.as_path())
+            .collect()
+    }
+
     fn identify_image_reference_replacements(&mut self) {
         // first handle missing references
         let image_filenames: HashSet<String> = self
@@ -556,18 +739,21 @@ impl ObsidianRepository {
         }
     }
 
-    fn mark_image_files_for_deletion(&mut self) {
+    // without reference updates a referenced image can't be deleted - nothing would point the
+    // references elsewhere - so only images without references are deleted
+    fn mark_image_files_for_deletion(&mut self, references_updated: bool) {
         let files_to_persist = self.markdown_files.files_to_persist();
 
         let files_to_persist: HashSet<_> = files_to_persist.iter().map(|f| &f.path).collect();
 
         // Check if all references are in files being persisted
-        fn can_delete(files_to_persist: &HashSet<&PathBuf>, image_file: &ImageFile) -> bool {
-            image_file
-                .markdown_file_references
-                .iter()
-                .all(|path| files_to_persist.contains(&path))
-        }
+        let can_delete = |files_to_persist: &HashSet<&PathBuf>, image_file: &ImageFile| -> bool {
+            references_updated
+                && image_file
+                    .markdown_file_references
+                    .iter()
+                    .all(|path| files_to_persist.contains(&path))
+        };
 
         for image_file in &mut self.image_files.files {
             match &image_file.image_state {
@@ -48,6 +48,8 @@ fn test_identify_ambiguous_matches() {
         replacement: "[[Ed Barnes|Ed]]".to_string(),
         position: 0,
         in_markdown_table: false,
+        resolved_by: None,
+        target: "Ed Barnes".to_string(),
     }];
 
     // Set up initial matches in test2.md
@@ -64,6 +66,8 @@ fn test_identify_ambiguous_matches() {
         replacement: "[[Unique Target]]".to_string(),
         position: 0,
         in_markdown_table: false,
+        resolved_by: None,
+        target: "Unique Target".to_string(),
     }];
 
     repository.markdown_files.push(test_file2);
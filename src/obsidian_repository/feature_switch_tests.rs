@@ -0,0 +1,183 @@
+use crate::image_file::ImageFileState;
+use crate::markdown_file::{MarkdownFile, PersistReason};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use crate::validated_config::ValidatedConfigBuilder;
+use std::fs;
+use tempfile::TempDir;
+
+fn create_repository(
+    temp_dir: &TempDir,
+    modifier: impl FnOnce(&mut ValidatedConfigBuilder),
+) -> ObsidianRepository {
+    let config =
+        validated_config_tests::get_test_validated_config_result(temp_dir, modifier).unwrap();
+    fs::create_dir_all(config.output_folder()).unwrap();
+    ObsidianRepository::new(&config).unwrap()
+}
+
+fn create_back_populate_files(temp_dir: &TempDir) {
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("# Target".to_string())
+        .create(temp_dir, "Target.md");
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("mentions Target here".to_string())
+        .create(temp_dir, "other.md");
+}
+
+fn create_image_files(temp_dir: &TempDir) {
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_content("![[keeper.png]]\n![[duplicate.png]]".to_string())
+        .create(temp_dir, "images.md");
+    TestFileBuilder::new()
+        .with_content(vec![1, 2, 3, 4])
+        .create(temp_dir, "keeper.png");
+    TestFileBuilder::new()
+        .with_content(vec![1, 2, 3, 4])
+        .create(temp_dir, "duplicate.png");
+    TestFileBuilder::new()
+        .with_content(vec![5, 6, 7, 8])
+        .create(temp_dir, "unreferenced.png");
+}
+
+fn has_image_references_modified(file: &MarkdownFile) -> bool {
+    file.persist_reasons
+        .iter()
+        .any(|reason| matches!(reason, PersistReason::ImageReferencesModified))
+}
+
+fn deleted_images(repository: &ObsidianRepository) -> Vec<String> {
+    let mut deleted: Vec<String> = repository
+        .image_files
+        .iter()
+        .filter(|image_file| image_file.delete)
+        .map(|image_file| {
+            image_file
+                .path
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+    deleted.sort();
+    deleted
+}
+
+#[test]
+fn test_back_populate_enabled_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let repository = create_repository(&temp_dir, |_| {});
+
+    assert!(repository
+        .markdown_files
+        .iter()
+        .any(|file| !file.matches.unambiguous.is_empty()));
+}
+
+#[test]
+fn test_back_populate_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let repository = create_repository(&temp_dir, |builder| {
+        builder.back_populate_enabled(false);
+    });
+
+    assert!(repository
+        .markdown_files
+        .iter()
+        .all(|file| file.matches.unambiguous.is_empty() && file.matches.ambiguous.is_empty()));
+    assert!(repository.markdown_files.iter().all(|file| !file
+        .persist_reasons
+        .iter()
+        .any(|reason| matches!(reason, PersistReason::BackPopulated))));
+}
+
+#[test]
+fn test_dates_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    TestFileBuilder::new()
+        .with_content("no frontmatter dates here".to_string())
+        .create(&temp_dir, "undated.md");
+
+    let repository = create_repository(&temp_dir, |builder| {
+        builder.dates_enabled(false);
+    });
+
+    let file = repository.markdown_files.iter().next().unwrap();
+    assert!(file.persist_reasons.is_empty());
+    assert!(repository.markdown_files.files_to_persist().is_empty());
+}
+
+#[test]
+fn test_dates_disabled_keeps_date_modified_on_edit() {
+    let temp_dir = TempDir::new().unwrap();
+    create_back_populate_files(&temp_dir);
+
+    let mut repository = create_repository(&temp_dir, |builder| {
+        builder.dates_enabled(false);
+    });
+    let file = repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with("other.md"))
+        .unwrap();
+    assert!(file.persist_reasons.contains(&PersistReason::BackPopulated));
+    repository.persist().unwrap();
+
+    let content = fs::read_to_string(temp_dir.path().join("other.md")).unwrap();
+    assert!(content.contains("[[Target]]"), "{}", content);
+    assert!(
+        content.contains("date_modified: \"[[2024-01-15]]\""),
+        "{}",
+        content
+    );
+}
+
+#[test]
+fn test_image_deletion_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    create_image_files(&temp_dir);
+
+    let repository = create_repository(&temp_dir, |builder| {
+        builder.image_deletion_enabled(false);
+    });
+
+    assert!(deleted_images(&repository).is_empty());
+    // references are still pointed at the keeper
+    assert!(repository
+        .markdown_files
+        .iter()
+        .any(has_image_references_modified));
+}
+
+#[test]
+fn test_image_references_disabled() {
+    let temp_dir = TempDir::new().unwrap();
+    create_image_files(&temp_dir);
+
+    let repository = create_repository(&temp_dir, |builder| {
+        builder.image_references_enabled(false);
+    });
+
+    // the duplicate is still referenced so only the unreferenced image can go
+    assert_eq!(deleted_images(&repository), vec!["unreferenced.png"]);
+    assert!(repository
+        .image_files
+        .iter()
+        .any(|image_file| matches!(image_file.image_state, ImageFileState::Duplicate { .. })));
+    assert!(repository
+        .markdown_files
+        .iter()
+        .all(|file| !has_image_references_modified(file)));
+}
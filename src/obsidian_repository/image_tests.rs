@@ -84,7 +84,7 @@ fn test_analyze_missing_references() {
 
     let mut repository = ObsidianRepository::new(&config).unwrap();
     if let Some(markdown_file) = repository.markdown_files.get_mut(&md_file) {
-        // Instead of using mark_image_reference_as_updated which uses current date,
+        // Instead of using mark_edited which uses current date,
         // directly set the date we want
         if let Some(fm) = &mut markdown_file.frontmatter {
             fm.set_date_modified(test_date, config.operational_timezone());
@@ -103,7 +103,7 @@ fn test_analyze_missing_references() {
 
     // Second analyze pass to verify idempotency
     let mut repository = ObsidianRepository::new(&config).unwrap();
-    repository.mark_image_files_for_deletion();
+    repository.mark_image_files_for_deletion(true);
     repository.persist().unwrap();
 
     // Verify content remains the same after second pass
@@ -241,10 +241,13 @@ fn test_image_replacement_outcomes() {
         for path in &created_paths {
             if path
                 .extension()
-                .map_or(false, |ext| ext == MARKDOWN_EXTENSION)
+                .is_some_and(|ext| ext == MARKDOWN_EXTENSION)
             {
                 if let Some(markdown_file) = repository.markdown_files.get_mut(path) {
-                    markdown_file.mark_image_reference_as_updated(config.operational_timezone());
+                    markdown_file.mark_edited(
+                        PersistReason::ImageReferencesModified,
+                        config.operational_timezone(),
+                    );
                 }
             }
         }
@@ -274,7 +277,7 @@ fn test_analyze_wikilink_errors() {
     let mut repository = ObsidianRepository::new(&config).unwrap();
 
     // Run analyze and verify it handles wikilink paths appropriately
-    repository.mark_image_files_for_deletion();
+    repository.mark_image_files_for_deletion(true);
 
     // Verify the content wasn't modified
     let final_content = fs::read_to_string(&md_file).unwrap();
@@ -495,7 +498,10 @@ fn test_referenced_and_unreferenced_duplicates() {
 
     // Mark markdown file for persistence so files can be deleted
     if let Some(markdown_file) = repository.markdown_files.get_mut(&created_paths[4]) {
-        markdown_file.mark_image_reference_as_updated(config.operational_timezone());
+        markdown_file.mark_edited(
+            PersistReason::ImageReferencesModified,
+            config.operational_timezone(),
+        );
     }
 
     repository.persist().unwrap();
@@ -24,7 +24,7 @@ fn test_update_modified_dates_changes_frontmatter() {
     let mut repository = ObsidianRepository::default();
     let mut markdown_file = test_utils::get_test_markdown_file(file_path.clone());
 
-    // Instead of using mark_image_reference_as_updated which uses current date,
+    // Instead of using mark_edited which uses current date,
     // set the frontmatter dates directly
     if let Some(fm) = &mut markdown_file.frontmatter {
         fm.set_date_modified(update_date, DEFAULT_TIMEZONE);
@@ -124,8 +124,8 @@ fn test_update_modified_uses_current_date() {
 
     let mut markdown_file = test_utils::get_test_markdown_file(file_path);
 
-    // Use the actual mark_image_reference_as_updated method
-    markdown_file.mark_image_reference_as_updated(DEFAULT_TIMEZONE);
+    // Use the actual mark_edited method
+    markdown_file.mark_edited(PersistReason::ImageReferencesModified, DEFAULT_TIMEZONE);
 
     // Get the frontmatter modified date
     let modified_date = markdown_file
@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod property_operations_tests;
+
+use crate::approval;
+use crate::markdown_file::PersistReason;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::schema;
+use crate::validated_config::ValidatedConfig;
+use globset::GlobMatcher;
+use itertools::Itertools;
+use serde_yaml::{Mapping, Value};
+use std::path::Path;
+
+/// what an operation does to a note's properties
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropertyAction {
+    /// keeps the value where it is - a note that already has the new name is left alone
+    Rename {
+        from: String,
+        to: String,
+    },
+    Remove(String),
+    Set {
+        property: String,
+        value: Value,
+    },
+    /// a missing property becomes a list and a single value becomes its first item
+    AddToList {
+        property: String,
+        values: Vec<Value>,
+    },
+    RemoveFromList {
+        property: String,
+        values: Vec<Value>,
+    },
+}
+
+impl PropertyAction {
+    /// every property the action reads or writes
+    pub fn properties(&self) -> Vec<&str> {
+        match self {
+            PropertyAction::Rename { from, to } => vec![from, to],
+            PropertyAction::Remove(property)
+            | PropertyAction::Set { property, .. }
+            | PropertyAction::AddToList { property, .. }
+            | PropertyAction::RemoveFromList { property, .. } => vec![property],
+        }
+    }
+
+    fn apply(&self, properties: &mut Mapping) {
+        match self {
+            PropertyAction::Rename { from, to } => {
+                if properties.contains_key(to.as_str()) {
+                    return;
+                }
+                if let Some(value) = properties.remove(from.as_str()) {
+                    properties.insert(Value::String(to.clone()), value);
+                }
+            }
+            PropertyAction::Remove(property) => {
+                properties.remove(property.as_str());
+            }
+            PropertyAction::Set { property, value } => {
+                properties.insert(Value::String(property.clone()), value.clone());
+            }
+            PropertyAction::AddToList { property, values } => {
+                let mut items = match properties.get(property.as_str()) {
+                    None | Some(Value::Null) => Vec::new(),
+                    Some(Value::Sequence(items)) => items.clone(),
+                    Some(value) => vec![value.clone()],
+                };
+                for value in values {
+                    if !items.contains(value) {
+                        items.push(value.clone());
+                    }
+                }
+                properties.insert(Value::String(property.clone()), Value::Sequence(items));
+            }
+            PropertyAction::RemoveFromList { property, values } => {
+                if let Some(Value::Sequence(items)) = properties.get_mut(property.as_str()) {
+                    items.retain(|item| !values.contains(item));
+                }
+            }
+        }
+    }
+}
+
+/// actions for every note in a folder, with a tag or with a property value
+#[derive(Debug, Clone)]
+pub struct PropertyOperation {
+    pub folder: Option<String>,
+    folder_matcher: Option<GlobMatcher>,
+    pub tag: Option<String>,
+    /// a property matches when it has the value - a list when one of its items does
+    pub filter: Vec<(String, Value)>,
+    pub actions: Vec<PropertyAction>,
+}
+
+impl PropertyOperation {
+    pub fn new(
+        folder: Option<String>,
+        tag: Option<String>,
+        filter: Vec<(String, Value)>,
+        actions: Vec<PropertyAction>,
+    ) -> Result<Self, String> {
+        if actions.is_empty() {
+            return Err(
+                "a property operation needs set, rename, remove, add_to_list or remove_from_list"
+                    .to_string(),
+            );
+        }
+
+        Ok(Self {
+            folder_matcher: folder.as_deref().map(schema::folder_matcher).transpose()?,
+            folder,
+            tag: tag.map(|tag| schema::normalize_tag(&tag)),
+            filter,
+            actions,
+        })
+    }
+
+    /// without a folder, tag or filter the operation applies to every note with frontmatter
+    pub fn applies_to(&self, relative_path: &Path, properties: &Mapping) -> bool {
+        let folder_matches = self
+            .folder_matcher
+            .as_ref()
+            .is_none_or(|matcher| schema::in_folder(matcher, relative_path));
+        let tag_matches = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| schema::note_tags(properties).contains(tag));
+        let filter_matches = self.filter.iter().all(|(property, expected)| {
+            match properties.get(property.as_str()) {
+                Some(Value::Sequence(items)) => items.contains(expected),
+                Some(value) => value == expected,
+                None => false,
+            }
+        });
+
+        folder_matches && tag_matches && filter_matches
+    }
+}
+
+/// one property of one note - None before is an added property, None after a removed one
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyChange {
+    pub property: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+    pub applied: bool,
+}
+
+/// the property operations from the config and the command line
+#[derive(Debug, Clone, Default)]
+pub struct PropertyOperations {
+    operations: Vec<PropertyOperation>,
+}
+
+impl PropertyOperations {
+    pub fn new(operations: Vec<PropertyOperation>) -> Self {
+        Self { operations }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PropertyOperation> {
+        self.operations.iter()
+    }
+
+    /// runs the operations in order - each one sees what the ones before it did - and returns
+    /// every property that ends up different
+    pub fn changes(&self, relative_path: &Path, properties: &Mapping) -> Vec<PropertyChange> {
+        let mut updated = properties.clone();
+        for operation in &self.operations {
+            if operation.applies_to(relative_path, &updated) {
+                for action in &operation.actions {
+                    action.apply(&mut updated);
+                }
+            }
+        }
+
+        properties
+            .keys()
+            .chain(updated.keys())
+            .unique()
+            .filter_map(|key| {
+                let before = properties.get(key);
+                let after = updated.get(key);
+                (before != after).then(|| PropertyChange {
+                    property: schema::value_text(key),
+                    before: before.cloned(),
+                    after: after.cloned(),
+                    applied: false,
+                })
+            })
+            .collect()
+    }
+}
+
+impl ObsidianRepository {
+    /// records the property changes of every note and applies them - unless `ok apply` found
+    /// change ids in the output file, then only the approved ones
+    pub fn apply_property_operations(&mut self, validated_config: &ValidatedConfig) {
+        let operations = validated_config.property_operations();
+        if operations.is_empty() {
+            return;
+        }
+
+        for file in &mut self.markdown_files {
+            // there's nowhere to put the properties
+            let Some(frontmatter) = file.frontmatter.as_mut() else {
+                continue;
+            };
+            let relative_path = file
+                .path
+                .strip_prefix(validated_config.obsidian_path())
+                .unwrap_or(&file.path)
+                .to_path_buf();
+
+            let mut properties = frontmatter.properties();
+            let mut changes = operations.changes(&relative_path, &properties);
+            if changes.is_empty() {
+                continue;
+            }
+
+            let approved = validated_config.approved_changes().is_none_or(|approved| {
+                approved.contains(&approval::property_change_id(&file.path, &changes))
+            });
+            for change in &changes {
+                let key = Value::String(change.property.clone());
+                match &change.after {
+                    Some(value) => properties.insert(key, value.clone()),
+                    None => properties.remove(&key),
+                };
+            }
+            // a value that doesn't fit a property ok reads - e.g. aliases that aren't a list -
+            // stays in the report but isn't applied
+            let applied = approved && frontmatter.set_properties(properties).is_ok();
+            changes
+                .iter_mut()
+                .for_each(|change| change.applied = applied);
+
+            file.property_changes = changes;
+            if applied {
+                file.mark_edited(
+                    PersistReason::PropertiesUpdated,
+                    validated_config.operational_timezone(),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use super::*;
+use crate::approval::ApprovedChanges;
+use crate::markdown_file::PersistReason;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use std::fs;
+use tempfile::TempDir;
+
+fn yaml(text: &str) -> Mapping {
+    serde_yaml::from_str(text).unwrap()
+}
+
+fn value(text: &str) -> Value {
+    serde_yaml::from_str(text).unwrap()
+}
+
+fn operation(actions: Vec<PropertyAction>) -> PropertyOperation {
+    PropertyOperation::new(None, None, Vec::new(), actions).unwrap()
+}
+
+/// (property, before, after) for every change
+fn changes(operations: Vec<PropertyOperation>, properties: &str) -> Vec<(String, String, String)> {
+    let text = |value: Option<Value>| value.as_ref().map(schema::value_text).unwrap_or_default();
+    PropertyOperations::new(operations)
+        .changes(Path::new("note.md"), &yaml(properties))
+        .into_iter()
+        .map(|change| (change.property, text(change.before), text(change.after)))
+        .collect()
+}
+
+fn change(property: &str, before: &str, after: &str) -> (String, String, String) {
+    (property.to_string(), before.to_string(), after.to_string())
+}
+
+#[test]
+fn test_actions() {
+    let test_cases = [
+        (
+            PropertyAction::Set {
+                property: "status".to_string(),
+                value: value("done"),
+            },
+            "status: open",
+            vec![change("status", "open", "done")],
+        ),
+        (
+            PropertyAction::Rename {
+                from: "type".to_string(),
+                to: "kind".to_string(),
+            },
+            "type: book",
+            vec![change("type", "book", ""), change("kind", "", "book")],
+        ),
+        // renaming onto an existing property would lose its value
+        (
+            PropertyAction::Rename {
+                from: "type".to_string(),
+                to: "kind".to_string(),
+            },
+            "type: book\nkind: novel",
+            vec![],
+        ),
+        (
+            PropertyAction::Remove("draft".to_string()),
+            "draft: true\nstatus: open",
+            vec![change("draft", "true", "")],
+        ),
+        (
+            PropertyAction::AddToList {
+                property: "tags".to_string(),
+                values: vec![value("book"), value("read")],
+            },
+            "tags: [book]",
+            vec![change("tags", "[book]", "[book, read]")],
+        ),
+        (
+            PropertyAction::AddToList {
+                property: "tags".to_string(),
+                values: vec![value("read")],
+            },
+            "tags: book",
+            vec![change("tags", "book", "[book, read]")],
+        ),
+        (
+            PropertyAction::AddToList {
+                property: "tags".to_string(),
+                values: vec![value("read")],
+            },
+            "status: open",
+            vec![change("tags", "", "[read]")],
+        ),
+        (
+            PropertyAction::RemoveFromList {
+                property: "tags".to_string(),
+                values: vec![value("inbox")],
+            },
+            "tags: [inbox, book]",
+            vec![change("tags", "[inbox, book]", "[book]")],
+        ),
+        // nothing to do is no change
+        (
+            PropertyAction::RemoveFromList {
+                property: "tags".to_string(),
+                values: vec![value("inbox")],
+            },
+            "tags: [book]",
+            vec![],
+        ),
+    ];
+
+    for (action, properties, expected) in test_cases {
+        assert_eq!(
+            changes(vec![operation(vec![action.clone()])], properties),
+            expected,
+            "Failed for: {:?} on {}",
+            action,
+            properties
+        );
+    }
+}
+
+#[test]
+fn test_operation_applies_to() {
+    let folder = PropertyOperation::new(
+        Some("projects".to_string()),
+        None,
+        Vec::new(),
+        vec![PropertyAction::Remove("draft".to_string())],
+    )
+    .unwrap();
+    let tag_and_filter = PropertyOperation::new(
+        None,
+        Some("#Book".to_string()),
+        vec![("status".to_string(), value("reading"))],
+        vec![PropertyAction::Remove("draft".to_string())],
+    )
+    .unwrap();
+
+    let test_cases = [
+        (&folder, "projects/a.md", "draft: true", true),
+        (&folder, "projects/2024/a.md", "draft: true", true),
+        (&folder, "daily/a.md", "draft: true", false),
+        (
+            &tag_and_filter,
+            "a.md",
+            "tags: [book]\nstatus: reading",
+            true,
+        ),
+        (
+            &tag_and_filter,
+            "a.md",
+            "tags: book\nstatus: [reading, lent]",
+            true,
+        ),
+        (&tag_and_filter, "a.md", "tags: [book]\nstatus: done", false),
+        (&tag_and_filter, "a.md", "tags: [book]", false),
+        (&tag_and_filter, "a.md", "status: reading", false),
+    ];
+
+    for (operation, path, properties, expected) in test_cases {
+        assert_eq!(
+            operation.applies_to(Path::new(path), &yaml(properties)),
+            expected,
+            "Failed for {:?} {} {}",
+            operation.folder,
+            path,
+            properties
+        );
+    }
+
+    assert!(PropertyOperation::new(None, None, Vec::new(), Vec::new()).is_err());
+}
+
+#[test]
+fn test_operations_run_in_order() {
+    let rename = operation(vec![PropertyAction::Rename {
+        from: "state".to_string(),
+        to: "status".to_string(),
+    }]);
+    // sees the renamed property
+    let archive = PropertyOperation::new(
+        None,
+        None,
+        vec![("status".to_string(), value("done"))],
+        vec![PropertyAction::Set {
+            property: "status".to_string(),
+            value: value("archived"),
+        }],
+    )
+    .unwrap();
+
+    assert_eq!(
+        changes(vec![rename, archive], "state: done"),
+        vec![
+            change("state", "done", ""),
+            change("status", "", "archived")
+        ]
+    );
+}
+
+#[test]
+fn test_apply_property_operations() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    let file_path = TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_tags(vec!["inbox".to_string()])
+        .with_custom_frontmatter("status: open # keep\n".to_string())
+        .with_content("# Note".to_string())
+        .create(&temp_dir, "note.md");
+    let operations = PropertyOperations::new(vec![operation(vec![
+        PropertyAction::Set {
+            property: "status".to_string(),
+            value: value("done"),
+        },
+        PropertyAction::RemoveFromList {
+            property: "tags".to_string(),
+            values: vec![value("inbox")],
+        },
+    ])]);
+    let config = |approved_changes: Option<ApprovedChanges>| {
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+            builder
+                .property_operations(operations.clone())
+                .approved_changes(approved_changes);
+        })
+        .unwrap()
+    };
+
+    // nothing approved leaves the note alone but still reports it
+    let repository =
+        ObsidianRepository::new(&config(ApprovedChanges::parse("[ ] ok-0123456789"))).unwrap();
+    let file = &repository.markdown_files[0];
+    assert_eq!(file.property_changes.len(), 2);
+    assert!(file.property_changes.iter().all(|change| !change.applied));
+    assert!(file.persist_reasons.is_empty());
+
+    let mut repository = ObsidianRepository::new(&config(None)).unwrap();
+    let file = &repository.markdown_files[0];
+    assert!(file.property_changes.iter().all(|change| change.applied));
+    assert!(file
+        .persist_reasons
+        .contains(&PersistReason::PropertiesUpdated));
+
+    repository.persist().unwrap();
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(content.contains("status: done"), "{}", content);
+    assert!(content.contains("tags: []"), "{}", content);
+    assert!(!content.contains("inbox"), "{}", content);
+}
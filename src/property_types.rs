@@ -0,0 +1,302 @@
+#[cfg(test)]
+mod property_types_tests;
+
+use crate::constants::*;
+use crate::date_settings::DateSettings;
+use crate::markdown_file::PersistReason;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::schema::{self, PropertyType};
+use crate::validated_config::ValidatedConfig;
+use serde::Deserialize;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Default, Deserialize)]
+struct TypesFile {
+    #[serde(default)]
+    types: HashMap<String, String>,
+}
+
+/// the property types obsidian keeps in .obsidian/types.json
+#[derive(Debug, Clone, Default)]
+pub struct ObsidianTypes {
+    types: HashMap<String, PropertyType>,
+}
+
+impl ObsidianTypes {
+    /// a missing or unreadable types.json is the same as an empty one - obsidian only writes it
+    /// once a property has been given a type
+    pub fn load(obsidian_path: &Path) -> Self {
+        fs::read_to_string(
+            obsidian_path
+                .join(OBSIDIAN_HIDDEN_FOLDER)
+                .join(OBSIDIAN_TYPES_FILE),
+        )
+        .map(|text| Self::parse(&text))
+        .unwrap_or_default()
+    }
+
+    /// types ok has no equivalent for are left out
+    pub fn parse(text: &str) -> Self {
+        let file: TypesFile = serde_json::from_str(text).unwrap_or_default();
+        Self {
+            types: file
+                .types
+                .into_iter()
+                .filter_map(|(property, name)| Some((property, obsidian_type(&name)?)))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, property: &str) -> Option<PropertyType> {
+        self.types.get(property).copied()
+    }
+}
+
+fn obsidian_type(name: &str) -> Option<PropertyType> {
+    match name {
+        OBSIDIAN_TYPE_TEXT => Some(PropertyType::String),
+        OBSIDIAN_TYPE_MULTITEXT | OBSIDIAN_TYPE_TAGS | OBSIDIAN_TYPE_ALIASES => {
+            Some(PropertyType::List)
+        }
+        OBSIDIAN_TYPE_NUMBER => Some(PropertyType::Number),
+        OBSIDIAN_TYPE_CHECKBOX => Some(PropertyType::Bool),
+        OBSIDIAN_TYPE_DATE | OBSIDIAN_TYPE_DATETIME => Some(PropertyType::Date),
+        _ => None,
+    }
+}
+
+/// where the type a property should have comes from
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpectedType {
+    Obsidian(PropertyType),
+    /// the type most of the notes with the property use
+    Majority {
+        property_type: PropertyType,
+        count: usize,
+        total: usize,
+    },
+    /// no type is used by more notes than every other
+    Undecided,
+}
+
+impl ExpectedType {
+    pub fn property_type(&self) -> Option<PropertyType> {
+        match self {
+            ExpectedType::Obsidian(property_type)
+            | ExpectedType::Majority { property_type, .. } => Some(*property_type),
+            ExpectedType::Undecided => None,
+        }
+    }
+}
+
+impl fmt::Display for ExpectedType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpectedType::Obsidian(property_type) => {
+                write!(f, "{} ({})", property_type, OBSIDIAN_TYPES_FILE)
+            }
+            ExpectedType::Majority {
+                property_type,
+                count,
+                total,
+            } => write!(f, "{} ({} of {} notes)", property_type, count, total),
+            ExpectedType::Undecided => write!(f, "no type is used by most notes"),
+        }
+    }
+}
+
+/// a property whose value has a different type than the property has elsewhere
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertyTypeMismatch {
+    pub property: String,
+    pub value: Value,
+    pub found: PropertyType,
+    pub expected: ExpectedType,
+    /// the value converted to the expected type - only when normalizing and it can be converted
+    pub fix: Option<Value>,
+    pub fixed: bool,
+}
+
+/// the type of a value the way obsidian shows it - a string only counts as a date when
+/// types.json says the property is one as obsidian shows any other string as text
+pub fn value_type(
+    value: &Value,
+    expected: Option<PropertyType>,
+    date_settings: &DateSettings,
+    timezone: &str,
+) -> Option<PropertyType> {
+    match value {
+        Value::Bool(_) => Some(PropertyType::Bool),
+        Value::Number(_) => Some(PropertyType::Number),
+        Value::Sequence(_) => Some(PropertyType::List),
+        Value::String(text) => {
+            let is_date = expected == Some(PropertyType::Date)
+                && date_settings.parse_fix_date(text, timezone).is_some();
+            Some(if is_date {
+                PropertyType::Date
+            } else {
+                PropertyType::String
+            })
+        }
+        // nothing to compare
+        Value::Null | Value::Mapping(_) | Value::Tagged(_) => None,
+    }
+}
+
+/// only conversions that keep the value - "3" to 3, "true" to true, a single value to a list
+/// and back - anything else needs a person to decide
+pub fn normalize(value: &Value, to: PropertyType) -> Option<Value> {
+    match (to, value) {
+        (PropertyType::List, Value::Sequence(_)) => None,
+        (PropertyType::List, value) => Some(Value::Sequence(vec![value.clone()])),
+        (PropertyType::Number, Value::String(text)) => {
+            let text = text.trim();
+            text.parse::<i64>().map(Value::from).ok().or_else(|| {
+                text.parse::<f64>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .map(Value::from)
+            })
+        }
+        (PropertyType::Bool, Value::String(text)) => match text.trim().to_lowercase().as_str() {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        (PropertyType::String, Value::Number(_) | Value::Bool(_)) => {
+            Some(Value::String(schema::value_text(value)))
+        }
+        (PropertyType::String, Value::Sequence(items)) => match items.as_slice() {
+            [item @ (Value::String(_) | Value::Number(_) | Value::Bool(_))] => {
+                Some(Value::String(schema::value_text(item)))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// the type every property should have - a property every note already agrees on is left out
+fn expected_types(
+    found: &[Vec<(String, Value, PropertyType)>],
+    obsidian_types: &ObsidianTypes,
+) -> HashMap<String, ExpectedType> {
+    let mut counts: HashMap<&str, HashMap<PropertyType, usize>> = HashMap::new();
+    for (property, _, property_type) in found.iter().flatten() {
+        *counts
+            .entry(property)
+            .or_default()
+            .entry(*property_type)
+            .or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .filter_map(|(property, types)| {
+            if let Some(property_type) = obsidian_types.get(property) {
+                return Some((property.to_string(), ExpectedType::Obsidian(property_type)));
+            }
+            if types.len() < 2 {
+                return None;
+            }
+
+            let total = types.values().sum();
+            let mut by_count: Vec<(PropertyType, usize)> = types.into_iter().collect();
+            by_count.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+            let expected = match by_count.as_slice() {
+                [(property_type, count), (_, runner_up), ..] if count > runner_up => {
+                    ExpectedType::Majority {
+                        property_type: *property_type,
+                        count: *count,
+                        total,
+                    }
+                }
+                _ => ExpectedType::Undecided,
+            };
+            Some((property.to_string(), expected))
+        })
+        .collect()
+}
+
+impl ObsidianRepository {
+    /// records every property value whose type disagrees with types.json or with the other
+    /// notes and - when normalize is on - converts it, unless `ok apply` found change ids in
+    /// the output file, then only the approved ones
+    pub fn check_property_types(&mut self, validated_config: &ValidatedConfig) {
+        let obsidian_types = ObsidianTypes::load(validated_config.obsidian_path());
+        let date_settings = validated_config.date_settings();
+        let timezone = validated_config.operational_timezone();
+
+        let found: Vec<Vec<(String, Value, PropertyType)>> = self
+            .markdown_files
+            .iter()
+            .map(|file| {
+                file.frontmatter
+                    .iter()
+                    .flat_map(|frontmatter| frontmatter.other_fields())
+                    .filter_map(|(property, value)| {
+                        let expected = obsidian_types.get(property);
+                        let property_type = value_type(value, expected, date_settings, timezone)?;
+                        Some((property.clone(), value.clone(), property_type))
+                    })
+                    .collect()
+            })
+            .collect();
+        let expected_types = expected_types(&found, &obsidian_types);
+        if expected_types.is_empty() {
+            return;
+        }
+
+        for (file, found) in self.markdown_files.iter_mut().zip(found) {
+            let mut mismatches: Vec<PropertyTypeMismatch> = found
+                .into_iter()
+                .filter_map(|(property, value, property_type)| {
+                    let expected = expected_types.get(&property)?;
+                    if expected.property_type() == Some(property_type) {
+                        return None;
+                    }
+                    let fix = expected
+                        .property_type()
+                        .filter(|_| validated_config.normalize_property_types())
+                        .and_then(|to| normalize(&value, to));
+                    Some(PropertyTypeMismatch {
+                        property,
+                        value,
+                        found: property_type,
+                        expected: expected.clone(),
+                        fix,
+                        fixed: false,
+                    })
+                })
+                .collect();
+            if mismatches.is_empty() {
+                continue;
+            }
+            mismatches.sort_by(|a, b| a.property.cmp(&b.property));
+
+            let Some(frontmatter) = file.frontmatter.as_mut() else {
+                continue;
+            };
+            for mismatch in &mut mismatches {
+                let approved = validated_config
+                    .approved_changes()
+                    .is_none_or(|approved| approved.contains(&mismatch.change_id(&file.path)));
+                if let (Some(value), true) = (&mismatch.fix, approved) {
+                    mismatch.fixed = frontmatter
+                        .set_property(&mismatch.property, value.clone())
+                        .is_ok();
+                }
+            }
+
+            let any_fixed = mismatches.iter().any(|mismatch| mismatch.fixed);
+            file.property_type_mismatches = mismatches;
+            if any_fixed {
+                file.mark_edited(PersistReason::PropertyTypesNormalized, timezone);
+            }
+        }
+    }
+}
@@ -0,0 +1,216 @@
+use super::*;
+use crate::approval::ApprovedChanges;
+use crate::markdown_file::PersistReason;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+fn value(text: &str) -> Value {
+    serde_yaml::from_str(text).unwrap()
+}
+
+#[test]
+fn test_parse_obsidian_types() {
+    let types = ObsidianTypes::parse(
+        r#"{"types": {"tags": "tags", "rating": "number", "done": "checkbox", "due": "datetime", "status": "text", "other": "unknown"}}"#,
+    );
+
+    assert_eq!(types.get("tags"), Some(PropertyType::List));
+    assert_eq!(types.get("rating"), Some(PropertyType::Number));
+    assert_eq!(types.get("done"), Some(PropertyType::Bool));
+    assert_eq!(types.get("due"), Some(PropertyType::Date));
+    assert_eq!(types.get("status"), Some(PropertyType::String));
+    assert_eq!(types.get("other"), None);
+    assert_eq!(ObsidianTypes::parse("not json").get("tags"), None);
+}
+
+#[test]
+fn test_value_type() {
+    let date_settings = DateSettings::default();
+    let test_cases = [
+        ("true", None, Some(PropertyType::Bool)),
+        ("3", None, Some(PropertyType::Number)),
+        ("[a, b]", None, Some(PropertyType::List)),
+        ("text", None, Some(PropertyType::String)),
+        // without types.json a date is just text
+        ("2024-01-15", None, Some(PropertyType::String)),
+        (
+            "2024-01-15",
+            Some(PropertyType::Date),
+            Some(PropertyType::Date),
+        ),
+        ("soon", Some(PropertyType::Date), Some(PropertyType::String)),
+        ("~", None, None),
+        ("{a: b}", None, None),
+    ];
+
+    for (text, expected_type, expected) in test_cases {
+        assert_eq!(
+            value_type(
+                &value(text),
+                expected_type,
+                &date_settings,
+                DEFAULT_TIMEZONE
+            ),
+            expected,
+            "Failed for: {}",
+            text
+        );
+    }
+}
+
+#[test]
+fn test_normalize() {
+    let test_cases = [
+        ("book", PropertyType::List, Some("[book]")),
+        ("[book]", PropertyType::List, None),
+        ("'4'", PropertyType::Number, Some("4")),
+        ("'4.5'", PropertyType::Number, Some("4.5")),
+        ("four", PropertyType::Number, None),
+        ("'True'", PropertyType::Bool, Some("true")),
+        ("yes please", PropertyType::Bool, None),
+        ("4", PropertyType::String, Some("'4'")),
+        ("[book]", PropertyType::String, Some("book")),
+        ("[a, b]", PropertyType::String, None),
+        ("text", PropertyType::Date, None),
+    ];
+
+    for (text, to, expected) in test_cases {
+        assert_eq!(
+            normalize(&value(text), to),
+            expected.map(value),
+            "Failed for: {} to {}",
+            text,
+            to
+        );
+    }
+}
+
+#[test]
+fn test_expected_types() {
+    let found = |types: &[(&str, PropertyType)]| {
+        types
+            .iter()
+            .map(|(property, property_type)| {
+                vec![(property.to_string(), Value::Null, *property_type)]
+            })
+            .collect::<Vec<_>>()
+    };
+    let obsidian_types = ObsidianTypes::parse(r#"{"types": {"tags": "tags"}}"#);
+
+    let expected = expected_types(
+        &found(&[
+            ("rating", PropertyType::Number),
+            ("rating", PropertyType::Number),
+            ("rating", PropertyType::String),
+            ("status", PropertyType::String),
+            ("status", PropertyType::String),
+            ("topic", PropertyType::String),
+            ("topic", PropertyType::List),
+            ("tags", PropertyType::List),
+        ]),
+        &obsidian_types,
+    );
+
+    assert_eq!(
+        expected.get("rating"),
+        Some(&ExpectedType::Majority {
+            property_type: PropertyType::Number,
+            count: 2,
+            total: 3,
+        })
+    );
+    // every note agrees
+    assert_eq!(expected.get("status"), None);
+    assert_eq!(expected.get("topic"), Some(&ExpectedType::Undecided));
+    // types.json is used even when every note agrees
+    assert_eq!(
+        expected.get("tags"),
+        Some(&ExpectedType::Obsidian(PropertyType::List))
+    );
+}
+
+#[test]
+fn test_check_property_types() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    let obsidian_folder = temp_dir.path().join(OBSIDIAN_HIDDEN_FOLDER);
+    fs::create_dir_all(&obsidian_folder).unwrap();
+    fs::write(
+        obsidian_folder.join(OBSIDIAN_TYPES_FILE),
+        r#"{"types": {"tags": "tags"}}"#,
+    )
+    .unwrap();
+
+    for (name, frontmatter) in [
+        ("a.md", "tags: book\nrating: 4\n"),
+        ("b.md", "tags: [book]\nrating: 5\n"),
+        ("c.md", "tags: [book]\nrating: \"3\"\n"),
+    ] {
+        TestFileBuilder::new()
+            .with_matching_dates(test_date)
+            .with_custom_frontmatter(frontmatter.to_string())
+            .create(&temp_dir, name);
+    }
+    let config = |normalize: bool, approved_changes: Option<ApprovedChanges>| {
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+            builder
+                .normalize_property_types(normalize)
+                .approved_changes(approved_changes);
+        })
+        .unwrap()
+    };
+    let mismatches = |repository: &ObsidianRepository, name: &str| {
+        repository
+            .markdown_files
+            .iter()
+            .find(|file| file.path.ends_with(name))
+            .unwrap()
+            .property_type_mismatches
+            .clone()
+    };
+
+    // report only
+    let repository = ObsidianRepository::new(&config(false, None)).unwrap();
+    let a = mismatches(&repository, "a.md");
+    assert_eq!(a.len(), 1);
+    assert_eq!(a[0].property, "tags");
+    assert_eq!(a[0].expected, ExpectedType::Obsidian(PropertyType::List));
+    assert_eq!(a[0].fix, None);
+    assert!(mismatches(&repository, "b.md").is_empty());
+    let c = mismatches(&repository, "c.md");
+    assert_eq!(c.len(), 1);
+    assert_eq!(c[0].property, "rating");
+    assert_eq!(c[0].found, PropertyType::String);
+
+    // normalized but not approved
+    let repository =
+        ObsidianRepository::new(&config(true, ApprovedChanges::parse("[ ] ok-0123456789")))
+            .unwrap();
+    let c = mismatches(&repository, "c.md");
+    assert_eq!(c[0].fix, Some(value("3")));
+    assert!(!c[0].fixed);
+
+    let mut repository = ObsidianRepository::new(&config(true, None)).unwrap();
+    let c = mismatches(&repository, "c.md");
+    assert!(c[0].fixed);
+    let file = repository
+        .markdown_files
+        .iter()
+        .find(|file| file.path.ends_with("c.md"))
+        .unwrap();
+    assert!(file
+        .persist_reasons
+        .contains(&PersistReason::PropertyTypesNormalized));
+
+    repository.persist().unwrap();
+    let a = fs::read_to_string(temp_dir.path().join("a.md")).unwrap();
+    assert!(
+        a.contains("tags:\n- book") || a.contains("tags: [book]"),
+        "{}",
+        a
+    );
+    let c = fs::read_to_string(temp_dir.path().join("c.md")).unwrap();
+    assert!(c.contains("rating: 3\n"), "{}", c);
+}
@@ -1,17 +1,25 @@
 mod ambiguous_matches_report;
 mod back_populate_report;
+mod check_summary;
 mod duplicate_images_report;
 mod frontmatter_issues_report;
 mod incompatible_image_report;
 mod invalid_wikilink_report;
+mod json_report;
 mod missing_references_report;
+mod patch_file;
 mod persist_reasons_report;
+mod property_changes_report;
+mod property_types_report;
+mod schema_violations_report;
+mod simplify_wikilinks_report;
 mod unreferenced_images_report;
 
 mod report_writer;
 
 pub use report_writer::*;
 
+use crate::cli::RunMode;
 use crate::constants::*;
 use crate::image_file::ImageFileState;
 use crate::markdown_file::ImageLinkState;
@@ -27,20 +35,46 @@ impl ObsidianRepository {
     pub fn write_reports(
         &self,
         validated_config: &ValidatedConfig,
+        run_mode: RunMode,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let writer = OutputFileWriter::new(validated_config.output_folder())?;
 
         self.write_execution_start(validated_config, &writer)?; // done
-        self.write_frontmatter_issues_report(&writer)?; // done
 
-        self.write_image_reports(validated_config, &writer)?;
-        self.write_back_populate_reports(validated_config, &writer)?;
+        if run_mode.writes_date_reports() {
+            self.write_frontmatter_issues_report(validated_config, &writer)?; // done
+            self.write_property_changes_report(validated_config, &writer)?;
+            self.write_property_types_report(validated_config, &writer)?;
+            self.write_schema_violations_report(validated_config, &writer)?;
+        }
+
+        // with both image features switched off nothing would happen to the images being reported
+        if run_mode.writes_image_reports()
+            && (validated_config.image_deletion_enabled()
+                || validated_config.image_references_enabled())
+        {
+            self.write_image_reports(validated_config, &writer)?;
+        }
+
+        if run_mode.writes_wikilink_reports() {
+            self.write_simplify_wikilinks_report(&writer)?;
+            self.write_back_populate_reports(validated_config, &writer)?;
+        }
 
         // this report is slightly duplicative - i.e., outputting when
         // back populate or image references are updated - which already have reports
         // but it does uniquely represent any date changes so that's helpful
         // and mostly it acts as an audit
-        self.write_persist_reasons_report(validated_config, &writer)?; // done
+        if run_mode.writes_date_reports() {
+            self.write_persist_reasons_report(validated_config, &writer)?; // done
+        }
+
+        // the json report always has everything - consumers can filter it themselves
+        self.write_json_report(validated_config)?;
+
+        if validated_config.write_patch() {
+            self.write_patch_file(validated_config)?;
+        }
 
         Ok(())
     }
@@ -50,29 +84,33 @@ impl ObsidianRepository {
         validated_config: &ValidatedConfig,
         writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        // Check for unambiguous matches in files_to_persist
+        // Check for unambiguous matches in files_to_persist - or ones link_density skipped
         let has_back_populate_entries = self
             .markdown_files
             .files_to_persist()
             .iter()
-            .any(|file| file.has_unambiguous_matches());
-
-        // Check for ambiguous matches globally
-        let has_ambiguous_matches = self
-            .markdown_files
-            .iter()
-            .any(|file| file.has_ambiguous_matches());
+            .any(|file| file.has_unambiguous_matches())
+            || self
+                .markdown_files
+                .iter()
+                .any(|file| !file.matches.skipped.is_empty());
+
+        // Check for ambiguous matches globally - resolved ones are reported with them
+        let has_ambiguous_matches = self.markdown_files.iter().any(|file| {
+            file.has_ambiguous_matches()
+                || file
+                    .matches
+                    .unambiguous
+                    .iter()
+                    .any(|m| m.resolved_by.is_some())
+        });
 
         // Check for invalid wikilinks globally
         let has_invalid_wikilinks = self.markdown_files.iter().any(|file| {
-            file.wikilinks.invalid.iter().any(|wikilink| {
-                !matches!(
-                    wikilink.reason,
-                    InvalidWikilinkReason::EmailAddress
-                        | InvalidWikilinkReason::Tag
-                        | InvalidWikilinkReason::RawHttpLink
-                )
-            })
+            file.wikilinks
+                .invalid
+                .iter()
+                .any(|wikilink| is_reportable_invalid_wikilink(&wikilink.reason))
         });
 
         // Write the report header if any type of entry exists
@@ -88,7 +126,7 @@ impl ObsidianRepository {
             }
 
             if has_back_populate_entries {
-                self.write_back_populate_report(writer)?;
+                self.write_back_populate_report(validated_config, writer)?;
             }
         }
 
@@ -110,10 +148,10 @@ impl ObsidianRepository {
                     | ImageFileState::Incompatible { .. }
             )
         }) || self.markdown_files.files_to_persist().iter().any(|file| {
-            file.image_links
+            !file
+                .image_links
                 .filter_by_variant(ImageLinkState::Missing)
-                .len()
-                > 0
+                .is_empty()
         });
 
         if has_report_entries {
@@ -180,6 +218,14 @@ impl ObsidianRepository {
             writer.writeln("", MODE_APPLY_CHANGES_OFF)?;
         }
 
+        let disabled_features = validated_config.disabled_features();
+        if !disabled_features.is_empty() {
+            writer.writeln(
+                "",
+                &format!("{} {}", MODE_DISABLED, disabled_features.join(", ")),
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -205,6 +251,16 @@ fn write_back_populate_report_header(
     Ok(())
 }
 
+// emails, tags and raw http links show up as invalid wikilinks but aren't problems to report
+fn is_reportable_invalid_wikilink(reason: &InvalidWikilinkReason) -> bool {
+    !matches!(
+        reason,
+        InvalidWikilinkReason::EmailAddress
+            | InvalidWikilinkReason::Tag
+            | InvalidWikilinkReason::RawHttpLink
+    )
+}
+
 fn format_wikilink(path: &Path, obsidian_path: &Path, use_full_filename: bool) -> String {
     let relative_path = path.strip_prefix(obsidian_path).unwrap_or(path);
     let display_name = if use_full_filename {
@@ -141,6 +141,75 @@ impl ReportDefinition for AmbiguousMatchesTable {
     }
 }
 
+struct ResolvedAmbiguousMatchesTable;
+
+impl ReportDefinition for ResolvedAmbiguousMatchesTable {
+    type Item = BackPopulateMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec!["file name", "line", TEXT, WILL_REPLACE_WITH, RESOLVED_BY]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(&self, items: &[Self::Item], _: Option<&ValidatedConfig>) -> Vec<Vec<String>> {
+        let mut sorted: Vec<&BackPopulateMatch> = items.iter().collect();
+        sorted.sort_by_key(|m| (m.relative_path.to_lowercase(), m.line_number, m.position));
+
+        sorted
+            .into_iter()
+            .map(|m| {
+                let file_stem = Path::new(&m.relative_path)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
+                let replacement = if m.in_markdown_table {
+                    m.replacement.clone()
+                } else {
+                    utils::escape_pipe(&m.replacement)
+                };
+
+                vec![
+                    file_stem.to_wikilink(),
+                    m.line_number.to_string(),
+                    m.found_text.clone(),
+                    replacement,
+                    m.resolved_by
+                        .map(|rule| rule.to_string())
+                        .unwrap_or_default(),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(RESOLVED_AMBIGUOUS_MATCHES.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&str> = items.iter().map(|m| m.relative_path.as_str()).collect();
+
+        DescriptionBuilder::new()
+            .pluralize_with_count(Phrase::Match(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text(WILL_BE_BACK_POPULATED)
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL3
+    }
+}
+
 impl ObsidianRepository {
     pub fn write_ambiguous_matches_report(
         &self,
@@ -148,6 +217,18 @@ impl ObsidianRepository {
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         writer.writeln(LEVEL2, MATCHES_AMBIGUOUS)?;
 
+        // the back populate tables show these too - this says which rule picked the target
+        let resolved: Vec<BackPopulateMatch> = self
+            .markdown_files
+            .files_to_persist()
+            .unambiguous_matches()
+            .into_iter()
+            .filter(|m| m.resolved_by.is_some())
+            .collect();
+        if !resolved.is_empty() {
+            ReportWriter::new(resolved).write(&ResolvedAmbiguousMatchesTable, writer)?;
+        }
+
         // Create a map to group ambiguous matches by their display text (case-insensitive)
         let mut matches_by_text: HashMap<String, Vec<BackPopulateMatch>> = HashMap::new();
 
@@ -156,7 +237,7 @@ impl ObsidianRepository {
         /*.files_to_persist()*/
         {
             for match_info in &markdown_file.matches.ambiguous {
-                let key = match_info.found_text.to_lowercase();
+                let key = utils::fold_case(&match_info.found_text);
                 matches_by_text
                     .entry(key)
                     .or_default()
@@ -167,7 +248,7 @@ impl ObsidianRepository {
         // Second pass: collect targets for each found text
         let mut targets_by_text: HashMap<String, HashSet<String>> = HashMap::new();
         for wikilink in &self.wikilinks_sorted {
-            if let Some(matches) = matches_by_text.get(&wikilink.display_text.to_lowercase()) {
+            if let Some(matches) = matches_by_text.get(&utils::fold_case(&wikilink.display_text)) {
                 targets_by_text
                     .entry(matches[0].found_text.clone())
                     .or_default()
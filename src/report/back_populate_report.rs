@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 use std::error::Error;
 use std::path::Path;
 
+use crate::approval;
 use crate::constants::*;
 use crate::markdown_file::BackPopulateMatch;
 use crate::obsidian_repository::ObsidianRepository;
@@ -15,6 +16,8 @@ struct BackPopulateTable {
     display_text: String,
     total_occurrences: usize,
     file_count: usize,
+    /// occurrences link_density left alone
+    skipped: usize,
 }
 
 impl ReportDefinition for BackPopulateTable {
@@ -22,6 +25,7 @@ impl ReportDefinition for BackPopulateTable {
 
     fn headers(&self) -> Vec<&str> {
         vec![
+            APPROVE,
             "file name",
             "line",
             TEXT,
@@ -33,6 +37,7 @@ impl ReportDefinition for BackPopulateTable {
 
     fn alignments(&self) -> Vec<ColumnAlignment> {
         vec![
+            ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Right,
             ColumnAlignment::Left,
@@ -64,6 +69,7 @@ impl ReportDefinition for BackPopulateTable {
                 };
 
                 table_rows.push(vec![
+                    approval::task_checkbox(&line_info.change_id),
                     file_stem.to_wikilink(),
                     line_info.line_number.to_string(),
                     utils::escape_pipe(&highlighted_line),
@@ -78,11 +84,14 @@ impl ReportDefinition for BackPopulateTable {
     }
 
     fn title(&self) -> Option<String> {
-        let stats = DescriptionBuilder::new()
+        let mut stats = DescriptionBuilder::new()
             .pluralize_with_count(Phrase::Time(self.total_occurrences))
             .text(IN)
-            .pluralize_with_count(Phrase::File(self.file_count))
-            .build();
+            .pluralize_with_count(Phrase::File(self.file_count));
+        if self.skipped > 0 {
+            stats = stats.no_space(COMMA).number(self.skipped).text(SKIPPED);
+        }
+        let stats = stats.build();
 
         let title = DescriptionBuilder::new()
             .text(FOUND)
@@ -106,6 +115,7 @@ impl ReportDefinition for BackPopulateTable {
 impl ObsidianRepository {
     pub fn write_back_populate_report(
         &self,
+        validated_config: &ValidatedConfig,
         writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let matches = self.markdown_files.files_to_persist().unambiguous_matches();
@@ -130,10 +140,29 @@ impl ObsidianRepository {
 
         writer.writeln("", &header_message)?;
 
+        let mut skipped_by_text: HashMap<String, usize> = HashMap::new();
+        for skipped in self
+            .markdown_files
+            .iter()
+            .flat_map(|file| &file.matches.skipped)
+        {
+            *skipped_by_text
+                .entry(utils::fold_case(&skipped.found_text))
+                .or_default() += 1;
+        }
+        if !skipped_by_text.is_empty() {
+            let skipped_message = DescriptionBuilder::new()
+                .pluralize_with_count(Phrase::Match(skipped_by_text.values().sum()))
+                .text(SKIPPED_BY_LINK_DENSITY)
+                .text(&validated_config.link_density().to_string())
+                .build();
+            writer.writeln("", &skipped_message)?;
+        }
+
         // Group matches by display text (case-insensitive)
         let mut matches_by_text: HashMap<String, Vec<BackPopulateMatch>> = HashMap::new();
         for match_info in matches {
-            let key = match_info.found_text.to_lowercase();
+            let key = utils::fold_case(&match_info.found_text);
             matches_by_text
                 .entry(key)
                 .or_default()
@@ -158,6 +187,7 @@ impl ObsidianRepository {
                 display_text: display_text.clone(),
                 total_occurrences,
                 file_count: file_paths.len(),
+                skipped: skipped_by_text.get(&key).copied().unwrap_or_default(),
             };
 
             let report = ReportWriter::new(group_matches.clone());
@@ -178,6 +208,7 @@ struct ConsolidatedMatch {
 
 #[derive(Debug, Clone)]
 struct LineInfo {
+    change_id: String,
     line_number: usize,
     line_text: String,
     positions: Vec<usize>,
@@ -190,7 +221,8 @@ fn consolidate_matches(matches: &[BackPopulateMatch]) -> Vec<ConsolidatedMatch>
     for match_info in matches {
         let key = (match_info.relative_path.clone(), match_info.line_number);
 
-        let line_info = line_map.entry(key).or_insert(LineInfo {
+        let line_info = line_map.entry(key).or_insert_with(|| LineInfo {
+            change_id: match_info.change_id(),
             line_number: match_info.line_number,
             line_text: match_info.line_text.clone(),
             positions: Vec::new(),
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod check_summary_tests;
+
+use crate::constants::*;
+use crate::image_file::ImageFileState;
+use crate::markdown_file::ImageLinkState;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::report::{is_reportable_invalid_wikilink, DescriptionBuilder};
+use crate::utils::VecEnumFilter;
+use std::fmt;
+
+/// what `ok check` found - counts per category plus the exit code they map to
+#[derive(Debug, Default, PartialEq)]
+pub struct CheckSummary {
+    pub pending_back_populate: usize,
+    pub ambiguous_matches: usize,
+    pub invalid_wikilinks: usize,
+    pub image_problems: usize,
+    pub frontmatter_issues: usize,
+}
+
+impl CheckSummary {
+    pub fn exit_code(&self) -> u8 {
+        [
+            (self.pending_back_populate, EXIT_PENDING_BACK_POPULATE),
+            (self.ambiguous_matches, EXIT_AMBIGUOUS_MATCHES),
+            (self.invalid_wikilinks, EXIT_INVALID_WIKILINKS),
+            (self.image_problems, EXIT_IMAGE_PROBLEMS),
+            (self.frontmatter_issues, EXIT_FRONTMATTER_ISSUES),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .fold(0, |code, (_, bit)| code | bit)
+    }
+}
+
+impl fmt::Display for CheckSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = [
+            (CHECK_PENDING_BACK_POPULATE, self.pending_back_populate),
+            (CHECK_AMBIGUOUS_MATCHES, self.ambiguous_matches),
+            (CHECK_INVALID_WIKILINKS, self.invalid_wikilinks),
+            (CHECK_IMAGE_PROBLEMS, self.image_problems),
+            (CHECK_FRONTMATTER_ISSUES, self.frontmatter_issues),
+        ];
+
+        for (label, count) in lines {
+            writeln!(f, "{:<24}{}", label, count)?;
+        }
+
+        let exit_code = self.exit_code();
+        if exit_code == 0 {
+            write!(f, "{}", CHECK_CLEAN)
+        } else {
+            write!(f, "{} {}", CHECK_EXIT_CODE, exit_code)
+        }
+    }
+}
+
+impl ObsidianRepository {
+    pub fn check_summary(&self) -> CheckSummary {
+        let files_to_persist = self.markdown_files.files_to_persist();
+
+        let image_files_with_problems = self
+            .image_files
+            .iter()
+            .filter(|image_file| {
+                matches!(
+                    image_file.image_state,
+                    ImageFileState::Unreferenced
+                        | ImageFileState::Duplicate { .. }
+                        | ImageFileState::Incompatible { .. }
+                )
+            })
+            .count();
+
+        let missing_image_references: usize = self
+            .markdown_files
+            .iter()
+            .map(|file| {
+                file.image_links
+                    .filter_by_variant(ImageLinkState::Missing)
+                    .len()
+            })
+            .sum();
+
+        CheckSummary {
+            pending_back_populate: files_to_persist
+                .iter()
+                .map(|file| file.matches.unambiguous.len())
+                .sum(),
+            ambiguous_matches: self
+                .markdown_files
+                .iter()
+                .map(|file| file.matches.ambiguous.len())
+                .sum(),
+            invalid_wikilinks: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| &file.wikilinks.invalid)
+                .filter(|wikilink| is_reportable_invalid_wikilink(&wikilink.reason))
+                .count(),
+            image_problems: image_files_with_problems + missing_image_references,
+            frontmatter_issues: self
+                .markdown_files
+                .iter()
+                .filter(|file| {
+                    file.frontmatter_error.is_some() || file.frontmatter_repair.is_some()
+                })
+                .count(),
+        }
+    }
+
+    pub fn print_check_summary(&self) -> CheckSummary {
+        let summary = self.check_summary();
+
+        let changeset = DescriptionBuilder::new()
+            .pluralize_with_count(Phrase::File(self.markdown_files.files_to_persist().len()))
+            .text(IN_CHANGESET)
+            .build();
+
+        println!("{}", changeset);
+        println!("{}", summary);
+        summary
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+#[test]
+fn test_exit_code_combines_categories() {
+    assert_eq!(CheckSummary::default().exit_code(), 0);
+
+    let summary = CheckSummary {
+        pending_back_populate: 3,
+        ..Default::default()
+    };
+    assert_eq!(summary.exit_code(), EXIT_PENDING_BACK_POPULATE);
+
+    let summary = CheckSummary {
+        ambiguous_matches: 1,
+        invalid_wikilinks: 2,
+        image_problems: 0,
+        frontmatter_issues: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        summary.exit_code(),
+        EXIT_AMBIGUOUS_MATCHES | EXIT_INVALID_WIKILINKS | EXIT_FRONTMATTER_ISSUES
+    );
+}
+
+#[test]
+fn test_check_summary_counts() {
+    let temp_dir = TempDir::new().unwrap();
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("I like tomato\nthis is [[broken|link|here]]\n![[missing.png]]".to_string())
+        .create(&temp_dir, "recipe.md");
+
+    TestFileBuilder::new()
+        .with_custom_frontmatter("invalid: [yaml".to_string())
+        .create(&temp_dir, "bad frontmatter.md");
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let repository = ObsidianRepository::new(&config).unwrap();
+
+    assert_eq!(
+        repository.check_summary(),
+        CheckSummary {
+            pending_back_populate: 1,
+            ambiguous_matches: 0,
+            invalid_wikilinks: 1,
+            image_problems: 1,
+            frontmatter_issues: 1,
+        }
+    );
+}
@@ -1,3 +1,4 @@
+use crate::approval;
 use crate::constants::*;
 use crate::image_file::{ImageFile, ImageFileState, ImageHash};
 use crate::markdown_files::MarkdownFiles;
@@ -20,6 +21,7 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
 
     fn headers(&self) -> Vec<&str> {
         vec![
+            APPROVE,
             THUMBNAIL,
             IMAGE_FILE,
             TYPE,
@@ -37,6 +39,7 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
             ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Left,
+            ColumnAlignment::Left,
             ColumnAlignment::Right,
             ColumnAlignment::Right,
             ColumnAlignment::Left,
@@ -60,12 +63,17 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
             let thumbnail = format!("![[{}\\|{}]]", filename, THUMBNAIL_WIDTH);
             let image_link = format!("[[{}]]", filename);
 
-            let (image_type, action, base_reference_update) = match &image.image_state {
-                ImageFileState::DuplicateKeeper { .. } => {
-                    ("keeper", NO_CHANGE.to_string(), NO_CHANGE.to_string())
-                }
+            let (approve, image_type, action, base_reference_update) = match &image.image_state {
+                ImageFileState::DuplicateKeeper { .. } => (
+                    String::new(),
+                    "keeper",
+                    NO_CHANGE.to_string(),
+                    NO_CHANGE.to_string(),
+                ),
                 ImageFileState::Duplicate { .. } => {
-                    let action = if config.apply_changes() {
+                    let action = if !image.delete {
+                        NO_CHANGE.to_string()
+                    } else if config.apply_changes() {
                         DELETED.to_string()
                     } else {
                         WILL_DELETE.to_string()
@@ -78,13 +86,24 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
                         UNKNOWN.to_string()
                     };
 
-                    ("duplicate", action, reference_update)
+                    (
+                        approval::task_checkbox(&image.change_id()),
+                        "duplicate",
+                        action,
+                        reference_update,
+                    )
                 }
-                _ => ("unknown", UNKNOWN.to_string(), UNKNOWN.to_string()),
+                _ => (
+                    String::new(),
+                    "unknown",
+                    UNKNOWN.to_string(),
+                    UNKNOWN.to_string(),
+                ),
             };
 
             if image.markdown_file_references.is_empty() {
                 rows.push(vec![
+                    approve.clone(),
                     thumbnail.clone(),
                     image_link.clone(),
                     image_type.to_string(),
@@ -125,6 +144,7 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
                         .unwrap_or((String::new(), String::new()));
 
                     rows.push(vec![
+                        approve.clone(),
                         thumbnail.clone(),
                         image_link.clone(),
                         image_type.to_string(),
@@ -141,7 +161,7 @@ impl ReportDefinition for DuplicateImagesTable<'_> {
                 }
             }
         }
-        rows.sort_by(|a, b| a[1].cmp(&b[1]));
+        rows.sort_by(|a, b| a[2].cmp(&b[2]));
 
         rows
     }
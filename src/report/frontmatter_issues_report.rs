@@ -1,6 +1,9 @@
+use crate::approval;
 use crate::constants::*;
+use crate::frontmatter_repair::FrontmatterRepair;
 use crate::obsidian_repository::ObsidianRepository;
 use crate::report::{DescriptionBuilder, ReportDefinition, ReportWriter};
+use crate::utils;
 use crate::utils::{ColumnAlignment, OutputFileWriter};
 use crate::validated_config::ValidatedConfig;
 use crate::wikilink::ToWikilink;
@@ -57,18 +60,96 @@ impl ReportDefinition for FrontmatterIssuesTable {
     }
 }
 
+pub struct FrontmatterRepairsTable;
+
+impl ReportDefinition for FrontmatterRepairsTable {
+    type Item = (PathBuf, FrontmatterRepair);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![APPROVE, FILE, REPAIRS, BEFORE, AFTER]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        config: Option<&ValidatedConfig>,
+    ) -> Vec<Vec<String>> {
+        let config = config.expect(CONFIG_EXPECT);
+        // a table cell is one line
+        let yaml = |yaml: &str| {
+            yaml.lines()
+                .map(|line| utils::escape_pipe(&utils::escape_brackets(line)))
+                .collect::<Vec<_>>()
+                .join("<br>")
+        };
+
+        items
+            .iter()
+            .map(|(path, repair)| {
+                vec![
+                    approval::task_checkbox(&repair.change_id(path)),
+                    crate::report::format_wikilink(path, config.obsidian_path(), false),
+                    repair
+                        .repairs
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    yaml(&repair.before),
+                    yaml(&repair.after),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(FRONTMATTER_REPAIRS.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::File(items.len()))
+            .pluralize(Phrase::With(items.len()))
+            .text(FRONTMATTER)
+            .text(THAT_OK_CAN_REPAIR)
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL1
+    }
+}
+
 impl ObsidianRepository {
     pub fn write_frontmatter_issues_report(
         &self,
+        config: &ValidatedConfig,
         writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
         let report = ReportWriter::new(self.collect_frontmatter_issues());
-        report.write(&FrontmatterIssuesTable, writer)
+        report.write(&FrontmatterIssuesTable, writer)?;
+
+        let report =
+            ReportWriter::new(self.collect_frontmatter_repairs()).with_validated_config(config);
+        report.write(&FrontmatterRepairsTable, writer)
     }
 
+    /// a frontmatter ok can repair is reported with the repairs
     fn collect_frontmatter_issues(&self) -> Vec<(PathBuf, String)> {
         self.markdown_files
             .iter()
+            .filter(|info| info.frontmatter_repair.is_none())
             .filter_map(|info| {
                 info.frontmatter_error
                     .as_ref()
@@ -76,4 +157,15 @@ impl ObsidianRepository {
             })
             .collect()
     }
+
+    fn collect_frontmatter_repairs(&self) -> Vec<(PathBuf, FrontmatterRepair)> {
+        self.markdown_files
+            .iter()
+            .filter_map(|info| {
+                info.frontmatter_repair
+                    .as_ref()
+                    .map(|repair| (info.path.clone(), repair.clone()))
+            })
+            .collect()
+    }
 }
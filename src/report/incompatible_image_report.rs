@@ -1,3 +1,4 @@
+use crate::approval;
 use crate::constants::*;
 use crate::image_file::{ImageFile, ImageFileState, IncompatibilityReason};
 use crate::markdown_file::ImageLinkState;
@@ -18,7 +19,9 @@ impl ReportDefinition for IncompatibleImagesReport<'_> {
     type Item = ImageFile;
 
     fn headers(&self) -> Vec<&str> {
-        vec![IMAGE_FILE, TYPE, ACTION, FILE, LINE, POSITION, ACTION]
+        vec![
+            APPROVE, IMAGE_FILE, TYPE, ACTION, FILE, LINE, POSITION, ACTION,
+        ]
     }
 
     fn alignments(&self) -> Vec<ColumnAlignment> {
@@ -27,6 +30,7 @@ impl ReportDefinition for IncompatibleImagesReport<'_> {
             ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Left,
+            ColumnAlignment::Left,
             ColumnAlignment::Right,
             ColumnAlignment::Right,
             ColumnAlignment::Left,
@@ -62,6 +66,7 @@ impl ReportDefinition for IncompatibleImagesReport<'_> {
 
             if image.markdown_file_references.is_empty() {
                 rows.push(vec![
+                    approval::task_checkbox(&image.change_id()),
                     image_file_link.clone(),
                     incompatibility_type.to_string(),
                     WILL_DELETE.to_string(),
@@ -93,6 +98,7 @@ impl ReportDefinition for IncompatibleImagesReport<'_> {
                             .unwrap_or(("".to_string(), "".to_string()));
 
                         rows.push(vec![
+                            approval::task_checkbox(&image.change_id()),
                             image_file_link.clone(),
                             incompatibility_type.to_string(),
                             WILL_DELETE.to_string(),
@@ -1,10 +1,12 @@
 use crate::constants::*;
 use crate::obsidian_repository::ObsidianRepository;
-use crate::report::{DescriptionBuilder, ReportDefinition, ReportWriter};
+use crate::report::{
+    is_reportable_invalid_wikilink, DescriptionBuilder, ReportDefinition, ReportWriter,
+};
 use crate::utils;
 use crate::utils::{ColumnAlignment, OutputFileWriter};
 use crate::validated_config::ValidatedConfig;
-use crate::wikilink::{InvalidWikilink, InvalidWikilinkReason, ToWikilink};
+use crate::wikilink::{InvalidWikilink, ToWikilink};
 use itertools::Itertools;
 use std::error::Error;
 use std::ffi::OsStr;
@@ -100,14 +102,7 @@ impl ObsidianRepository {
                     .wikilinks
                     .invalid
                     .iter()
-                    .filter(|wikilink| {
-                        !matches!(
-                            wikilink.reason,
-                            InvalidWikilinkReason::EmailAddress
-                                | InvalidWikilinkReason::Tag
-                                | InvalidWikilinkReason::RawHttpLink
-                        )
-                    })
+                    .filter(|wikilink| is_reportable_invalid_wikilink(&wikilink.reason))
                     .map(move |wikilink| (markdown_file.path.clone(), (*wikilink).clone()))
             })
             .collect::<Vec<_>>()
@@ -0,0 +1,509 @@
+#[cfg(test)]
+mod json_report_tests;
+
+use crate::constants::*;
+use crate::frontmatter_repair::{FrontmatterRepair, RepairKind};
+use crate::image_file::{ImageFile, ImageFileState, IncompatibilityReason};
+use crate::markdown_file::{
+    BackPopulateMatch, DateValidationIssue, MarkdownFile, PersistReason, SimplifyWikilinkMatch,
+};
+use crate::obsidian_repository::ObsidianRepository;
+use crate::property_operations::PropertyChange;
+use crate::property_types::{ExpectedType, PropertyTypeMismatch};
+use crate::report::is_reportable_invalid_wikilink;
+use crate::schema::{SchemaIssue, SchemaViolation};
+use crate::utils;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::{InvalidWikilink, InvalidWikilinkReason};
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// bump this whenever a field is renamed or removed or its meaning changes
+/// adding a field is not a breaking change
+pub const JSON_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// machine-readable counterpart to "obsidian knife output.md"
+/// every path is relative to obsidian_path and every enum is a snake_case string
+#[derive(Debug, Serialize)]
+pub struct JsonReport {
+    pub schema_version: u32,
+    pub timestamp_utc: String,
+    pub apply_changes: bool,
+    pub file_limit: Option<usize>,
+    pub files_to_persist: usize,
+    pub back_populate_matches: Vec<MatchRecord>,
+    pub ambiguous_matches: Vec<AmbiguousMatchRecord>,
+    /// left alone by link_density
+    pub skipped_back_populate_matches: Vec<MatchRecord>,
+    pub simplify_wikilink_matches: Vec<SimplifyWikilinkRecord>,
+    pub invalid_wikilinks: Vec<InvalidWikilinkRecord>,
+    pub image_files: Vec<ImageFileRecord>,
+    pub frontmatter_errors: Vec<FrontmatterErrorRecord>,
+    pub persist_reasons: Vec<PersistReasonRecord>,
+    pub schema_violations: Vec<SchemaViolationRecord>,
+    pub property_changes: Vec<PropertyChangeRecord>,
+    pub frontmatter_repairs: Vec<FrontmatterRepairRecord>,
+    pub property_type_mismatches: Vec<PropertyTypeMismatchRecord>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MatchRecord {
+    pub file: String,
+    pub line_number: usize,
+    pub position: usize,
+    pub found_text: String,
+    pub replacement: String,
+    pub in_markdown_table: bool,
+    /// the rule that picked the target when the text has several
+    pub resolved_by: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AmbiguousMatchRecord {
+    pub file: String,
+    pub line_number: usize,
+    pub position: usize,
+    pub found_text: String,
+    pub targets: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimplifyWikilinkRecord {
+    pub file: String,
+    pub line_number: usize,
+    pub position: usize,
+    pub wikilink_text: String,
+    pub replacement: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InvalidWikilinkRecord {
+    pub file: String,
+    pub line_number: usize,
+    pub content: String,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ImageFileRecord {
+    pub file: String,
+    pub state: &'static str,
+    pub reason: Option<&'static str>,
+    pub hash: String,
+    pub size: u64,
+    pub delete: bool,
+    pub referenced_by: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FrontmatterErrorRecord {
+    pub file: String,
+    pub error: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PersistReasonRecord {
+    pub file: String,
+    pub reason: &'static str,
+    pub detail: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SchemaViolationRecord {
+    pub file: String,
+    pub property: String,
+    pub issue: &'static str,
+    /// the expected type or the value that isn't allowed
+    pub detail: Option<String>,
+    /// the value the property is set to when the violation can be fixed
+    pub fix: Option<serde_yaml::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyChangeRecord {
+    pub file: String,
+    pub property: String,
+    /// None when the property is added
+    pub before: Option<serde_yaml::Value>,
+    /// None when the property is removed
+    pub after: Option<serde_yaml::Value>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FrontmatterRepairRecord {
+    pub file: String,
+    pub error: String,
+    pub repairs: Vec<&'static str>,
+    pub before: String,
+    pub after: String,
+    pub applied: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PropertyTypeMismatchRecord {
+    pub file: String,
+    pub property: String,
+    pub value: serde_yaml::Value,
+    pub found: String,
+    /// None when no type is used by most notes
+    pub expected: Option<String>,
+    /// types_json or majority
+    pub expected_from: Option<&'static str>,
+    /// the converted value when it can be converted and normalize is on
+    pub fix: Option<serde_yaml::Value>,
+}
+
+impl ObsidianRepository {
+    pub fn write_json_report(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let report = self.build_json_report(validated_config);
+        let file = File::create(validated_config.output_folder().join(OUTPUT_JSON_FILE))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &report)?;
+        Ok(())
+    }
+
+    pub fn build_json_report(&self, validated_config: &ValidatedConfig) -> JsonReport {
+        let obsidian_path = validated_config.obsidian_path();
+        let files_to_persist = self.markdown_files.files_to_persist();
+
+        JsonReport {
+            schema_version: JSON_REPORT_SCHEMA_VERSION,
+            timestamp_utc: Utc::now().to_rfc3339(),
+            apply_changes: validated_config.apply_changes(),
+            file_limit: validated_config.file_limit(),
+            files_to_persist: files_to_persist.len(),
+            back_populate_matches: files_to_persist
+                .iter()
+                .flat_map(|file| &file.matches.unambiguous)
+                .map(MatchRecord::from)
+                .collect(),
+            ambiguous_matches: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| &file.matches.ambiguous)
+                .map(|ambiguous| self.ambiguous_match_record(ambiguous))
+                .collect(),
+            skipped_back_populate_matches: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| &file.matches.skipped)
+                .map(MatchRecord::from)
+                .collect(),
+            simplify_wikilink_matches: files_to_persist
+                .iter()
+                .flat_map(|file| &file.simplify_wikilink_matches)
+                .map(SimplifyWikilinkRecord::from)
+                .collect(),
+            invalid_wikilinks: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| {
+                    file.wikilinks
+                        .invalid
+                        .iter()
+                        .filter(|wikilink| is_reportable_invalid_wikilink(&wikilink.reason))
+                        .map(move |wikilink| {
+                            InvalidWikilinkRecord::new(file, wikilink, obsidian_path)
+                        })
+                })
+                .collect(),
+            image_files: self
+                .image_files
+                .iter()
+                .map(|image_file| ImageFileRecord::new(image_file, obsidian_path))
+                .collect(),
+            frontmatter_errors: self
+                .markdown_files
+                .iter()
+                .filter_map(|file| {
+                    file.frontmatter_error
+                        .as_ref()
+                        .map(|error| FrontmatterErrorRecord {
+                            file: relative_path(&file.path, obsidian_path),
+                            error: error.to_string(),
+                        })
+                })
+                .collect(),
+            persist_reasons: files_to_persist
+                .iter()
+                .flat_map(|file| {
+                    file.persist_reasons
+                        .iter()
+                        .map(move |reason| PersistReasonRecord::new(file, reason, obsidian_path))
+                })
+                .collect(),
+            schema_violations: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| {
+                    file.schema_violations.iter().map(move |violation| {
+                        SchemaViolationRecord::new(file, violation, obsidian_path)
+                    })
+                })
+                .collect(),
+            property_changes: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| {
+                    file.property_changes
+                        .iter()
+                        .map(move |change| PropertyChangeRecord::new(file, change, obsidian_path))
+                })
+                .collect(),
+            frontmatter_repairs: self
+                .markdown_files
+                .iter()
+                .filter_map(|file| {
+                    file.frontmatter_repair
+                        .as_ref()
+                        .map(|repair| FrontmatterRepairRecord::new(file, repair, obsidian_path))
+                })
+                .collect(),
+            property_type_mismatches: self
+                .markdown_files
+                .iter()
+                .flat_map(|file| {
+                    file.property_type_mismatches.iter().map(move |mismatch| {
+                        PropertyTypeMismatchRecord::new(file, mismatch, obsidian_path)
+                    })
+                })
+                .collect(),
+        }
+    }
+
+    fn ambiguous_match_record(&self, ambiguous: &BackPopulateMatch) -> AmbiguousMatchRecord {
+        let targets: BTreeSet<String> = self
+            .wikilinks_sorted
+            .iter()
+            .filter(|wikilink| utils::eq_fold_case(&wikilink.display_text, &ambiguous.found_text))
+            .map(|wikilink| wikilink.target.clone())
+            .collect();
+
+        AmbiguousMatchRecord {
+            file: ambiguous.relative_path.clone(),
+            line_number: ambiguous.line_number,
+            position: ambiguous.position,
+            found_text: ambiguous.found_text.clone(),
+            targets: targets.into_iter().collect(),
+        }
+    }
+}
+
+impl From<&BackPopulateMatch> for MatchRecord {
+    fn from(back_populate_match: &BackPopulateMatch) -> Self {
+        MatchRecord {
+            file: back_populate_match.relative_path.clone(),
+            line_number: back_populate_match.line_number,
+            position: back_populate_match.position,
+            found_text: back_populate_match.found_text.clone(),
+            replacement: back_populate_match.replacement.clone(),
+            in_markdown_table: back_populate_match.in_markdown_table,
+            resolved_by: back_populate_match.resolved_by.map(|rule| rule.to_string()),
+        }
+    }
+}
+
+impl From<&SimplifyWikilinkMatch> for SimplifyWikilinkRecord {
+    fn from(simplify_match: &SimplifyWikilinkMatch) -> Self {
+        SimplifyWikilinkRecord {
+            file: simplify_match.relative_path.clone(),
+            line_number: simplify_match.line_number,
+            position: simplify_match.position,
+            wikilink_text: simplify_match.wikilink_text.clone(),
+            replacement: simplify_match.display_text.clone(),
+        }
+    }
+}
+
+impl InvalidWikilinkRecord {
+    fn new(file: &MarkdownFile, wikilink: &InvalidWikilink, obsidian_path: &Path) -> Self {
+        InvalidWikilinkRecord {
+            file: relative_path(&file.path, obsidian_path),
+            line_number: wikilink.line_number,
+            content: wikilink.content.clone(),
+            reason: invalid_wikilink_reason_name(&wikilink.reason),
+        }
+    }
+}
+
+impl ImageFileRecord {
+    fn new(image_file: &ImageFile, obsidian_path: &Path) -> Self {
+        let (state, reason) = match &image_file.image_state {
+            ImageFileState::Valid => ("valid", None),
+            ImageFileState::Incompatible { reason } => {
+                ("incompatible", Some(incompatibility_reason_name(reason)))
+            }
+            ImageFileState::Unreferenced => ("unreferenced", None),
+            ImageFileState::Duplicate { .. } => ("duplicate", None),
+            ImageFileState::DuplicateKeeper { .. } => ("duplicate_keeper", None),
+        };
+
+        ImageFileRecord {
+            file: relative_path(&image_file.path, obsidian_path),
+            state,
+            reason,
+            hash: image_file.hash.to_string(),
+            size: image_file.size,
+            delete: image_file.delete,
+            referenced_by: image_file
+                .markdown_file_references
+                .iter()
+                .map(|path| relative_path(path, obsidian_path))
+                .collect(),
+        }
+    }
+}
+
+impl PersistReasonRecord {
+    fn new(file: &MarkdownFile, reason: &PersistReason, obsidian_path: &Path) -> Self {
+        let (reason, detail) = match reason {
+            PersistReason::DateCreatedUpdated { reason } => {
+                ("date_created_updated", Some(date_issue_name(reason)))
+            }
+            PersistReason::DateModifiedUpdated { reason } => {
+                ("date_modified_updated", Some(date_issue_name(reason)))
+            }
+            PersistReason::DateCreatedFixApplied => ("date_created_fix_applied", None),
+            PersistReason::FrontmatterCreated => ("frontmatter_created", None),
+            PersistReason::FrontmatterRepaired => ("frontmatter_repaired", None),
+            PersistReason::BackPopulated => ("back_populated", None),
+            PersistReason::ImageReferencesModified => ("image_references_modified", None),
+            PersistReason::WikilinksSimplified => ("wikilinks_simplified", None),
+            PersistReason::SchemaFixed => ("schema_fixed", None),
+            PersistReason::PropertiesUpdated => ("properties_updated", None),
+            PersistReason::PropertyTypesNormalized => ("property_types_normalized", None),
+        };
+
+        PersistReasonRecord {
+            file: relative_path(&file.path, obsidian_path),
+            reason,
+            detail,
+        }
+    }
+}
+
+impl SchemaViolationRecord {
+    fn new(file: &MarkdownFile, violation: &SchemaViolation, obsidian_path: &Path) -> Self {
+        let (issue, detail) = match &violation.issue {
+            SchemaIssue::Missing => ("missing", None),
+            SchemaIssue::WrongType(property_type) => {
+                ("wrong_type", Some(property_type.to_string()))
+            }
+            SchemaIssue::NotAllowed(value) => ("not_allowed", Some(value.clone())),
+        };
+
+        SchemaViolationRecord {
+            file: relative_path(&file.path, obsidian_path),
+            property: violation.property.clone(),
+            issue,
+            detail,
+            fix: violation.fix.clone(),
+        }
+    }
+}
+
+impl PropertyChangeRecord {
+    fn new(file: &MarkdownFile, change: &PropertyChange, obsidian_path: &Path) -> Self {
+        PropertyChangeRecord {
+            file: relative_path(&file.path, obsidian_path),
+            property: change.property.clone(),
+            before: change.before.clone(),
+            after: change.after.clone(),
+        }
+    }
+}
+
+impl FrontmatterRepairRecord {
+    fn new(file: &MarkdownFile, repair: &FrontmatterRepair, obsidian_path: &Path) -> Self {
+        FrontmatterRepairRecord {
+            file: relative_path(&file.path, obsidian_path),
+            error: repair.error.clone(),
+            repairs: repair.repairs.iter().map(repair_kind_name).collect(),
+            before: repair.before.clone(),
+            after: repair.after.clone(),
+            applied: repair.applied,
+        }
+    }
+}
+
+impl PropertyTypeMismatchRecord {
+    fn new(file: &MarkdownFile, mismatch: &PropertyTypeMismatch, obsidian_path: &Path) -> Self {
+        let expected_from = match &mismatch.expected {
+            ExpectedType::Obsidian(_) => Some("types_json"),
+            ExpectedType::Majority { .. } => Some("majority"),
+            ExpectedType::Undecided => None,
+        };
+
+        PropertyTypeMismatchRecord {
+            file: relative_path(&file.path, obsidian_path),
+            property: mismatch.property.clone(),
+            value: mismatch.value.clone(),
+            found: mismatch.found.to_string(),
+            expected: mismatch
+                .expected
+                .property_type()
+                .map(|property_type| property_type.to_string()),
+            expected_from,
+            fix: mismatch.fix.clone(),
+        }
+    }
+}
+
+fn relative_path(path: &Path, obsidian_path: &Path) -> String {
+    path.strip_prefix(obsidian_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+// the names below are part of the schema - don't derive them from the enum variant names
+// so that renaming a variant doesn't silently break consumers
+
+fn invalid_wikilink_reason_name(reason: &InvalidWikilinkReason) -> &'static str {
+    match reason {
+        InvalidWikilinkReason::DoubleAlias => "double_alias",
+        InvalidWikilinkReason::EmptyWikilink => "empty_wikilink",
+        InvalidWikilinkReason::EmailAddress => "email_address",
+        InvalidWikilinkReason::NestedOpening => "nested_opening",
+        InvalidWikilinkReason::RawHttpLink => "raw_http_link",
+        InvalidWikilinkReason::Tag => "tag",
+        InvalidWikilinkReason::UnclosedInlineCode => "unclosed_inline_code",
+        InvalidWikilinkReason::UnmatchedClosing => "unmatched_closing",
+        InvalidWikilinkReason::UnmatchedMarkdownLinkOpening => "unmatched_markdown_link_opening",
+        InvalidWikilinkReason::UnmatchedOpening => "unmatched_opening",
+        InvalidWikilinkReason::UnmatchedSingleInWikilink => "unmatched_single_in_wikilink",
+    }
+}
+
+fn incompatibility_reason_name(reason: &IncompatibilityReason) -> &'static str {
+    match reason {
+        IncompatibilityReason::TiffFormat => "tiff_format",
+        IncompatibilityReason::ZeroByte => "zero_byte",
+    }
+}
+
+fn date_issue_name(issue: &DateValidationIssue) -> &'static str {
+    match issue {
+        DateValidationIssue::Missing => "missing",
+        DateValidationIssue::InvalidDateFormat => "invalid_date_format",
+        DateValidationIssue::InvalidWikilink => "invalid_wikilink",
+        DateValidationIssue::FileSystemMismatch => "file_system_mismatch",
+    }
+}
+
+fn repair_kind_name(kind: &RepairKind) -> &'static str {
+    match kind {
+        RepairKind::DuplicateDelimiter => "duplicate_delimiter",
+        RepairKind::DuplicateKey => "duplicate_key",
+        RepairKind::MissingClosingDelimiter => "missing_closing_delimiter",
+        RepairKind::TabIndentation => "tab_indentation",
+        RepairKind::UnquotedValue => "unquoted_value",
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::cli::RunMode;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+#[test]
+fn test_json_report_contents() {
+    let temp_dir = TempDir::new().unwrap();
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("I like tomato\nthis is [[broken|link|here]]".to_string())
+        .create(&temp_dir, "recipe.md");
+
+    TestFileBuilder::new()
+        .with_content(Vec::new())
+        .create(&temp_dir, "empty.png");
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let report = repository.build_json_report(&config);
+
+    assert_eq!(report.schema_version, JSON_REPORT_SCHEMA_VERSION);
+    assert!(!report.apply_changes);
+
+    assert_eq!(report.back_populate_matches.len(), 1);
+    let back_populate_match = &report.back_populate_matches[0];
+    assert_eq!(back_populate_match.file, "recipe.md");
+    // line numbers include the frontmatter lines
+    assert_eq!(back_populate_match.line_number, 5);
+    assert_eq!(back_populate_match.found_text, "tomato");
+    assert_eq!(back_populate_match.replacement, "[[Tomato|tomato]]");
+
+    assert_eq!(report.invalid_wikilinks.len(), 1);
+    assert_eq!(report.invalid_wikilinks[0].reason, "double_alias");
+
+    assert_eq!(report.image_files.len(), 1);
+    assert_eq!(report.image_files[0].file, "empty.png");
+    assert_eq!(report.image_files[0].state, "incompatible");
+    assert_eq!(report.image_files[0].reason, Some("zero_byte"));
+
+    assert!(report
+        .persist_reasons
+        .iter()
+        .any(|record| record.file == "recipe.md" && record.reason == "back_populated"));
+}
+
+#[test]
+fn test_json_report_written_alongside_markdown_report() {
+    let temp_dir = TempDir::new().unwrap();
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("# Tomato".to_string())
+        .create(&temp_dir, "Tomato.md");
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    std::fs::create_dir_all(config.output_folder()).unwrap();
+
+    let repository = ObsidianRepository::new(&config).unwrap();
+    repository.write_reports(&config, RunMode::Report).unwrap();
+
+    let json = std::fs::read_to_string(config.output_folder().join(OUTPUT_JSON_FILE)).unwrap();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["schema_version"], JSON_REPORT_SCHEMA_VERSION);
+    for key in [
+        "back_populate_matches",
+        "ambiguous_matches",
+        "simplify_wikilink_matches",
+        "invalid_wikilinks",
+        "image_files",
+        "frontmatter_errors",
+        "persist_reasons",
+    ] {
+        assert!(value[key].is_array(), "missing array: {}", key);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::approval;
 use crate::constants::*;
 use crate::markdown_file::ImageLinkState;
 use crate::obsidian_repository::ObsidianRepository;
@@ -11,14 +12,22 @@ use std::path::PathBuf;
 pub struct MissingReferencesTable;
 
 impl ReportDefinition for MissingReferencesTable {
-    type Item = (PathBuf, String, usize, usize); // (markdown_path, extracted_filename, line, position)
+    type Item = (PathBuf, String, usize, usize, String); // (markdown_path, extracted_filename, line, position, change_id)
 
     fn headers(&self) -> Vec<&str> {
-        vec![FILE, LINE, POSITION, MISSING_IMAGE_REFERENCES, ACTION]
+        vec![
+            APPROVE,
+            FILE,
+            LINE,
+            POSITION,
+            MISSING_IMAGE_REFERENCES,
+            ACTION,
+        ]
     }
 
     fn alignments(&self) -> Vec<ColumnAlignment> {
         vec![
+            ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Right,
             ColumnAlignment::Right,
@@ -37,7 +46,7 @@ impl ReportDefinition for MissingReferencesTable {
         let mut rows: Vec<Vec<String>> = items
             .iter()
             .map(
-                |(markdown_path, extracted_filename, line_number, position)| {
+                |(markdown_path, extracted_filename, line_number, position, change_id)| {
                     let markdown_link = crate::report::format_wikilink(
                         markdown_path,
                         config.obsidian_path(),
@@ -55,6 +64,7 @@ impl ReportDefinition for MissingReferencesTable {
                     };
 
                     vec![
+                        approval::task_checkbox(change_id),
                         markdown_link,
                         line_number.to_string(),
                         position.to_string(),
@@ -65,8 +75,8 @@ impl ReportDefinition for MissingReferencesTable {
             )
             .collect();
 
-        // Sort rows by markdown link (first column)
-        rows.sort_by(|a, b| a[0].cmp(&b[0]));
+        // Sort rows by markdown link
+        rows.sort_by(|a, b| a[1].cmp(&b[1]));
         rows
     }
 
@@ -94,7 +104,7 @@ impl ObsidianRepository {
         config: &ValidatedConfig,
         writer: &OutputFileWriter,
     ) -> Result<(), Box<dyn Error + Send + Sync>> {
-        let missing_refs: Vec<(PathBuf, String, usize, usize)> = self
+        let missing_refs: Vec<(PathBuf, String, usize, usize, String)> = self
             .markdown_files
             .files_to_persist()
             .iter()
@@ -106,6 +116,7 @@ impl ObsidianRepository {
                         missing.filename.clone(),
                         missing.line_number,
                         missing.position,
+                        missing.missing_reference_change_id(&file.path),
                     )
                 })
             })
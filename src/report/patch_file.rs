@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod patch_file_tests;
+
+use crate::constants::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::utils;
+use crate::validated_config::ValidatedConfig;
+use similar::TextDiff;
+use std::error::Error;
+use std::fs;
+
+impl ObsidianRepository {
+    /// unified diff of every file in files_to_persist - frontmatter included - so the exact
+    /// result can be reviewed or applied with `git apply` from obsidian_path
+    pub fn write_patch_file(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let patch = self.build_patch(validated_config)?;
+        fs::write(
+            validated_config.output_folder().join(OUTPUT_PATCH_FILE),
+            patch,
+        )?;
+        Ok(())
+    }
+
+    pub fn build_patch(
+        &self,
+        validated_config: &ValidatedConfig,
+    ) -> Result<String, Box<dyn Error + Send + Sync>> {
+        let mut files_to_persist = self.markdown_files.files_to_persist();
+        files_to_persist.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut patch = String::new();
+        for file in files_to_persist.iter() {
+            // the file on disk is still the original - reports are written before persist
+            let before = utils::read_contents_from_file(&file.path)?;
+            let after = file.to_full_content();
+            if before == after {
+                continue;
+            }
+
+            let relative_path = file
+                .path
+                .strip_prefix(validated_config.obsidian_path())
+                .unwrap_or(&file.path)
+                .to_string_lossy()
+                .replace('\\', "/");
+
+            patch.push_str(&format!("diff --git a/{0} b/{0}\n", relative_path));
+            patch.push_str(
+                &TextDiff::from_lines(&before, &after)
+                    .unified_diff()
+                    .header(
+                        &format!("a/{}", relative_path),
+                        &format!("b/{}", relative_path),
+                    )
+                    .to_string(),
+            );
+        }
+
+        Ok(patch)
+    }
+}
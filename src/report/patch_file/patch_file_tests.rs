@@ -0,0 +1,58 @@
+use super::*;
+use crate::cli::RunMode;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use tempfile::TempDir;
+
+fn create_test_files(temp_dir: &TempDir) {
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("# Tomato".to_string())
+        .create(temp_dir, "Tomato.md");
+
+    TestFileBuilder::new()
+        .with_matching_dates(test_utils::eastern_midnight(2024, 1, 2))
+        .with_content("I like tomato\nand nothing else".to_string())
+        .create(temp_dir, "recipe.md");
+}
+
+#[test]
+fn test_patch_contents() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_files(&temp_dir);
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    let repository = ObsidianRepository::new(&config).unwrap();
+    let patch = repository.build_patch(&config).unwrap();
+
+    assert!(patch.contains("diff --git a/recipe.md b/recipe.md\n"));
+    assert!(patch.contains("--- a/recipe.md\n+++ b/recipe.md\n"));
+    assert!(patch.contains("\n-I like tomato\n"));
+    assert!(patch.contains("\n+I like [[Tomato|tomato]]\n"));
+    // back population also updates date_modified in the frontmatter
+    assert!(patch.contains("\n-date_modified: \"[[2024-01-02]]\"\n"));
+    // persist trims the trailing newline and the patch shows it
+    assert!(patch.contains("+and nothing else\n\\ No newline at end of file\n"));
+}
+
+#[test]
+fn test_patch_file_written_only_when_enabled() {
+    let temp_dir = TempDir::new().unwrap();
+    create_test_files(&temp_dir);
+
+    let config = validated_config_tests::get_test_validated_config(&temp_dir, None);
+    fs::create_dir_all(config.output_folder()).unwrap();
+    let repository = ObsidianRepository::new(&config).unwrap();
+    repository.write_reports(&config, RunMode::Report).unwrap();
+    assert!(!config.output_folder().join(OUTPUT_PATCH_FILE).exists());
+
+    let config = validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+        builder.write_patch(true);
+    })
+    .unwrap();
+    repository.write_reports(&config, RunMode::Report).unwrap();
+
+    let patch = fs::read_to_string(config.output_folder().join(OUTPUT_PATCH_FILE)).unwrap();
+    assert_eq!(patch, repository.build_patch(&config).unwrap());
+}
@@ -1,3 +1,4 @@
+use crate::approval;
 use crate::constants::*;
 use crate::markdown_file::PersistReason;
 use crate::obsidian_repository::ObsidianRepository;
@@ -5,6 +6,7 @@ use crate::report::{ReportDefinition, ReportWriter};
 use crate::utils;
 use crate::utils::{ColumnAlignment, OutputFileWriter};
 use crate::validated_config::ValidatedConfig;
+use std::collections::HashSet;
 use std::error::Error;
 use std::path::PathBuf;
 
@@ -13,13 +15,18 @@ pub struct PersistReasonsTable;
 #[derive(Clone)]
 pub struct PersistReasonData {
     back_populate_count: usize,
+    date_change_id: String,
     date_created_fix: Option<(String, String)>,
     date_validation_created: Option<(String, String)>, // (before, after)
     date_validation_modified: Option<(String, String)>,
     full_path: PathBuf, //for sorting
     image_refs_count: usize,
     parent_path: String,
+    property_change_count: usize,
+    property_type_fix_count: usize,
     reason: PersistReason,
+    schema_fix_count: usize,
+    simplify_wikilinks_count: usize,
     wikilink: String,
 }
 
@@ -27,7 +34,7 @@ impl ReportDefinition for PersistReasonsTable {
     type Item = PersistReasonData;
 
     fn headers(&self) -> Vec<&str> {
-        vec![FILE, PATH, REASON, INFO, BEFORE, AFTER]
+        vec![APPROVE, FILE, PATH, REASON, INFO, BEFORE, AFTER]
     }
 
     fn alignments(&self) -> Vec<ColumnAlignment> {
@@ -38,10 +45,17 @@ impl ReportDefinition for PersistReasonsTable {
             ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Left,
+            ColumnAlignment::Left,
         ]
     }
 
-    fn build_rows(&self, items: &[Self::Item], _: Option<&ValidatedConfig>) -> Vec<Vec<String>> {
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        config: Option<&ValidatedConfig>,
+    ) -> Vec<Vec<String>> {
+        let config = config.expect(CONFIG_EXPECT);
+        let mut date_change_ids = HashSet::new();
         items
             .iter()
             .map(|item| {
@@ -60,6 +74,9 @@ impl ReportDefinition for PersistReasonsTable {
                         let (before, after) = item.date_created_fix.clone().unwrap_or_default();
                         (before, after, String::new())
                     }
+                    PersistReason::FrontmatterCreated | PersistReason::FrontmatterRepaired => {
+                        (String::new(), String::new(), String::new())
+                    }
                     PersistReason::BackPopulated => (
                         String::new(),
                         String::new(),
@@ -70,12 +87,47 @@ impl ReportDefinition for PersistReasonsTable {
                         String::new(),
                         format!("{} instances", item.image_refs_count),
                     ),
+                    PersistReason::WikilinksSimplified => (
+                        String::new(),
+                        String::new(),
+                        format!("{} instances", item.simplify_wikilinks_count),
+                    ),
+                    PersistReason::SchemaFixed => (
+                        String::new(),
+                        String::new(),
+                        format!("{} properties", item.schema_fix_count),
+                    ),
+                    PersistReason::PropertiesUpdated => (
+                        String::new(),
+                        String::new(),
+                        format!("{} properties", item.property_change_count),
+                    ),
+                    PersistReason::PropertyTypesNormalized => (
+                        String::new(),
+                        String::new(),
+                        format!("{} properties", item.property_type_fix_count),
+                    ),
+                };
+
+                // the other reasons are approved in their own sections of the output file - and
+                // a file's date reasons share one change id so only its first row gets a checkbox
+                let approve = match &item.reason {
+                    PersistReason::DateCreatedUpdated { .. }
+                    | PersistReason::DateModifiedUpdated { .. }
+                    | PersistReason::DateCreatedFixApplied
+                    | PersistReason::FrontmatterCreated
+                        if date_change_ids.insert(&item.date_change_id) =>
+                    {
+                        approval::task_checkbox(&item.date_change_id)
+                    }
+                    _ => String::new(),
                 };
 
                 vec![
+                    approve,
                     item.wikilink.clone(),
                     item.parent_path.clone(),
-                    item.reason.to_string(),
+                    item.reason.describe(&config.date_settings().properties),
                     reason_info,
                     before,
                     after,
@@ -147,49 +199,63 @@ impl ObsidianRepository {
                     .iter()
                     .filter(|&r| matches!(r, PersistReason::ImageReferencesModified))
                     .count();
+                let simplify_wikilinks_count = file.simplify_wikilink_matches.len();
+                let property_change_count = file
+                    .property_changes
+                    .iter()
+                    .filter(|change| change.applied)
+                    .count();
+                let property_type_fix_count = file
+                    .property_type_mismatches
+                    .iter()
+                    .filter(|mismatch| mismatch.fixed)
+                    .count();
+                let schema_fix_count = file
+                    .schema_violations
+                    .iter()
+                    .filter(|violation| violation.fixed)
+                    .count();
+
+                let date_settings = config.date_settings();
+                let timezone = config.operational_timezone();
+                let file_system_created = date_settings
+                    .format_date(file.date_validation_created.file_system_date, timezone);
 
                 for reason in &file.persist_reasons {
                     let data = PersistReasonData {
+                        date_change_id: file.date_change_id(),
                         full_path: file.path.clone(), // Store full path for filtering
                         wikilink: utils::escape_pipe(&wikilink),
                         reason: reason.clone(),
                         back_populate_count,
                         image_refs_count,
                         parent_path: parent_path.clone(),
+                        property_change_count,
+                        property_type_fix_count,
+                        schema_fix_count,
+                        simplify_wikilinks_count,
                         date_validation_created: Some((
                             file.date_validation_created
                                 .frontmatter_date
                                 .clone()
                                 .unwrap_or_default(),
-                            format!(
-                                "[[{}]]",
-                                file.date_validation_created
-                                    .operational_file_system_date()
-                                    .format("%Y-%m-%d")
-                            ),
+                            file_system_created.clone(),
                         )),
                         date_validation_modified: Some((
                             file.date_validation_modified
                                 .frontmatter_date
                                 .clone()
                                 .unwrap_or_default(),
-                            format!(
-                                "[[{}]]",
-                                file.date_validation_modified
-                                    .operational_file_system_date()
-                                    .format("%Y-%m-%d")
+                            date_settings.format_date(
+                                file.date_validation_modified.file_system_date,
+                                timezone,
                             ),
                         )),
                         date_created_fix: Some((
-                            format!(
-                                "[[{}]]",
-                                file.date_validation_created
-                                    .operational_file_system_date()
-                                    .format("%Y-%m-%d")
-                            ),
+                            file_system_created.clone(),
                             file.date_created_fix
                                 .fix_date
-                                .map(|d| format!("[[{}]]", d.format("%Y-%m-%d")))
+                                .map(|d| date_settings.format_date(d, timezone))
                                 .unwrap_or_default(),
                         )),
                     };
@@ -220,7 +286,7 @@ impl ObsidianRepository {
 
         for chunk in persist_data.chunks(500) {
             let table = PersistReasonsTable;
-            let report = ReportWriter::new(chunk.to_vec());
+            let report = ReportWriter::new(chunk.to_vec()).with_validated_config(config);
 
             // Write each chunk using ReportWriter
             report.write(&table, writer)?;
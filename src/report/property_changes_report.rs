@@ -0,0 +1,101 @@
+use crate::approval;
+use crate::constants::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::property_operations::PropertyChange;
+use crate::report::{DescriptionBuilder, ReportDefinition, ReportWriter};
+use crate::schema;
+use crate::utils;
+use crate::utils::{ColumnAlignment, OutputFileWriter};
+use crate::validated_config::ValidatedConfig;
+use itertools::Itertools;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub struct PropertyChangesTable;
+
+impl ReportDefinition for PropertyChangesTable {
+    // (file path, the file's change id, change)
+    type Item = (PathBuf, String, PropertyChange);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![APPROVE, FILE, PROPERTY, BEFORE, AFTER]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        config: Option<&ValidatedConfig>,
+    ) -> Vec<Vec<String>> {
+        let config = config.expect(CONFIG_EXPECT);
+        let value = |value: &Option<serde_yaml::Value>| {
+            let text = value.as_ref().map(schema::value_text).unwrap_or_default();
+            utils::escape_pipe(&utils::escape_brackets(&text))
+        };
+
+        items
+            .iter()
+            .map(|(path, change_id, change)| {
+                // every change to a file is approved together
+                vec![
+                    approval::task_checkbox(change_id),
+                    crate::report::format_wikilink(path, config.obsidian_path(), false),
+                    change.property.clone(),
+                    value(&change.before),
+                    value(&change.after),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(PROPERTY_CHANGES.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let files = items.iter().map(|(path, ..)| path).dedup().count();
+        DescriptionBuilder::new()
+            .number(items.len())
+            .text(PROPERTY)
+            .pluralize(Phrase::Change(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(files))
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL1
+    }
+}
+
+impl ObsidianRepository {
+    pub fn write_property_changes_report(
+        &self,
+        config: &ValidatedConfig,
+        writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut changes: Vec<(PathBuf, String, PropertyChange)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|file| {
+                let change_id = file.property_change_id();
+                file.property_changes
+                    .iter()
+                    .map(move |change| (file.path.clone(), change_id.clone(), change.clone()))
+            })
+            .collect();
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let report = ReportWriter::new(changes).with_validated_config(config);
+        report.write(&PropertyChangesTable, writer)
+    }
+}
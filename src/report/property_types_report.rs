@@ -0,0 +1,123 @@
+use crate::approval;
+use crate::constants::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::property_types::PropertyTypeMismatch;
+use crate::report::{DescriptionBuilder, ReportDefinition, ReportWriter};
+use crate::schema;
+use crate::utils;
+use crate::utils::{ColumnAlignment, OutputFileWriter};
+use crate::validated_config::ValidatedConfig;
+use itertools::Itertools;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub struct PropertyTypesTable;
+
+impl ReportDefinition for PropertyTypesTable {
+    type Item = (PathBuf, PropertyTypeMismatch);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![APPROVE, FILE, PROPERTY, VALUE, TYPE, EXPECTED, ACTION]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        config: Option<&ValidatedConfig>,
+    ) -> Vec<Vec<String>> {
+        let config = config.expect(CONFIG_EXPECT);
+        let escape = |text: &str| utils::escape_pipe(&utils::escape_brackets(text));
+
+        items
+            .iter()
+            .map(|(path, mismatch)| {
+                // only conversions are approved - the rest have to be fixed by hand
+                let (approve, action) = match &mismatch.fix {
+                    Some(value) => {
+                        let action = if config.apply_changes() {
+                            SET_TO
+                        } else {
+                            WILL_BE_SET_TO
+                        };
+                        (
+                            approval::task_checkbox(&mismatch.change_id(path)),
+                            format!("{} {}", action, schema::value_text(value)),
+                        )
+                    }
+                    None => (String::new(), String::new()),
+                };
+
+                vec![
+                    approve,
+                    crate::report::format_wikilink(path, config.obsidian_path(), false),
+                    mismatch.property.clone(),
+                    escape(&schema::value_text(&mismatch.value)),
+                    mismatch.found.to_string(),
+                    mismatch.expected.to_string(),
+                    escape(&action),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(PROPERTY_TYPES.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let properties = items
+            .iter()
+            .map(|(_, mismatch)| &mismatch.property)
+            .unique()
+            .count();
+        let files = items.iter().map(|(path, _)| path).dedup().count();
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .pluralize_with_count(Phrase::Property(properties))
+            .text(WITH_INCONSISTENT_TYPES)
+            .text(IN)
+            .pluralize_with_count(Phrase::File(files))
+            .text_with_newline("")
+            .no_space(SCHEMA_FIX_BY_HAND)
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL1
+    }
+}
+
+impl ObsidianRepository {
+    pub fn write_property_types_report(
+        &self,
+        config: &ValidatedConfig,
+        writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut mismatches: Vec<(PathBuf, PropertyTypeMismatch)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|file| {
+                file.property_type_mismatches
+                    .iter()
+                    .map(|mismatch| (file.path.clone(), mismatch.clone()))
+            })
+            .collect();
+        // grouped by property so the notes that disagree are next to each other
+        mismatches.sort_by(|a, b| a.1.property.cmp(&b.1.property).then_with(|| a.0.cmp(&b.0)));
+
+        let report = ReportWriter::new(mismatches).with_validated_config(config);
+        report.write(&PropertyTypesTable, writer)
+    }
+}
@@ -0,0 +1,108 @@
+use crate::approval;
+use crate::constants::*;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::report::{DescriptionBuilder, ReportDefinition, ReportWriter};
+use crate::schema::{self, SchemaViolation};
+use crate::utils;
+use crate::utils::{ColumnAlignment, OutputFileWriter};
+use crate::validated_config::ValidatedConfig;
+use std::error::Error;
+use std::path::PathBuf;
+
+pub struct SchemaViolationsTable;
+
+impl ReportDefinition for SchemaViolationsTable {
+    type Item = (PathBuf, SchemaViolation);
+
+    fn headers(&self) -> Vec<&str> {
+        vec![APPROVE, FILE, PROPERTY, ISSUE, ACTION]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(
+        &self,
+        items: &[Self::Item],
+        config: Option<&ValidatedConfig>,
+    ) -> Vec<Vec<String>> {
+        let config = config.expect(CONFIG_EXPECT);
+
+        items
+            .iter()
+            .map(|(path, violation)| {
+                // only fixes are approved - the rest have to be fixed by hand
+                let (approve, action) = match &violation.fix {
+                    Some(value) => {
+                        let action = if config.apply_changes() {
+                            SET_TO
+                        } else {
+                            WILL_BE_SET_TO
+                        };
+                        (
+                            approval::task_checkbox(&violation.change_id(path)),
+                            format!("{} {}", action, schema::value_text(value)),
+                        )
+                    }
+                    None => (String::new(), String::new()),
+                };
+
+                vec![
+                    approve,
+                    crate::report::format_wikilink(path, config.obsidian_path(), false),
+                    violation.property.clone(),
+                    utils::escape_pipe(&violation.issue.to_string()),
+                    utils::escape_pipe(&utils::escape_brackets(&action)),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(SCHEMA_VIOLATIONS.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        DescriptionBuilder::new()
+            .text(FOUND)
+            .number(items.len())
+            .text(SCHEMA)
+            .pluralize(Phrase::Issue(items.len()))
+            .text_with_newline("")
+            .no_space(SCHEMA_FIX_BY_HAND)
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL1
+    }
+}
+
+impl ObsidianRepository {
+    pub fn write_schema_violations_report(
+        &self,
+        config: &ValidatedConfig,
+        writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let mut violations: Vec<(PathBuf, SchemaViolation)> = self
+            .markdown_files
+            .iter()
+            .flat_map(|file| {
+                file.schema_violations
+                    .iter()
+                    .map(|violation| (file.path.clone(), violation.clone()))
+            })
+            .collect();
+        violations.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.property.cmp(&b.1.property)));
+
+        let report = ReportWriter::new(violations).with_validated_config(config);
+        report.write(&SchemaViolationsTable, writer)
+    }
+}
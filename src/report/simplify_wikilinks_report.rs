@@ -0,0 +1,110 @@
+use crate::approval;
+use crate::constants::*;
+use crate::markdown_file::SimplifyWikilinkMatch;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::report::{ReportDefinition, ReportWriter};
+use crate::utils::{ColumnAlignment, OutputFileWriter};
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink::ToWikilink;
+use crate::{report, utils};
+use std::collections::HashSet;
+use std::error::Error;
+use std::path::Path;
+
+pub struct SimplifyWikilinksTable;
+
+impl ReportDefinition for SimplifyWikilinksTable {
+    type Item = SimplifyWikilinkMatch;
+
+    fn headers(&self) -> Vec<&str> {
+        vec![
+            APPROVE,
+            "file name",
+            "line",
+            TEXT,
+            RENDERS_AS,
+            WILL_REPLACE_WITH,
+        ]
+    }
+
+    fn alignments(&self) -> Vec<ColumnAlignment> {
+        vec![
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Right,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+            ColumnAlignment::Left,
+        ]
+    }
+
+    fn build_rows(&self, items: &[Self::Item], _: Option<&ValidatedConfig>) -> Vec<Vec<String>> {
+        let mut sorted_items = items.to_vec();
+        sorted_items.sort_by(|a, b| {
+            a.relative_path
+                .to_lowercase()
+                .cmp(&b.relative_path.to_lowercase())
+                .then(a.line_number.cmp(&b.line_number))
+                .then(a.position.cmp(&b.position))
+        });
+
+        sorted_items
+            .iter()
+            .map(|item| {
+                let file_stem = Path::new(&item.relative_path)
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
+
+                let highlighted_line = report::highlight_matches(
+                    &item.line_text,
+                    &[item.position],
+                    item.wikilink_text.len(),
+                );
+
+                vec![
+                    approval::task_checkbox(&item.change_id()),
+                    file_stem.to_wikilink(),
+                    item.line_number.to_string(),
+                    utils::escape_pipe(&highlighted_line),
+                    utils::escape_pipe(&item.rendered_text),
+                    utils::escape_pipe(&item.display_text),
+                ]
+            })
+            .collect()
+    }
+
+    fn title(&self) -> Option<String> {
+        Some(SIMPLIFY_WIKILINKS.to_string())
+    }
+
+    fn description(&self, items: &[Self::Item]) -> String {
+        let unique_files: HashSet<&str> = items.iter().map(|m| m.relative_path.as_str()).collect();
+
+        DescriptionBuilder::new()
+            .pluralize_with_count(Phrase::Wikilink(items.len()))
+            .text(IN)
+            .pluralize_with_count(Phrase::File(unique_files.len()))
+            .text(WILL_BE_SIMPLIFIED)
+            .build()
+    }
+
+    fn level(&self) -> &'static str {
+        LEVEL1
+    }
+}
+
+impl ObsidianRepository {
+    pub fn write_simplify_wikilinks_report(
+        &self,
+        writer: &OutputFileWriter,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let matches = self
+            .markdown_files
+            .files_to_persist()
+            .simplify_wikilink_matches();
+
+        let report = ReportWriter::new(matches);
+        report.write(&SimplifyWikilinksTable, writer)
+    }
+}
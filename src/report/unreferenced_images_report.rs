@@ -1,3 +1,4 @@
+use crate::approval;
 use crate::constants::*;
 use crate::image_file::ImageFile;
 use crate::image_file::ImageFileState;
@@ -14,7 +15,7 @@ impl ReportDefinition for UnreferencedImagesReport {
     type Item = ImageFile;
 
     fn headers(&self) -> Vec<&str> {
-        vec![THUMBNAIL, IMAGE_FILE, ACTION]
+        vec![APPROVE, THUMBNAIL, IMAGE_FILE, ACTION]
     }
 
     fn alignments(&self) -> Vec<ColumnAlignment> {
@@ -22,6 +23,7 @@ impl ReportDefinition for UnreferencedImagesReport {
             ColumnAlignment::Left,
             ColumnAlignment::Left,
             ColumnAlignment::Left,
+            ColumnAlignment::Left,
         ]
     }
 
@@ -37,13 +39,20 @@ impl ReportDefinition for UnreferencedImagesReport {
                 let sample =
                     utils::escape_pipe(format!("![[{}|{}]]", file_name, THUMBNAIL_WIDTH).as_str());
                 let file_link = format!("[[{}]]", file_name);
-                let action = if config.map_or(false, |c| c.apply_changes()) {
+                let action = if !image.delete {
+                    NO_CHANGE
+                } else if config.is_some_and(|c| c.apply_changes()) {
                     DELETED
                 } else {
                     WILL_DELETE
                 };
 
-                vec![sample, file_link, action.to_string()]
+                vec![
+                    approval::task_checkbox(&image.change_id()),
+                    sample,
+                    file_link,
+                    action.to_string(),
+                ]
             })
             .collect()
     }
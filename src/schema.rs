@@ -0,0 +1,359 @@
+#[cfg(test)]
+mod schema_tests;
+
+use crate::constants::*;
+use crate::date_settings::DateSettings;
+use crate::frontmatter::FrontMatter;
+use crate::markdown_file::PersistReason;
+use crate::obsidian_repository::ObsidianRepository;
+use crate::validated_config::ValidatedConfig;
+use crate::wikilink;
+use globset::{Glob, GlobMatcher};
+use serde_yaml::{Mapping, Value};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// the type a property's value must have
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PropertyType {
+    Bool,
+    /// a date in the configured format or a plain iso date, with or without brackets
+    Date,
+    List,
+    Number,
+    String,
+    /// a single [[wikilink]]
+    Wikilink,
+}
+
+impl FromStr for PropertyType {
+    type Err = String;
+
+    fn from_str(property_type: &str) -> Result<Self, Self::Err> {
+        match property_type {
+            SCHEMA_TYPE_BOOL => Ok(PropertyType::Bool),
+            SCHEMA_TYPE_DATE => Ok(PropertyType::Date),
+            SCHEMA_TYPE_LIST => Ok(PropertyType::List),
+            SCHEMA_TYPE_NUMBER => Ok(PropertyType::Number),
+            SCHEMA_TYPE_STRING => Ok(PropertyType::String),
+            SCHEMA_TYPE_WIKILINK => Ok(PropertyType::Wikilink),
+            unknown => Err(format!("unknown property type: {}", unknown)),
+        }
+    }
+}
+
+impl fmt::Display for PropertyType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PropertyType::Bool => SCHEMA_TYPE_BOOL,
+            PropertyType::Date => SCHEMA_TYPE_DATE,
+            PropertyType::List => SCHEMA_TYPE_LIST,
+            PropertyType::Number => SCHEMA_TYPE_NUMBER,
+            PropertyType::String => SCHEMA_TYPE_STRING,
+            PropertyType::Wikilink => SCHEMA_TYPE_WIKILINK,
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl PropertyType {
+    fn matches(&self, value: &Value, date_settings: &DateSettings, timezone: &str) -> bool {
+        match (self, value) {
+            (PropertyType::Bool, Value::Bool(_)) => true,
+            (PropertyType::Date, Value::String(text)) => {
+                date_settings.parse_fix_date(text, timezone).is_some()
+            }
+            (PropertyType::List, Value::Sequence(_)) => true,
+            (PropertyType::Number, Value::Number(_)) => true,
+            (PropertyType::String, Value::String(_)) => true,
+            (PropertyType::Wikilink, Value::String(text)) => {
+                wikilink::is_wikilink(Some(text.trim()))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PropertySchema {
+    pub name: String,
+    /// the value a missing property is added with when the rule fixes violations
+    pub default: Option<Value>,
+    pub property_type: Option<PropertyType>,
+    pub required: bool,
+    /// every value - or every item of a list - has to be one of these
+    pub values: Option<Vec<String>>,
+}
+
+impl PropertySchema {
+    fn check(
+        &self,
+        value: Option<&Value>,
+        date_settings: &DateSettings,
+        timezone: &str,
+    ) -> Option<SchemaIssue> {
+        let value = match value {
+            None | Some(Value::Null) => return self.required.then_some(SchemaIssue::Missing),
+            Some(value) => value,
+        };
+
+        if let Some(property_type) = self.property_type {
+            if !property_type.matches(value, date_settings, timezone) {
+                return Some(SchemaIssue::WrongType(property_type));
+            }
+        }
+
+        let allowed = self.values.as_ref()?;
+        let items = match value {
+            Value::Sequence(items) => items.iter().collect(),
+            value => vec![value],
+        };
+        items
+            .into_iter()
+            .map(value_text)
+            .find(|text| !allowed.contains(text))
+            .map(SchemaIssue::NotAllowed)
+    }
+
+    /// only a missing property with a default and a single value that should be a list can be
+    /// fixed - anything else needs a person to decide
+    fn fix(&self, issue: &SchemaIssue, value: Option<&Value>) -> Option<Value> {
+        match (issue, value) {
+            (SchemaIssue::Missing, _) => self.default.clone(),
+            (SchemaIssue::WrongType(PropertyType::List), Some(value))
+                if !matches!(value, Value::Mapping(_)) =>
+            {
+                Some(Value::Sequence(vec![value.clone()]))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// the properties every note in a folder or with a tag must have
+#[derive(Debug, Clone)]
+pub struct SchemaRule {
+    pub folder: Option<String>,
+    folder_matcher: Option<GlobMatcher>,
+    pub tag: Option<String>,
+    /// fix the violations that can be fixed rather than only reporting them
+    pub fix: bool,
+    pub properties: Vec<PropertySchema>,
+}
+
+impl SchemaRule {
+    pub fn new(
+        folder: Option<String>,
+        tag: Option<String>,
+        fix: bool,
+        properties: Vec<PropertySchema>,
+    ) -> Result<Self, String> {
+        if folder.is_none() && tag.is_none() {
+            return Err("a schema rule needs a folder or a tag".to_string());
+        }
+
+        let folder_matcher = folder.as_deref().map(folder_matcher).transpose()?;
+
+        Ok(Self {
+            folder,
+            folder_matcher,
+            tag: tag.map(|tag| normalize_tag(&tag)),
+            fix,
+            properties,
+        })
+    }
+
+    /// the folder glob is matched against the note's path and every folder above it so both
+    /// `daily` and `daily/**` cover the notes in daily - when a rule has a folder and a tag the
+    /// note needs both
+    pub fn applies_to(&self, relative_path: &Path, properties: &Mapping) -> bool {
+        let folder_matches = self
+            .folder_matcher
+            .as_ref()
+            .is_none_or(|matcher| in_folder(matcher, relative_path));
+        let tag_matches = self
+            .tag
+            .as_ref()
+            .is_none_or(|tag| note_tags(properties).contains(tag));
+
+        folder_matches && tag_matches
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaIssue {
+    Missing,
+    WrongType(PropertyType),
+    NotAllowed(String),
+}
+
+impl fmt::Display for SchemaIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaIssue::Missing => write!(f, "missing"),
+            SchemaIssue::WrongType(property_type) => write!(f, "should be a {}", property_type),
+            SchemaIssue::NotAllowed(value) => write!(f, "'{}' is not an allowed value", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaViolation {
+    pub property: String,
+    pub issue: SchemaIssue,
+    /// the value the property is set to - only when the rule fixes violations and this one can be
+    pub fix: Option<Value>,
+    pub fixed: bool,
+}
+
+/// the frontmatter rules from the config
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    rules: Vec<SchemaRule>,
+}
+
+impl Schema {
+    pub fn new(rules: Vec<SchemaRule>) -> Self {
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// checks a note's properties against every rule that applies to it
+    pub fn check(
+        &self,
+        relative_path: &Path,
+        properties: &Mapping,
+        date_settings: &DateSettings,
+        timezone: &str,
+    ) -> Vec<SchemaViolation> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.applies_to(relative_path, properties))
+            .flat_map(|rule| {
+                rule.properties.iter().filter_map(move |property| {
+                    let value = properties.get(property.name.as_str());
+                    let issue = property.check(value, date_settings, timezone)?;
+                    let fix = rule.fix.then(|| property.fix(&issue, value)).flatten();
+                    Some(SchemaViolation {
+                        property: property.name.clone(),
+                        issue,
+                        fix,
+                        fixed: false,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+pub fn folder_matcher(folder: &str) -> Result<GlobMatcher, String> {
+    Glob::new(folder.trim_matches('/'))
+        .map(|glob| glob.compile_matcher())
+        .map_err(|e| format!("invalid folder glob '{}': {}", folder, e))
+}
+
+/// the glob is matched against the note's path and every folder above it
+pub fn in_folder(matcher: &GlobMatcher, relative_path: &Path) -> bool {
+    relative_path
+        .ancestors()
+        .filter(|path| !path.as_os_str().is_empty())
+        .any(|path| matcher.is_match(path))
+}
+
+/// the tags property holds either a list or a single comma or space separated string - tags
+/// are compared without the # and ignoring case
+pub fn note_tags(properties: &Mapping) -> Vec<String> {
+    match properties.get(TAGS_PROPERTY) {
+        Some(Value::Sequence(tags)) => tags.iter().map(value_text).collect(),
+        Some(Value::String(tags)) => tags
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+    .iter()
+    .map(|tag| normalize_tag(tag))
+    .filter(|tag| !tag.is_empty())
+    .collect()
+}
+
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// a scalar as it reads in the frontmatter
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(flag) => flag.to_string(),
+        Value::Sequence(items) => format!(
+            "[{}]",
+            items.iter().map(value_text).collect::<Vec<_>>().join(", ")
+        ),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+impl ObsidianRepository {
+    /// records the violations of every note and applies the fixes - unless `ok apply` found
+    /// change ids in the output file, then only the approved ones
+    pub fn validate_schema(&mut self, validated_config: &ValidatedConfig) {
+        let schema = validated_config.schema();
+        if schema.is_empty() {
+            return;
+        }
+        let timezone = validated_config.operational_timezone();
+
+        for file in &mut self.markdown_files {
+            let relative_path = file
+                .path
+                .strip_prefix(validated_config.obsidian_path())
+                .unwrap_or(&file.path)
+                .to_path_buf();
+            let properties = file
+                .frontmatter
+                .as_ref()
+                .map(FrontMatter::properties)
+                .unwrap_or_default();
+
+            let mut violations = schema.check(
+                &relative_path,
+                &properties,
+                validated_config.date_settings(),
+                timezone,
+            );
+
+            if let Some(frontmatter) = file.frontmatter.as_mut() {
+                for violation in &mut violations {
+                    let approved = validated_config
+                        .approved_changes()
+                        .is_none_or(|approved| approved.contains(&violation.change_id(&file.path)));
+                    if let (Some(value), true) = (&violation.fix, approved) {
+                        violation.fixed = frontmatter
+                            .set_property(&violation.property, value.clone())
+                            .is_ok();
+                    }
+                }
+            } else {
+                // there's nowhere to put a fix
+                violations
+                    .iter_mut()
+                    .for_each(|violation| violation.fix = None);
+            }
+
+            let any_fixed = violations.iter().any(|violation| violation.fixed);
+            file.schema_violations = violations;
+            if any_fixed {
+                file.mark_edited(PersistReason::SchemaFixed, timezone);
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use super::*;
+use crate::approval::ApprovedChanges;
+use crate::markdown_file::PersistReason;
+use crate::test_utils;
+use crate::test_utils::TestFileBuilder;
+use crate::validated_config::validated_config_tests;
+use std::fs;
+use tempfile::TempDir;
+
+fn property(name: &str) -> PropertySchema {
+    PropertySchema {
+        name: name.to_string(),
+        default: None,
+        property_type: None,
+        required: false,
+        values: None,
+    }
+}
+
+fn yaml(text: &str) -> Mapping {
+    serde_yaml::from_str(text).unwrap()
+}
+
+fn tag_rule(fix: bool, properties: Vec<PropertySchema>) -> SchemaRule {
+    SchemaRule::new(None, Some("#Book".to_string()), fix, properties).unwrap()
+}
+
+fn check(rule: SchemaRule, relative_path: &str, properties: &str) -> Vec<SchemaViolation> {
+    Schema::new(vec![rule]).check(
+        Path::new(relative_path),
+        &yaml(properties),
+        &DateSettings::default(),
+        DEFAULT_TIMEZONE,
+    )
+}
+
+#[test]
+fn test_property_checks() {
+    let typed = |property_type| PropertySchema {
+        property_type: Some(property_type),
+        ..property("value")
+    };
+    let test_cases = [
+        (
+            PropertySchema {
+                required: true,
+                ..property("value")
+            },
+            "other: 1",
+            Some(SchemaIssue::Missing),
+        ),
+        (
+            PropertySchema {
+                required: true,
+                ..property("value")
+            },
+            "value:",
+            Some(SchemaIssue::Missing),
+        ),
+        // a property that isn't required may be left out
+        (typed(PropertyType::Number), "other: 1", None),
+        (typed(PropertyType::Number), "value: 3.5", None),
+        (
+            typed(PropertyType::Number),
+            "value: three",
+            Some(SchemaIssue::WrongType(PropertyType::Number)),
+        ),
+        (typed(PropertyType::Bool), "value: true", None),
+        (typed(PropertyType::List), "value: [a, b]", None),
+        (
+            typed(PropertyType::List),
+            "value: a",
+            Some(SchemaIssue::WrongType(PropertyType::List)),
+        ),
+        (typed(PropertyType::Date), "value: \"[[2024-01-15]]\"", None),
+        (typed(PropertyType::Date), "value: 2024-01-15", None),
+        (
+            typed(PropertyType::Date),
+            "value: someday",
+            Some(SchemaIssue::WrongType(PropertyType::Date)),
+        ),
+        (typed(PropertyType::Wikilink), "value: \"[[Acme]]\"", None),
+        (
+            typed(PropertyType::Wikilink),
+            "value: Acme",
+            Some(SchemaIssue::WrongType(PropertyType::Wikilink)),
+        ),
+        (typed(PropertyType::String), "value: text", None),
+        (
+            typed(PropertyType::String),
+            "value: [text]",
+            Some(SchemaIssue::WrongType(PropertyType::String)),
+        ),
+        (
+            PropertySchema {
+                values: Some(vec!["active".to_string(), "archived".to_string()]),
+                ..property("value")
+            },
+            "value: [active, paused]",
+            Some(SchemaIssue::NotAllowed("paused".to_string())),
+        ),
+        (
+            PropertySchema {
+                values: Some(vec!["active".to_string()]),
+                ..property("value")
+            },
+            "value: active",
+            None,
+        ),
+    ];
+
+    for (property, properties, expected) in test_cases {
+        let issue = property.check(
+            yaml(properties).get("value"),
+            &DateSettings::default(),
+            DEFAULT_TIMEZONE,
+        );
+        assert_eq!(issue, expected, "Failed for: {}", properties);
+    }
+}
+
+#[test]
+fn test_rule_applies_to() {
+    let folder_rule =
+        |folder: &str| SchemaRule::new(Some(folder.to_string()), None, false, Vec::new()).unwrap();
+    let both = SchemaRule::new(
+        Some("books".to_string()),
+        Some("book".to_string()),
+        false,
+        Vec::new(),
+    )
+    .unwrap();
+
+    let test_cases = [
+        (folder_rule("daily"), "daily/2024-01-15.md", "", true),
+        (folder_rule("daily/"), "daily/2024/01/15.md", "", true),
+        (folder_rule("daily/**"), "daily/2024-01-15.md", "", true),
+        (folder_rule("daily"), "weekly/2024-01-15.md", "", false),
+        (folder_rule("daily"), "daily.md", "", false),
+        (folder_rule("projects/*"), "projects/ok/notes.md", "", true),
+        (folder_rule("projects/*"), "projects.md", "", false),
+        (tag_rule(false, Vec::new()), "note.md", "tags: [book]", true),
+        (
+            tag_rule(false, Vec::new()),
+            "note.md",
+            "tags: \"#book, novel\"",
+            true,
+        ),
+        (
+            tag_rule(false, Vec::new()),
+            "note.md",
+            "tags: [books]",
+            false,
+        ),
+        (tag_rule(false, Vec::new()), "note.md", "title: book", false),
+        (both.clone(), "books/note.md", "tags: [book]", true),
+        (both.clone(), "books/note.md", "tags: [novel]", false),
+        (both, "note.md", "tags: [book]", false),
+    ];
+
+    for (rule, path, properties, expected) in test_cases {
+        assert_eq!(
+            rule.applies_to(Path::new(path), &yaml(properties)),
+            expected,
+            "Failed for: {:?} {} {}",
+            rule.folder,
+            path,
+            properties
+        );
+    }
+}
+
+#[test]
+fn test_schema_rule_needs_folder_or_tag() {
+    assert!(SchemaRule::new(None, None, false, Vec::new()).is_err());
+}
+
+#[test]
+fn test_fixes() {
+    let properties = || {
+        vec![
+            PropertySchema {
+                property_type: Some(PropertyType::List),
+                ..property("author")
+            },
+            PropertySchema {
+                default: Some(Value::Bool(false)),
+                required: true,
+                ..property("read")
+            },
+            PropertySchema {
+                required: true,
+                ..property("title")
+            },
+        ]
+    };
+    let note = "tags: [book]\nauthor: Ursula K. Le Guin";
+
+    let fixes = |rule| {
+        check(rule, "note.md", note)
+            .into_iter()
+            .map(|violation| (violation.property, violation.fix))
+            .collect::<Vec<_>>()
+    };
+
+    // the violations are reported either way but only a rule with fix proposes fixes
+    assert_eq!(
+        fixes(tag_rule(false, properties())),
+        vec![
+            ("author".to_string(), None),
+            ("read".to_string(), None),
+            ("title".to_string(), None)
+        ]
+    );
+    assert_eq!(
+        fixes(tag_rule(true, properties())),
+        vec![
+            (
+                "author".to_string(),
+                Some(Value::Sequence(vec![Value::String(
+                    "Ursula K. Le Guin".to_string()
+                )]))
+            ),
+            ("read".to_string(), Some(Value::Bool(false))),
+            ("title".to_string(), None)
+        ]
+    );
+}
+
+#[test]
+fn test_validate_schema_fixes_notes() {
+    let temp_dir = TempDir::new().unwrap();
+    let test_date = test_utils::eastern_midnight(2024, 1, 15);
+    let file_path = TestFileBuilder::new()
+        .with_matching_dates(test_date)
+        .with_tags(vec!["book".to_string()])
+        .with_custom_frontmatter("author: Ursula K. Le Guin\n".to_string())
+        .with_content("# The Dispossessed".to_string())
+        .create(&temp_dir, "book.md");
+    let schema = Schema::new(vec![tag_rule(
+        true,
+        vec![
+            PropertySchema {
+                property_type: Some(PropertyType::List),
+                ..property("author")
+            },
+            PropertySchema {
+                required: true,
+                ..property("rating")
+            },
+        ],
+    )]);
+    let config = |approved_changes: Option<ApprovedChanges>| {
+        validated_config_tests::get_test_validated_config_result(&temp_dir, |builder| {
+            builder
+                .schema(schema.clone())
+                .approved_changes(approved_changes);
+        })
+        .unwrap()
+    };
+
+    // nothing approved leaves the note alone but still reports it
+    let repository =
+        ObsidianRepository::new(&config(ApprovedChanges::parse("[ ] ok-0123456789"))).unwrap();
+    let file = &repository.markdown_files[0];
+    assert_eq!(file.schema_violations.len(), 2);
+    assert!(file
+        .schema_violations
+        .iter()
+        .all(|violation| !violation.fixed));
+    assert!(
+        file.persist_reasons.is_empty(),
+        "{:?}",
+        file.persist_reasons
+    );
+
+    let mut repository = ObsidianRepository::new(&config(None)).unwrap();
+    let file = &repository.markdown_files[0];
+    assert_eq!(
+        file.schema_violations
+            .iter()
+            .map(|violation| (violation.property.as_str(), violation.fixed))
+            .collect::<Vec<_>>(),
+        vec![("author", true), ("rating", false)]
+    );
+    assert!(file.persist_reasons.contains(&PersistReason::SchemaFixed));
+
+    repository.persist().unwrap();
+    let content = fs::read_to_string(&file_path).unwrap();
+    assert!(
+        content.contains("author:\n- Ursula K. Le Guin"),
+        "{}",
+        content
+    );
+}
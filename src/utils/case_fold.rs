@@ -0,0 +1,113 @@
+use caseless::Caseless;
+use unicode_normalization::char::canonical_combining_class;
+use unicode_normalization::UnicodeNormalization;
+
+/// the form text is compared in when case doesn't matter - nfc first so a decomposed "ü", the
+/// way macOS names files, is the same as a composed one, then full unicode case folding
+pub fn fold_case(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_ascii_lowercase();
+    }
+    text.nfc().default_case_fold().collect()
+}
+
+pub fn eq_fold_case(a: &str, b: &str) -> bool {
+    a == b || fold_case(a) == fold_case(b)
+}
+
+/// a line folded for matching along with where every folded byte came from - so a match in
+/// the folded text can be mapped back to the line
+pub struct FoldedText {
+    pub text: String,
+    /// the original offset of every folded byte plus one for the end - empty for ascii text as
+    /// folding doesn't move anything
+    offsets: Vec<usize>,
+}
+
+impl FoldedText {
+    pub fn new(original: &str) -> Self {
+        if original.is_ascii() {
+            return Self {
+                text: original.to_ascii_lowercase(),
+                offsets: Vec::new(),
+            };
+        }
+
+        let mut text = String::with_capacity(original.len());
+        let mut offsets = Vec::with_capacity(original.len() + 1);
+        let mut chars = original.char_indices().peekable();
+        // a character is folded together with the combining marks after it as nfc merges them
+        while let Some((start, _)) = chars.next() {
+            while chars
+                .peek()
+                .is_some_and(|(_, ch)| canonical_combining_class(*ch) != 0)
+            {
+                chars.next();
+            }
+            let end = chars.peek().map_or(original.len(), |(idx, _)| *idx);
+
+            let folded = fold_case(&original[start..end]);
+            offsets.extend(std::iter::repeat_n(start, folded.len()));
+            text.push_str(&folded);
+        }
+        offsets.push(original.len());
+
+        Self { text, offsets }
+    }
+
+    /// None when the span starts or ends inside what a single character folded to - e.g. one
+    /// of the two s's "ß" folds to
+    pub fn original_span(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        if self.offsets.is_empty() {
+            return Some((start, end));
+        }
+
+        let is_boundary =
+            |idx: usize| idx == 0 || self.offsets.get(idx) != self.offsets.get(idx - 1);
+        (is_boundary(start) && is_boundary(end)).then(|| (self.offsets[start], self.offsets[end]))
+    }
+}
+
+#[cfg(test)]
+mod case_fold_tests {
+    use super::*;
+
+    #[test]
+    fn test_fold_case() {
+        let test_cases = [
+            ("Test Link", "test link"),
+            ("Zürich", "zürich"),
+            // decomposed u + combining diaeresis
+            ("Zu\u{308}rich", "zürich"),
+            ("ÉCLAIR", "éclair"),
+            ("МОСКВА", "москва"),
+            ("ΟΔΥΣΣΕΥΣ", "οδυσσευσ"),
+            ("Straße", "strasse"),
+        ];
+
+        for (text, expected) in test_cases {
+            assert_eq!(fold_case(text), expected, "Failed for: {}", text);
+        }
+        assert!(eq_fold_case("Οδυσσεύς", "ΟΔΥΣΣΕΎΣ"));
+    }
+
+    #[test]
+    fn test_original_span() {
+        let original = "in Zu\u{308}rich and STRASSE";
+        let folded = FoldedText::new(original);
+
+        let start = folded.text.find("zürich").unwrap();
+        let (original_start, original_end) =
+            folded.original_span(start, start + "zürich".len()).unwrap();
+        assert_eq!(&original[original_start..original_end], "Zu\u{308}rich");
+
+        let folded = FoldedText::new("Straße");
+        assert_eq!(folded.text, "strasse");
+        assert_eq!(folded.original_span(0, 7), Some((0, "Straße".len())));
+        // the first s of ß
+        assert_eq!(folded.original_span(0, 5), None);
+
+        let folded = FoldedText::new("ASCII Text");
+        assert_eq!(folded.original_span(6, 10), Some((6, 10)));
+    }
+}
@@ -3,6 +3,7 @@ use chrono::{DateTime, Utc};
 use filetime::FileTime;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::error::Error;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::{fs, io};
@@ -24,6 +25,35 @@ pub fn read_contents_from_file(path: &Path) -> Result<String, Box<dyn Error + Se
     Ok(contents)
 }
 
+/// writes to a hidden temp file next to path and renames it into place so a crash can't leave
+/// a half-written file behind
+pub fn write_atomically(path: &Path, contents: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("not a file: {}", path.display()))?;
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}",
+        file_name.to_string_lossy(),
+        TEMP_FILE_EXTENSION
+    ));
+
+    let result = (|| -> io::Result<()> {
+        let mut file = fs::File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp_path, metadata.permissions())?;
+        }
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+
+    Ok(result?)
+}
+
 // Expands a path that starts with `~/` to the user's home directory.
 pub fn expand_tilde<P: AsRef<Path>>(path: P) -> PathBuf {
     let path = path.as_ref();
@@ -131,6 +161,12 @@ pub fn collect_repository_files(
     })
 }
 
+/// only macos lets ok set a file's creation date - elsewhere it's the birth time the file system
+/// gave the file
+pub fn can_set_created_date() -> bool {
+    cfg!(target_os = "macos")
+}
+
 #[cfg(target_os = "macos")]
 pub fn set_file_dates(
     path: &Path,
@@ -1,4 +1,5 @@
 // mod vec_enum_filter;
+mod case_fold;
 mod file_utils;
 mod output_file_writer;
 mod regex_utils;
@@ -6,6 +7,7 @@ mod sha256_cache;
 mod timer;
 mod vec_enum_filter;
 
+pub use case_fold::*;
 pub use file_utils::*;
 pub use output_file_writer::*;
 pub use regex_utils::*;
@@ -34,3 +34,29 @@ pub fn build_case_insensitive_word_finder(patterns: &Option<Vec<String>>) -> Opt
             .collect()
     })
 }
+
+// rendered text patterns can start or end with punctuation (e.g., "Ed:") so
+// a word boundary is only required on the side of the pattern that is a word character
+pub fn build_case_insensitive_rendered_text_finder(patterns: &[String]) -> Vec<Regex> {
+    fn is_word_char(ch: Option<char>) -> bool {
+        ch.is_some_and(|ch| ch.is_alphanumeric() || ch == '_')
+    }
+
+    patterns
+        .iter()
+        .map(|pattern| {
+            let start = if is_word_char(pattern.chars().next()) {
+                r"\b"
+            } else {
+                ""
+            };
+            let end = if is_word_char(pattern.chars().last()) {
+                r"\b"
+            } else {
+                ""
+            };
+            Regex::new(&format!(r"(?i){}{}{}", start, regex::escape(pattern), end))
+                .expect("Failed to build regex for rendered text pattern")
+        })
+        .collect()
+}
@@ -1,10 +1,20 @@
 #[cfg(test)]
 pub(crate) mod validated_config_tests;
 
+use crate::ambiguous_resolution::AmbiguousResolver;
+use crate::approval::ApprovedChanges;
+use crate::date_settings::DateSettings;
+use crate::frontmatter::FrontMatter;
+use crate::inflection::Inflections;
+use crate::link_density::LinkDensity;
+use crate::markdown_file::SkipRegion;
+use crate::property_operations::PropertyOperations;
+use crate::schema::Schema;
 use crate::{constants::*, utils};
 use chrono_tz::Tz;
 use derive_builder::Builder;
 use regex::Regex;
+use serde_yaml::Mapping;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -14,10 +24,28 @@ pub enum ValidationError {
     EmptyBackPopulateFileFilter,
     #[error("Empty output folder")]
     EmptyOutputFolder,
+    #[error("Invalid ambiguous resolution rule: {0}")]
+    InvalidAmbiguousRule(String),
+    #[error("Invalid create_frontmatter properties: {0}")]
+    InvalidCreateFrontmatter(String),
+    #[error("Invalid date settings: {0}")]
+    InvalidDateSettings(String),
     #[error("Back populate file count must be >= 1")]
     InvalidFileLimit,
+    #[error("Ignore rendered text cannot contain a wikilink: {0}")]
+    InvalidIgnoreRenderedText(String),
+    #[error("Invalid inflections: {0}")]
+    InvalidInflection(String),
+    #[error("Invalid link_density: {0}")]
+    InvalidLinkDensity(String),
+    #[error("Invalid property operation: {0}")]
+    InvalidPropertyOperation(String),
     #[error("Invalid timezone: {0}")]
     InvalidTimezone(String),
+    #[error("Invalid schema: {0}")]
+    InvalidSchema(String),
+    #[error("Invalid skip_regions: {0}")]
+    InvalidSkipRegion(String),
     #[error("Obsidian path does not exist: {0}")]
     InvalidObsidianPath(String),
     #[error("Missing obsidian path")]
@@ -41,10 +69,23 @@ impl From<derive_builder::UninitializedFieldError> for ValidationError {
     )
 )]
 pub struct ValidatedConfig {
+    #[builder(default)]
+    ambiguous_resolver: AmbiguousResolver,
     #[builder(default = "false")]
     apply_changes: bool,
     #[builder(default)]
+    approved_changes: Option<ApprovedChanges>,
+    #[builder(default = "true")]
+    back_populate_enabled: bool,
+    #[builder(default)]
     back_populate_file_filter: Option<String>,
+    /// the properties a note without frontmatter is given - None unless create_frontmatter is on
+    #[builder(default)]
+    create_frontmatter: Option<Mapping>,
+    #[builder(default)]
+    date_settings: DateSettings,
+    #[builder(default = "true")]
+    dates_enabled: bool,
     #[builder(setter(custom), default)]
     #[allow(dead_code)]
     do_not_back_populate: Option<Vec<String>>,
@@ -54,12 +95,50 @@ pub struct ValidatedConfig {
     file_limit: Option<usize>,
     #[builder(setter(custom), default)]
     ignore_folders: Option<Vec<PathBuf>>,
+    /// the builder holds the configured patterns, this holds them compiled
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Vec<String>",
+            build = "(!self.ignore_rendered_text.is_empty()).then(|| utils::build_case_insensitive_rendered_text_finder(&self.ignore_rendered_text))"
+        )
+    )]
+    ignore_rendered_text: Option<Vec<Regex>>,
+    #[builder(default = "true")]
+    image_deletion_enabled: bool,
+    #[builder(default = "true")]
+    image_references_enabled: bool,
+    /// None unless back populate also matches plurals and the like
+    #[builder(default)]
+    inflections: Option<Inflections>,
+    #[builder(default)]
+    link_density: LinkDensity,
     #[builder(setter(into))]
     obsidian_path: PathBuf,
+    #[builder(default = "false")]
+    normalize_property_types: bool,
     #[builder(default = "DEFAULT_TIMEZONE.to_string()")]
     operational_timezone: String,
     #[builder(setter(custom))]
     output_folder: PathBuf,
+    #[builder(default)]
+    property_operations: PropertyOperations,
+    #[builder(default)]
+    schema: Schema,
+    /// fenced code is always skipped
+    #[builder(default = "SkipRegion::defaults()")]
+    skip_regions: Vec<SkipRegion>,
+    /// the builder holds the configured patterns, this holds them compiled
+    #[builder(
+        setter(custom),
+        field(
+            ty = "Vec<String>",
+            build = "(!self.simplify_wikilinks.is_empty()).then(|| utils::build_case_insensitive_rendered_text_finder(&self.simplify_wikilinks))"
+        )
+    )]
+    simplify_wikilinks: Option<Vec<Regex>>,
+    #[builder(default = "false")]
+    write_patch: bool,
 }
 
 impl ValidatedConfigBuilder {
@@ -91,6 +170,15 @@ impl ValidatedConfigBuilder {
             }
         }
 
+        // Validate ignore_rendered_text - rendered text never contains wikilinks so it could never match
+        if let Some(pattern) = self
+            .ignore_rendered_text
+            .iter()
+            .find(|p| p.contains(OPENING_WIKILINK) || p.contains(CLOSING_WIKILINK))
+        {
+            return Err(ValidationError::InvalidIgnoreRenderedText(pattern.clone()));
+        }
+
         // Validate output_folder
         if let Some(folder) = &self.output_folder {
             let path_str = folder.as_os_str().to_string_lossy();
@@ -99,6 +187,45 @@ impl ValidatedConfigBuilder {
             }
         }
 
+        // Validate date property names and format
+        if let Some(date_settings) = &self.date_settings {
+            date_settings
+                .validate()
+                .map_err(ValidationError::InvalidDateSettings)?;
+        }
+
+        // Validate create_frontmatter - the properties have to fit the ones ok reads, e.g. aliases
+        if let Some(Some(properties)) = &self.create_frontmatter {
+            let date_settings = self.date_settings.clone().unwrap_or_default();
+            FrontMatter::create(properties, &date_settings)
+                .map_err(|e| ValidationError::InvalidCreateFrontmatter(e.to_string()))?;
+        }
+
+        // Validate property_operations - the date properties are ok's to manage
+        if let Some(operations) = &self.property_operations {
+            let date_settings = self.date_settings.clone().unwrap_or_default();
+            let date_properties = date_settings.properties;
+            if let Some(property) = operations
+                .iter()
+                .flat_map(|operation| &operation.actions)
+                .flat_map(|action| action.properties())
+                .find(|property| {
+                    [
+                        &date_properties.created,
+                        &date_properties.created_fix,
+                        &date_properties.modified,
+                    ]
+                    .iter()
+                    .any(|date_property| date_property.as_str() == *property)
+                })
+            {
+                return Err(ValidationError::InvalidPropertyOperation(format!(
+                    "{} is a date property",
+                    property
+                )));
+            }
+        }
+
         // Validate timezone
         let timezone = self
             .operational_timezone
@@ -135,6 +262,26 @@ impl ValidatedConfigBuilder {
         self
     }
 
+    pub fn ignore_rendered_text(&mut self, val: Option<Vec<String>>) -> &mut Self {
+        self.ignore_rendered_text = val
+            .unwrap_or_default()
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        self
+    }
+
+    pub fn simplify_wikilinks(&mut self, val: Option<Vec<String>>) -> &mut Self {
+        self.simplify_wikilinks = val
+            .unwrap_or_default()
+            .iter()
+            .map(|p| p.trim().to_string())
+            .filter(|p| !p.is_empty())
+            .collect();
+        self
+    }
+
     fn resolve_paths(&self, paths: Vec<PathBuf>) -> Vec<PathBuf> {
         if let Some(obsidian_path) = &self.obsidian_path {
             paths
@@ -184,21 +331,78 @@ impl ValidatedConfigBuilder {
     pub fn ignore_folders(&mut self, val: Option<Vec<PathBuf>>) -> &mut Self {
         let mut folders = val.unwrap_or_default();
         let obsidian_folder = PathBuf::from(".obsidian");
+        let cache_folder = PathBuf::from(CACHE_FOLDER);
 
         if !folders.contains(&obsidian_folder) {
             folders.push(obsidian_folder);
         }
 
+        // holds the image cache and the journals - none of which are part of the vault
+        if !folders.contains(&cache_folder) {
+            folders.push(cache_folder);
+        }
+
         self.ignore_folders = Some(Some(self.resolve_paths(folders)));
         self
     }
 }
 
 impl ValidatedConfig {
+    pub fn ambiguous_resolver(&self) -> &AmbiguousResolver {
+        &self.ambiguous_resolver
+    }
+
     pub fn apply_changes(&self) -> bool {
         self.apply_changes
     }
 
+    /// None unless `ok apply` found change ids in the output file - then only those are applied
+    pub fn approved_changes(&self) -> Option<&ApprovedChanges> {
+        self.approved_changes.as_ref()
+    }
+
+    pub fn set_approved_changes(&mut self, approved_changes: Option<ApprovedChanges>) {
+        self.approved_changes = approved_changes;
+    }
+
+    pub fn back_populate_enabled(&self) -> bool {
+        self.back_populate_enabled
+    }
+
+    pub fn create_frontmatter(&self) -> Option<&Mapping> {
+        self.create_frontmatter.as_ref()
+    }
+
+    pub fn dates_enabled(&self) -> bool {
+        self.dates_enabled
+    }
+
+    pub fn date_settings(&self) -> &DateSettings {
+        &self.date_settings
+    }
+
+    pub fn image_deletion_enabled(&self) -> bool {
+        self.image_deletion_enabled
+    }
+
+    pub fn image_references_enabled(&self) -> bool {
+        self.image_references_enabled
+    }
+
+    /// config names of the features switched off - used to mark them in the output file
+    pub fn disabled_features(&self) -> Vec<&'static str> {
+        [
+            (self.back_populate_enabled, FEATURE_BACK_POPULATE),
+            (self.dates_enabled, FEATURE_DATES),
+            (self.image_deletion_enabled, FEATURE_IMAGE_DELETION),
+            (self.image_references_enabled, FEATURE_IMAGE_REFERENCES),
+        ]
+        .into_iter()
+        .filter(|(enabled, _)| !enabled)
+        .map(|(_, feature)| feature)
+        .collect()
+    }
+
     pub fn file_limit(&self) -> Option<usize> {
         self.file_limit
     }
@@ -234,6 +438,18 @@ impl ValidatedConfig {
         self.ignore_folders.as_deref()
     }
 
+    pub fn ignore_rendered_text_regexes(&self) -> Option<&[Regex]> {
+        self.ignore_rendered_text.as_deref()
+    }
+
+    pub fn inflections(&self) -> Option<&Inflections> {
+        self.inflections.as_ref()
+    }
+
+    pub fn link_density(&self) -> LinkDensity {
+        self.link_density
+    }
+
     pub fn obsidian_path(&self) -> &Path {
         &self.obsidian_path
     }
@@ -245,4 +461,28 @@ impl ValidatedConfig {
     pub fn output_folder(&self) -> &Path {
         &self.output_folder
     }
+
+    pub fn property_operations(&self) -> &PropertyOperations {
+        &self.property_operations
+    }
+
+    pub fn normalize_property_types(&self) -> bool {
+        self.normalize_property_types
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn skip_regions(&self) -> &[SkipRegion] {
+        &self.skip_regions
+    }
+
+    pub fn simplify_wikilinks_regexes(&self) -> Option<&[Regex]> {
+        self.simplify_wikilinks.as_deref()
+    }
+
+    pub fn write_patch(&self) -> bool {
+        self.write_patch
+    }
 }
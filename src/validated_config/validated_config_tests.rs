@@ -225,6 +225,41 @@ output_folder: "  ""#,
     ));
 }
 
+#[test]
+fn test_ignore_rendered_text() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let yaml = format!(
+        r#"
+obsidian_path: {}
+ignore_rendered_text:
+  - " Ed: music reco: "
+  - "  ""#,
+        temp_dir.path().display()
+    );
+
+    let config: Config = serde_yaml::from_str(&yaml).unwrap();
+    let validated = config.validate().unwrap();
+    let regexes = validated.ignore_rendered_text_regexes().unwrap();
+    assert_eq!(regexes.len(), 1);
+    assert!(regexes[0].is_match("ed: Music Reco: Test Link"));
+    assert!(!regexes[0].is_match("Ed: music"));
+
+    let validated = get_test_validated_config_result(&temp_dir, |builder| {
+        builder.ignore_rendered_text(Some(vec!["  ".to_string()]));
+    })
+    .unwrap();
+    assert!(validated.ignore_rendered_text_regexes().is_none());
+
+    let result = get_test_validated_config_result(&temp_dir, |builder| {
+        builder.ignore_rendered_text(Some(vec!["[[Ed Barnes|Ed]]: music reco:".to_string()]));
+    });
+    assert!(matches!(
+        result.unwrap_err(),
+        ValidationError::InvalidIgnoreRenderedText(_)
+    ));
+}
+
 #[test]
 fn test_invalid_back_populate_count() {
     let temp_dir = TempDir::new().unwrap();
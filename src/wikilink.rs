@@ -3,6 +3,8 @@ mod extract_wikilink_tests;
 #[cfg(test)]
 mod markdown_link_tests;
 #[cfg(test)]
+mod render_wikilink_tests;
+#[cfg(test)]
 mod wikilink_creation_tests;
 
 mod wikilink_types;
@@ -11,6 +13,7 @@ pub use wikilink_types::*;
 use crate::{
     constants::*,
     markdown_file::InlineCodeExcluder,
+    utils,
     utils::{EMAIL_REGEX, TAG_REGEX},
 };
 
@@ -40,6 +43,14 @@ pub fn create_filename_wikilink(filename: &str) -> Wikilink {
     }
 }
 
+/// a wikilink target and a note name compare by the note's name alone - [[people/Bill#Work]] and
+/// bill.md are the same note
+pub fn target_key(target: &str) -> String {
+    let note = target.split('#').next().unwrap_or(target);
+    let name = note.rsplit('/').next().unwrap_or(note);
+    utils::fold_case(name.strip_suffix(MARKDOWN_SUFFIX).unwrap_or(name))
+}
+
 pub fn extract_wikilinks(line: &str) -> ParsedExtractedWikilinks {
     let mut extracted_wikilinks = ParsedExtractedWikilinks::default();
     let mut inline_code = InlineCodeExcluder::new();
@@ -421,3 +432,57 @@ pub(crate) fn is_within_wikilink(line: &str, byte_position: usize) -> bool {
     }
     false
 }
+
+/// renders the wikilinks on a line to the text obsidian would display for them
+/// image embeds are left alone as they don't render as text
+pub fn render_wikilinks(line: &str) -> RenderedLine {
+    lazy_static! {
+        static ref RENDERABLE_WIKILINK: Regex =
+            Regex::new(r"(!?)\[\[([^\[\]|\\]+)(?:\\?\|([^\[\]]+))?\]\]").unwrap();
+    }
+
+    let mut rendered = RenderedLine::default();
+    let mut last_end = 0;
+
+    let push_text = |rendered: &mut RenderedLine, source_start: usize, source_end: usize| {
+        if source_start < source_end {
+            let rendered_start = rendered.text.len();
+            rendered.text.push_str(&line[source_start..source_end]);
+            rendered.segments.push(RenderedSegment {
+                rendered_span: (rendered_start, rendered.text.len()),
+                source_span: (source_start, source_end),
+                wikilink: None,
+            });
+        }
+    };
+
+    for captures in RENDERABLE_WIKILINK.captures_iter(line) {
+        let whole = captures.get(0).unwrap();
+        let is_image = !captures[1].is_empty();
+        let target = captures[2].trim();
+        let display_text = captures.get(3).map_or(target, |m| m.as_str().trim());
+
+        if is_image || target.is_empty() || display_text.is_empty() {
+            continue;
+        }
+
+        push_text(&mut rendered, last_end, whole.start());
+
+        let rendered_start = rendered.text.len();
+        rendered.text.push_str(display_text);
+        rendered.segments.push(RenderedSegment {
+            rendered_span: (rendered_start, rendered.text.len()),
+            source_span: (whole.start(), whole.end()),
+            wikilink: Some(Wikilink {
+                display_text: display_text.to_string(),
+                target: target.to_string(),
+            }),
+        });
+
+        last_end = whole.end();
+    }
+
+    push_text(&mut rendered, last_end, line.len());
+
+    rendered
+}
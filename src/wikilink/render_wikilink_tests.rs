@@ -0,0 +1,65 @@
+use crate::wikilink::render_wikilinks;
+
+#[test]
+fn test_render_wikilinks() {
+    struct TestCase {
+        name: &'static str,
+        line: &'static str,
+        expected: &'static str,
+    }
+
+    let test_cases = vec![
+        TestCase {
+            name: "no wikilinks",
+            line: "plain text",
+            expected: "plain text",
+        },
+        TestCase {
+            name: "simple wikilink",
+            line: "see [[Bob Rock]] here",
+            expected: "see Bob Rock here",
+        },
+        TestCase {
+            name: "aliased wikilink",
+            line: "[[Ed Barnes|Ed]]: music reco",
+            expected: "Ed: music reco",
+        },
+        TestCase {
+            name: "escaped pipe in a table",
+            line: "| [[Ed Barnes\\|Ed]]: | x |",
+            expected: "| Ed: | x |",
+        },
+        TestCase {
+            name: "image embeds are not rendered",
+            line: "![[photo.png]] and [[note]]",
+            expected: "![[photo.png]] and note",
+        },
+    ];
+
+    for case in test_cases {
+        assert_eq!(
+            render_wikilinks(case.line).text,
+            case.expected,
+            "Failed test: {}",
+            case.name
+        );
+    }
+}
+
+#[test]
+fn test_rendered_line_source_span() {
+    let line = "say [[Ed Barnes|Ed]]: now";
+    let rendered = render_wikilinks(line);
+    assert_eq!(rendered.text, "say Ed: now");
+
+    // "Ed:" in the rendered text covers the whole wikilink plus the colon
+    let (start, end) = rendered.source_span(4, 7);
+    assert_eq!(&line[start..end], "[[Ed Barnes|Ed]]:");
+
+    // plain text maps one to one
+    let (start, end) = rendered.source_span(8, 11);
+    assert_eq!(&line[start..end], "now");
+
+    assert_eq!(rendered.wikilink_segments_within(4, 7).len(), 1);
+    assert!(rendered.wikilink_segments_within(4, 5).is_empty());
+}
@@ -179,3 +179,60 @@ pub struct ParsedExtractedWikilinks {
     pub valid: Vec<Wikilink>,
     pub invalid: Vec<ParsedInvalidWikilink>,
 }
+
+/// a piece of a line after its wikilinks have been rendered to their display text
+/// `rendered_span` is the position within the rendered text and `source_span` is the
+/// position within the original line - for plain text both spans have the same length
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedSegment {
+    pub rendered_span: (usize, usize),
+    pub source_span: (usize, usize),
+    pub wikilink: Option<Wikilink>,
+}
+
+/// a line as obsidian would render it with respect to wikilinks - i.e., [[foo|Ed]]: renders as Ed:
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RenderedLine {
+    pub text: String,
+    pub segments: Vec<RenderedSegment>,
+}
+
+impl RenderedLine {
+    /// maps a span of the rendered text back to the span of the original line that produces it
+    /// a span that starts or ends inside of a rendered wikilink is widened to cover the whole wikilink
+    pub fn source_span(&self, start: usize, end: usize) -> (usize, usize) {
+        let source_start = self
+            .segments
+            .iter()
+            .find(|segment| start >= segment.rendered_span.0 && start < segment.rendered_span.1)
+            .map(|segment| match segment.wikilink {
+                Some(_) => segment.source_span.0,
+                None => segment.source_span.0 + (start - segment.rendered_span.0),
+            })
+            .unwrap_or(start);
+
+        let source_end = self
+            .segments
+            .iter()
+            .find(|segment| end > segment.rendered_span.0 && end <= segment.rendered_span.1)
+            .map(|segment| match segment.wikilink {
+                Some(_) => segment.source_span.1,
+                None => segment.source_span.0 + (end - segment.rendered_span.0),
+            })
+            .unwrap_or(end);
+
+        (source_start, source_end)
+    }
+
+    /// the rendered wikilinks that fall completely within the rendered span
+    pub fn wikilink_segments_within(&self, start: usize, end: usize) -> Vec<&RenderedSegment> {
+        self.segments
+            .iter()
+            .filter(|segment| {
+                segment.wikilink.is_some()
+                    && segment.rendered_span.0 >= start
+                    && segment.rendered_span.1 <= end
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(test)]
+mod yaml_editor_tests;
+
+use crate::yaml_frontmatter::YamlFrontMatterError;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde_yaml::{Mapping, Value};
+use std::ops::Range;
+
+lazy_static! {
+    // a top level key starts in column 0 and may be plain, single or double quoted
+    static ref TOP_LEVEL_KEY: Regex =
+        Regex::new(r#"^("(?:[^"\\]|\\.)*"|'(?:[^']|'')*'|[^\s#'"\-][^:#]*?|-[^\s:#][^:#]*?)\s*:(?:\s|$)"#)
+            .unwrap();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum QuoteStyle {
+    Double,
+    Plain,
+    Single,
+}
+
+/// one top level key of the original yaml and every line that belongs to its value
+#[derive(Debug)]
+struct KeyBlock {
+    key: String,
+    key_text: String,
+    start: usize,
+    end: usize,
+    style: QuoteStyle,
+}
+
+/// rewrites `original` so that it serializes to `updated` while touching only the top level
+/// keys whose values differ - every other line, comment and quote is kept byte for byte
+///
+/// changed keys are rewritten in place, removed keys are deleted along with their nested
+/// lines and new keys are appended at the end in alphabetical order
+pub fn update_yaml(original: &str, updated: &Value) -> Result<String, YamlFrontMatterError> {
+    let original_value: Value =
+        serde_yaml::from_str(original).map_err(|e| YamlFrontMatterError::Parse(e.to_string()))?;
+    let (Value::Mapping(original_map), Value::Mapping(updated_map)) = (&original_value, updated)
+    else {
+        return Err(YamlFrontMatterError::Serialize(
+            "Expected a mapping".to_string(),
+        ));
+    };
+
+    let lines: Vec<&str> = original.lines().collect();
+    let blocks = find_key_blocks(&lines);
+
+    // flow mappings and other layouts we can't edit line by line are left to the caller
+    if original_map
+        .keys()
+        .any(|key| !blocks.iter().any(|block| key.as_str() == Some(&block.key)))
+    {
+        return Err(YamlFrontMatterError::Serialize(
+            "unable to locate every key in the original yaml".to_string(),
+        ));
+    }
+
+    // replacement text for the lines of a block - None removes the block
+    let mut replacements: Vec<(usize, usize, Option<String>)> = Vec::new();
+    for block in &blocks {
+        let key = Value::String(block.key.clone());
+        let before = original_map.get(&key).unwrap_or(&Value::Null);
+        match updated_map.get(&key) {
+            Some(after) if after != before => replacements.push((
+                block.start,
+                block.end,
+                Some(render_entry(&block.key_text, after, block.style)?),
+            )),
+            // a key left empty in the original is the same as one that doesn't serialize
+            None if !before.is_null() => replacements.push((block.start, block.end, None)),
+            _ => {}
+        }
+    }
+
+    let mut added: Vec<(&str, &Value)> = updated_map
+        .iter()
+        .filter_map(|(key, value)| key.as_str().map(|key| (key, value)))
+        .filter(|(key, value)| !value.is_null() && !blocks.iter().any(|b| b.key == *key))
+        .collect();
+    added.sort_by(|a, b| a.0.cmp(b.0));
+
+    if replacements.is_empty() && added.is_empty() {
+        return Ok(original.to_string());
+    }
+
+    let mut output: Vec<String> = Vec::with_capacity(lines.len());
+    let mut line_idx = 0;
+    for (start, end, replacement) in replacements {
+        output.extend(lines[line_idx..start].iter().map(|line| line.to_string()));
+        if let Some(replacement) = replacement {
+            output.push(replacement);
+        }
+        line_idx = end;
+    }
+    output.extend(lines[line_idx..].iter().map(|line| line.to_string()));
+
+    for (key, value) in added {
+        output.push(render_entry(&render_key(key)?, value, QuoteStyle::Plain)?);
+    }
+
+    let mut result = output.join("\n");
+    if original.ends_with('\n') {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// every top level key with the lines its value spans - in the order they appear, so a key
+/// that's in the yaml twice is returned twice
+pub fn top_level_keys(lines: &[&str]) -> Vec<(String, Range<usize>)> {
+    find_key_blocks(lines)
+        .into_iter()
+        .map(|block| (block.key, block.start..block.end))
+        .collect()
+}
+
+fn find_key_blocks(lines: &[&str]) -> Vec<KeyBlock> {
+    let starts: Vec<(usize, &str)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            TOP_LEVEL_KEY
+                .captures(line)
+                .map(|captures| (idx, captures.get(1).unwrap().as_str()))
+        })
+        .collect();
+
+    starts
+        .iter()
+        .map(|&(start, key_text)| {
+            // nested lines are indented or, for sequences, may start with a dash in column 0
+            // blank lines only belong to the block if more nested lines follow them
+            let mut end = start + 1;
+            let mut idx = start + 1;
+            while idx < lines.len() {
+                let line = lines[idx];
+                if line.trim().is_empty() {
+                    idx += 1;
+                    continue;
+                }
+                if !is_nested_line(line) {
+                    break;
+                }
+                idx += 1;
+                end = idx;
+            }
+
+            let value_text = lines[start][key_text.len()..]
+                .trim_start()
+                .trim_start_matches(':')
+                .trim_start();
+
+            KeyBlock {
+                key: unquote_key(key_text),
+                key_text: key_text.to_string(),
+                start,
+                end,
+                style: quote_style(value_text),
+            }
+        })
+        .collect()
+}
+
+fn is_nested_line(line: &str) -> bool {
+    line.starts_with(' ') || line.starts_with('\t') || line == "-" || line.starts_with("- ")
+}
+
+fn quote_style(value_text: &str) -> QuoteStyle {
+    if value_text.starts_with('"') {
+        QuoteStyle::Double
+    } else if value_text.starts_with('\'') {
+        QuoteStyle::Single
+    } else {
+        QuoteStyle::Plain
+    }
+}
+
+fn unquote_key(key_text: &str) -> String {
+    if key_text.starts_with('"') {
+        serde_json::from_str(key_text).unwrap_or_else(|_| key_text.to_string())
+    } else if let Some(inner) = key_text
+        .strip_prefix('\'')
+        .and_then(|text| text.strip_suffix('\''))
+    {
+        inner.replace("''", "'")
+    } else {
+        key_text.trim_end().to_string()
+    }
+}
+
+fn render_key(key: &str) -> Result<String, YamlFrontMatterError> {
+    Ok(to_yaml(&Value::String(key.to_string()))?
+        .trim_end()
+        .to_string())
+}
+
+/// strings keep the quoting style the key used before - when serde_yaml would have to quote a
+/// plain value anyway we use double quotes as that's what obsidian itself writes
+fn render_entry(
+    key_text: &str,
+    value: &Value,
+    style: QuoteStyle,
+) -> Result<String, YamlFrontMatterError> {
+    match value {
+        Value::String(text) if !text.contains('\n') => {
+            let rendered = match style {
+                QuoteStyle::Double => double_quoted(text),
+                QuoteStyle::Single => format!("'{}'", text.replace('\'', "''")),
+                QuoteStyle::Plain => {
+                    let plain = to_yaml(value)?.trim_end().to_string();
+                    if plain.starts_with('\'') || plain.starts_with('"') {
+                        double_quoted(text)
+                    } else {
+                        plain
+                    }
+                }
+            };
+            Ok(format!("{}: {}", key_text, rendered))
+        }
+        _ => {
+            let mut entry = Mapping::new();
+            entry.insert(Value::String(String::new()), value.clone());
+            // serialize with a placeholder key so nested values get serde_yaml's layout
+            let rendered = to_yaml(&Value::Mapping(entry))?;
+            let rendered = rendered.trim_end().trim_start_matches("''");
+            Ok(format!("{}{}", key_text, rendered))
+        }
+    }
+}
+
+fn double_quoted(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_else(|_| format!("\"{}\"", text))
+}
+
+fn to_yaml(value: &Value) -> Result<String, YamlFrontMatterError> {
+    serde_yaml::to_string(value).map_err(|e| YamlFrontMatterError::Serialize(e.to_string()))
+}
@@ -0,0 +1,149 @@
+use super::*;
+
+fn update(original: &str, changes: &[(&str, Option<Value>)]) -> String {
+    let mut updated: Value = serde_yaml::from_str(original).unwrap();
+    let map = updated.as_mapping_mut().unwrap();
+    for (key, value) in changes {
+        match value {
+            Some(value) => {
+                map.insert(Value::String(key.to_string()), value.clone());
+            }
+            None => {
+                map.remove(*key);
+            }
+        }
+    }
+    update_yaml(original, &updated).unwrap()
+}
+
+fn string(text: &str) -> Option<Value> {
+    Some(Value::String(text.to_string()))
+}
+
+#[test]
+fn test_unchanged_yaml_is_returned_verbatim() {
+    let original = "# my note\ntags:\n  - zebra\n  - apple\ntitle:   'spaced'  # why not\n";
+    assert_eq!(update(original, &[]), original);
+}
+
+#[test]
+fn test_only_changed_key_is_rewritten() {
+    let original = "\
+zeta: 1
+# dates are managed by ok
+date_modified: \"[[2024-01-01]]\"
+tags: [zebra, apple]
+alpha: 'kept'";
+
+    let result = update(original, &[("date_modified", string("[[2024-02-03]]"))]);
+
+    assert_eq!(
+        result,
+        "\
+zeta: 1
+# dates are managed by ok
+date_modified: \"[[2024-02-03]]\"
+tags: [zebra, apple]
+alpha: 'kept'"
+    );
+}
+
+#[test]
+fn test_quote_style_is_kept() {
+    let test_cases = [
+        (
+            "date_created: '[[2024-01-01]]'",
+            "date_created: '[[2024-05-06]]'",
+        ),
+        (
+            "date_created: \"[[2024-01-01]]\"",
+            "date_created: \"[[2024-05-06]]\"",
+        ),
+        // plain values that would need quoting get obsidian's double quotes
+        (
+            "date_created: 2024-01-01",
+            "date_created: \"[[2024-05-06]]\"",
+        ),
+    ];
+
+    for (original, expected) in test_cases {
+        assert_eq!(
+            update(original, &[("date_created", string("[[2024-05-06]]"))]),
+            expected,
+            "Failed for: {}",
+            original
+        );
+    }
+}
+
+#[test]
+fn test_removed_key_takes_nested_lines_with_it() {
+    let original = "\
+date_created_fix:
+- \"[[2024-01-01]]\"
+
+- \"[[2024-01-02]]\"
+aliases:
+- one
+
+# trailing comment";
+
+    let result = update(original, &[("date_created_fix", None)]);
+
+    assert_eq!(result, "aliases:\n- one\n\n# trailing comment");
+}
+
+#[test]
+fn test_new_keys_are_appended_in_order() {
+    let original = "title: note\n";
+
+    let result = update(
+        original,
+        &[
+            ("date_modified", string("[[2024-01-02]]")),
+            ("date_created", string("[[2024-01-01]]")),
+            ("empty", Some(Value::Null)),
+        ],
+    );
+
+    assert_eq!(
+        result,
+        "title: note\ndate_created: \"[[2024-01-01]]\"\ndate_modified: \"[[2024-01-02]]\"\n"
+    );
+}
+
+#[test]
+fn test_empty_key_is_not_removed() {
+    let original = "date_created:\ntitle: note";
+    assert_eq!(update(original, &[("date_created", None)]), original);
+}
+
+#[test]
+fn test_changed_sequence_uses_block_layout() {
+    let original = "aliases: [one]\ntitle: note";
+
+    let result = update(
+        original,
+        &[(
+            "aliases",
+            Some(Value::Sequence(vec![
+                Value::String("one".to_string()),
+                Value::String("two".to_string()),
+            ])),
+        )],
+    );
+
+    assert_eq!(result, "aliases:\n- one\n- two\ntitle: note");
+}
+
+#[test]
+fn test_flow_mapping_is_not_edited() {
+    let original = "{title: note, date_created: x}";
+    let mut updated: Value = serde_yaml::from_str(original).unwrap();
+    updated.as_mapping_mut().unwrap().insert(
+        Value::String("date_created".to_string()),
+        string("y").unwrap(),
+    );
+
+    assert!(update_yaml(original, &updated).is_err());
+}
@@ -132,44 +132,49 @@ pub trait YamlFrontMatter: DeserializeOwned + Serialize {
         let value = serde_yaml::to_value(self)
             .map_err(|e| YamlFrontMatterError::Serialize(e.to_string()))?;
 
-        if let Value::Mapping(map) = value {
-            // Create a sorted mapping
-            let mut sorted_map = serde_yaml::Mapping::new();
+        to_sorted_yaml_str(value)
+    }
+}
 
-            // Collect all keys and sort them
-            let mut keys: Vec<String> = map
-                .keys()
-                .filter_map(|k| k.as_str().map(String::from))
-                .collect();
-            keys.sort();
+/// sorts all properties alphabetically, plus any contained lists are also sorted
+pub fn to_sorted_yaml_str(value: Value) -> Result<String, YamlFrontMatterError> {
+    if let Value::Mapping(map) = value {
+        // Create a sorted mapping
+        let mut sorted_map = serde_yaml::Mapping::new();
 
-            // Rebuild mapping in sorted order
-            for key in keys {
-                if let Some(value) = map.get(Value::String(key.clone())) {
-                    // Sort sequence/list values if present
-                    let sorted_value = match value {
-                        Value::Sequence(seq) => {
-                            let mut sorted_seq: Vec<String> = seq
-                                .iter()
-                                .filter_map(|v| v.as_str().map(String::from))
-                                .collect();
-                            sorted_seq.sort();
-                            Value::Sequence(sorted_seq.into_iter().map(Value::String).collect())
-                        }
-                        _ => value.clone(),
-                    };
-                    sorted_map.insert(Value::String(key), sorted_value);
-                }
-            }
+        // Collect all keys and sort them
+        let mut keys: Vec<String> = map
+            .keys()
+            .filter_map(|k| k.as_str().map(String::from))
+            .collect();
+        keys.sort();
 
-            // Serialize the sorted mapping
-            serde_yaml::to_string(&Value::Mapping(sorted_map))
-                .map_err(|e| YamlFrontMatterError::Serialize(e.to_string()))
-        } else {
-            Err(YamlFrontMatterError::Serialize(
-                "Expected a mapping".to_string(),
-            ))
+        // Rebuild mapping in sorted order
+        for key in keys {
+            if let Some(value) = map.get(Value::String(key.clone())) {
+                // Sort sequence/list values if present
+                let sorted_value = match value {
+                    Value::Sequence(seq) => {
+                        let mut sorted_seq: Vec<String> = seq
+                            .iter()
+                            .filter_map(|v| v.as_str().map(String::from))
+                            .collect();
+                        sorted_seq.sort();
+                        Value::Sequence(sorted_seq.into_iter().map(Value::String).collect())
+                    }
+                    _ => value.clone(),
+                };
+                sorted_map.insert(Value::String(key), sorted_value);
+            }
         }
+
+        // Serialize the sorted mapping
+        serde_yaml::to_string(&Value::Mapping(sorted_map))
+            .map_err(|e| YamlFrontMatterError::Serialize(e.to_string()))
+    } else {
+        Err(YamlFrontMatterError::Serialize(
+            "Expected a mapping".to_string(),
+        ))
     }
 }
 
@@ -217,7 +222,6 @@ pub fn find_yaml_section(content: &str) -> Result<Option<(&str, &str)>, YamlFron
 mod tests {
     use super::*;
     use crate::test_utils::assert_result;
-    use crate::yaml_frontmatter_struct;
     use serde::{Deserialize, Serialize};
     use std::cmp::PartialEq;
 